@@ -1,12 +1,12 @@
 use std::fs;
 use std::path::Path;
 
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::{Diagnostic, Label, Severity};
 use codespan_reporting::term::{self, termcolor};
 use termcolor::{ColorChoice, StandardStream};
 use typst::diag::{bail, SourceError, StrResult};
 use typst::doc::Document;
-use typst::eval::eco_format;
+use typst::eval::{eco_format, Tracer};
 use typst::file::FileId;
 use typst::geom::Color;
 use typst::syntax::Source;
@@ -47,13 +47,17 @@ pub fn compile_once(
     world.reset();
     world.source(world.main()).map_err(|err| err.to_string())?;
 
-    let result = typst::compile(world);
+    let mut tracer = Tracer::default();
+    let result = typst::compile_with_tracer(world, &mut tracer);
     let duration = start.elapsed();
+    let warnings = tracer.warnings();
 
     match result {
         // Export the PDF / PNG.
         Ok(document) => {
             export(&document, command)?;
+            print_diagnostics(world, &[], warnings, command.diagnostic_format)
+                .map_err(|_| "failed to print diagnostics")?;
 
             tracing::info!("Compilation succeeded in {duration:?}");
             if watching {
@@ -74,7 +78,7 @@ pub fn compile_once(
                 Status::Error.print(command).unwrap();
             }
 
-            print_diagnostics(world, *errors, command.diagnostic_format)
+            print_diagnostics(world, &errors, warnings, command.diagnostic_format)
                 .map_err(|_| "failed to print diagnostics")?;
         }
     }
@@ -144,7 +148,8 @@ fn open_file(open: Option<&str>, path: &Path) -> StrResult<()> {
 /// Print diagnostic messages to the terminal.
 fn print_diagnostics(
     world: &SystemWorld,
-    errors: Vec<SourceError>,
+    errors: &[SourceError],
+    warnings: &[SourceError],
     diagnostic_format: DiagnosticFormat,
 ) -> Result<(), codespan_reporting::files::Error> {
     let mut w = match diagnostic_format {
@@ -157,10 +162,15 @@ fn print_diagnostics(
         config.display_style = term::DisplayStyle::Short;
     }
 
-    for error in errors {
+    let diagnostics = errors
+        .iter()
+        .map(|error| (Severity::Error, error))
+        .chain(warnings.iter().map(|warning| (Severity::Warning, warning)));
+
+    for (severity, error) in diagnostics {
         // The main diagnostic.
-        let diag = Diagnostic::error()
-            .with_message(error.message)
+        let diag = Diagnostic::new(severity)
+            .with_message(error.message.clone())
             .with_notes(
                 error
                     .hints
@@ -173,7 +183,7 @@ fn print_diagnostics(
         term::emit(&mut w, &config, world, &diag)?;
 
         // Stacktrace-like helper diagnostics.
-        for point in error.trace {
+        for point in &error.trace {
             let message = point.v.to_string();
             let help = Diagnostic::help().with_message(message).with_labels(vec![
                 Label::primary(point.span.id(), point.span.range(world)),
@@ -22,6 +22,14 @@ pub fn fonts(command: FontsCommand) -> StrResult<()> {
             for info in infos {
                 let FontVariant { style, weight, stretch } = info.variant;
                 println!("- Style: {style:?}, Weight: {weight:?}, Stretch: {stretch:?}");
+                for instance in &info.instances {
+                    let FontVariant { style, weight, stretch } = instance.variant;
+                    println!(
+                        "  - Instance {:?}: Style: {style:?}, Weight: {weight:?}, \
+                         Stretch: {stretch:?}",
+                        instance.name,
+                    );
+                }
             }
         }
     }
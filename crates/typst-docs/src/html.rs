@@ -319,8 +319,11 @@ impl<'a> Handler<'a> {
             let name = parts.next()?;
 
             let value = focus.get(name).ok()?;
-            let Value::Func(func) = value else { return None };
-            let info = func.info()?;
+            let info = match value {
+                Value::Func(func) => func.info()?,
+                Value::Type(ty) => ty.constructor_info()?,
+                _ => return None,
+            };
             route.push_str(info.category);
             route.push('/');
 
@@ -251,7 +251,14 @@ fn category_page(resolver: &dyn Resolver, category: &str) -> PageModel {
 
     // Add functions.
     for (_, value) in focus.scope().iter() {
-        let Value::Func(func) = value else { continue };
+        let func = match value {
+            Value::Func(func) => func.clone(),
+            Value::Type(ty) => match ty.constructor() {
+                Ok(func) => func,
+                Err(_) => continue,
+            },
+            _ => continue,
+        };
         let Some(info) = func.info() else { continue };
         if info.category != category {
             continue;
@@ -266,7 +273,7 @@ fn category_page(resolver: &dyn Resolver, category: &str) -> PageModel {
             continue;
         }
 
-        let subpage = function_page(resolver, &route, func, info, parents);
+        let subpage = function_page(resolver, &route, &func, info, parents);
         items.push(CategoryItem {
             name: info.name.into(),
             route: subpage.route.clone(),
@@ -954,6 +961,7 @@ const TYPE_ORDER: &[&str] = &[
     "boolean",
     "integer",
     "float",
+    "decimal",
     "length",
     "angle",
     "ratio",
@@ -961,7 +969,9 @@ const TYPE_ORDER: &[&str] = &[
     "fraction",
     "color",
     "datetime",
+    "duration",
     "string",
+    "bytes",
     "regex",
     "label",
     "content",
@@ -969,6 +979,7 @@ const TYPE_ORDER: &[&str] = &[
     "dictionary",
     "function",
     "arguments",
+    "type",
     "location",
     "dir",
     "alignment",
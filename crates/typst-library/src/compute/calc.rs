@@ -4,7 +4,7 @@ use std::cmp;
 use std::cmp::Ordering;
 use std::ops::{Div, Rem};
 
-use typst::eval::{Module, Scope};
+use typst::eval::{Decimal, Module, RoundingMode, Scope};
 
 use crate::prelude::*;
 
@@ -752,13 +752,16 @@ pub fn fract(
 
 /// Rounds a number to the nearest integer.
 ///
-/// Optionally, a number of decimal places can be specified.
+/// Optionally, a number of decimal places can be specified. When rounding a
+/// [decimal]($func/decimal), the result has exactly this many decimal places.
 ///
 /// ## Example { #example }
 /// ```example
 /// #assert(calc.round(3.14) == 3)
 /// #assert(calc.round(3.5) == 4)
-/// #calc.round(3.1415, digits: 2)
+/// #calc.round(3.1415, digits: 2) \
+/// #calc.round(decimal("2.5"), digits: 2) \
+/// #calc.round(decimal("2.345"), digits: 2, mode: "half-even")
 /// ```
 ///
 /// Display: Round
@@ -766,20 +769,44 @@ pub fn fract(
 #[func]
 pub fn round(
     /// The number to round.
-    value: Num,
+    value: Roundable,
     /// The number of decimal places.
     #[named]
     #[default(0)]
     digits: i64,
-) -> Num {
-    match value {
-        Num::Int(n) if digits == 0 => Num::Int(n),
-        _ => {
-            let n = value.float();
+    /// How to round the number.
+    ///
+    /// - `{"half-up"}`: Round to the nearest value. Halfway cases are rounded
+    ///   away from zero.
+    /// - `{"half-down"}`: Round to the nearest value. Halfway cases are
+    ///   rounded towards zero.
+    /// - `{"half-even"}`: Round to the nearest value. Halfway cases are
+    ///   rounded to the even neighbour. This is also known as banker's
+    ///   rounding.
+    /// - `{"up"}`: Round away from zero.
+    /// - `{"down"}`: Round towards zero.
+    /// - `{"ceiling"}`: Round towards positive infinity.
+    /// - `{"floor"}`: Round towards negative infinity.
+    ///
+    /// ```example
+    /// #calc.round(decimal("0.125"), digits: 2, mode: "half-up") \
+    /// #calc.round(decimal("0.125"), digits: 2, mode: "half-even") \
+    /// #calc.round(decimal("0.121"), digits: 2, mode: "up")
+    /// ```
+    #[named]
+    #[default]
+    mode: RoundingMode,
+) -> StrResult<Roundable> {
+    Ok(match value {
+        Roundable::Num(Num::Int(n)) if digits == 0 => Roundable::Num(Num::Int(n)),
+        Roundable::Num(n) => {
             let factor = 10.0_f64.powi(digits as i32);
-            Num::Float((n * factor).round() / factor)
+            Roundable::Num(Num::Float(mode.round_f64(n.float() * factor) / factor))
         }
-    }
+        Roundable::Decimal(n) => {
+            Roundable::Decimal(n.round(digits, mode).ok_or("value is too large")?)
+        }
+    })
 }
 
 /// Clamps a number between a minimum and maximum value.
@@ -1009,6 +1036,22 @@ cast! {
     v: f64 => Self::Float(v),
 }
 
+/// A value that can be passed to the rounding function.
+pub enum Roundable {
+    Num(Num),
+    Decimal(Decimal),
+}
+
+cast! {
+    Roundable,
+    self => match self {
+        Self::Num(v) => v.into_value(),
+        Self::Decimal(v) => v.into_value(),
+    },
+    v: Num => Self::Num(v),
+    v: Decimal => Self::Decimal(v),
+}
+
 /// A value that can be passed to a trigonometric function.
 pub enum AngleLike {
     Int(i64),
@@ -3,7 +3,9 @@ use std::str::FromStr;
 
 use time::{Month, PrimitiveDateTime};
 
-use typst::eval::{Datetime, Module, Regex};
+use typst::eval::{Datetime, Decimal, Duration, Module, Regex};
+use typst::model::UserElemFunc;
+use typst::util::Bytes;
 
 use crate::prelude::*;
 
@@ -11,6 +13,7 @@ use crate::prelude::*;
 ///
 /// - Booleans are converted to `0` or `1`.
 /// - Floats are floored to the next 64-bit integer.
+/// - Decimals are truncated to the next 64-bit integer.
 /// - Strings are parsed in base 10.
 ///
 /// ## Example { #example }
@@ -39,6 +42,7 @@ cast! {
     v: bool => Self(v as i64),
     v: i64 => Self(v),
     v: f64 => Self(v as i64),
+    v: Decimal => Self(v.to_i64().ok_or("number too large")?),
     v: EcoString => Self(v.parse().map_err(|_| eco_format!("invalid integer: {}", v))?),
 }
 
@@ -46,6 +50,7 @@ cast! {
 ///
 /// - Booleans are converted to `0.0` or `1.0`.
 /// - Integers are converted to the closest 64-bit float.
+/// - Decimals are converted to the closest 64-bit float.
 /// - Ratios are divided by 100%.
 /// - Strings are parsed in base 10 to the closest 64-bit float.
 ///   Exponential notation is supported.
@@ -78,10 +83,57 @@ cast! {
     v: bool => Self(v as i64 as f64),
     v: i64 => Self(v as f64),
     v: f64 => Self(v),
+    v: Decimal => Self(v.to_f64()),
     v: Ratio => Self(v.get()),
     v: EcoString => Self(v.parse().map_err(|_| eco_format!("invalid float: {}", v))?),
 }
 
+/// Converts a value to a decimal.
+///
+/// Decimals store numbers in base 10 and compute with them exactly, which
+/// makes them suitable for financial documents, where the rounding errors of
+/// [floats]($type/float) are not acceptable. They support up to 28 digits
+/// after the decimal point and are never displayed in exponential notation.
+///
+/// - Integers are converted exactly.
+/// - Floats are converted to the closest decimal. As floats are inexact to
+///   begin with, prefer constructing decimals from strings.
+/// - Strings are parsed in base 10 without any loss of precision. Exponential
+///   notation is not supported.
+///
+/// Decimals can be added, subtracted, multiplied and divided with each other
+/// and with integers. To round them to a fixed number of decimal places, use
+/// [`calc.round`]($func/calc.round), which supports several rounding modes.
+///
+/// ## Example { #example }
+/// ```example
+/// #(0.1 + 0.2) \
+/// #(decimal("0.1") + decimal("0.2")) \
+/// #(decimal("19.99") * 3) \
+/// #calc.round(decimal("2.345"), digits: 2)
+/// ```
+///
+/// Display: Decimal
+/// Category: construct
+#[func]
+pub fn decimal(
+    /// The value that should be converted to a decimal.
+    value: ToDecimal,
+) -> Decimal {
+    value.0
+}
+
+/// A value that can be cast to a decimal.
+pub struct ToDecimal(Decimal);
+
+cast! {
+    ToDecimal,
+    v: i64 => Self(v.into()),
+    v: f64 => Self(Decimal::from_f64(v).ok_or("number cannot be represented as a decimal")?),
+    v: Decimal => Self(v),
+    v: EcoString => Self(Decimal::parse(&v)?),
+}
+
 /// Creates a grayscale color.
 ///
 /// ## Example { #example }
@@ -211,11 +263,25 @@ cast! {
 /// Depending on how it is stored, the [`display`]($type/datetime.display)
 /// method will choose a different formatting by default.
 ///
+/// ## Arithmetic { #arithmetic }
+/// You can add [durations]($func/duration) to and subtract them from
+/// datetimes. Subtracting two datetimes of the same kind yields the duration
+/// between them. Datetimes of the same kind can also be compared.
+///
+/// ```example
+/// #let start = datetime(year: 2023, month: 3, day: 28)
+/// #let due = start + duration(weeks: 2)
+/// #due.display() \
+/// #(due - start).days() days
+/// ```
+///
 /// Display: Datetime
 /// Category: construct
 #[func]
 #[scope(
     scope.define("today", datetime_today_func());
+    scope.define("from-iso", datetime_from_iso_func());
+    scope.define("parse", datetime_parse_func());
     scope
 )]
 pub fn datetime(
@@ -338,6 +404,114 @@ pub fn datetime_today(
         .ok_or("unable to get the current date")?)
 }
 
+/// Parses a datetime from an ISO 8601 string.
+///
+/// Supports dates like `{"2023-05-01"}`, times like `{"10:30:00"}` or
+/// `{"10:30"}`, and full datetimes like `{"2023-05-01T10:30:00"}`. Full
+/// datetimes may end with a UTC offset, such as `{"Z"}` or `{"+02:00"}`.
+///
+/// ## Example
+/// ```example
+/// #datetime.from-iso("2023-05-01").display() \
+/// #datetime.from-iso("2023-05-01T10:30:00Z", offset: 2).display()
+/// ```
+///
+/// Display: From ISO
+/// Category: construct
+#[func]
+pub fn datetime_from_iso(
+    /// The ISO 8601 string to parse.
+    text: EcoString,
+    /// The UTC offset to convert the datetime to, in hours. Datetimes without
+    /// an offset are assumed to be in UTC. If set to `{auto}`, the time is
+    /// kept as written.
+    #[named]
+    #[default]
+    offset: Smart<i64>,
+) -> StrResult<Datetime> {
+    Datetime::from_iso(&text, offset.as_custom())
+}
+
+/// Parses a datetime from a string in a custom format.
+///
+/// The format uses the same syntax as the
+/// [`display`]($type/datetime.display) method. Depending on the components
+/// in the format, the result is a date, a time, or a full datetime.
+///
+/// ## Example
+/// ```example
+/// #let date = datetime.parse(
+///   "03.08.2012",
+///   "[day].[month].[year]",
+/// )
+/// #date.display()
+/// ```
+///
+/// Display: Parse
+/// Category: construct
+#[func]
+pub fn datetime_parse(
+    /// The string to parse.
+    text: EcoString,
+    /// The format of the string.
+    format: EcoString,
+    /// The UTC offset to convert the datetime to, in hours. This only has an
+    /// effect for full datetimes, which are assumed to be in UTC unless the
+    /// format contains an offset. If set to `{auto}`, the time is kept as
+    /// written.
+    #[named]
+    #[default]
+    offset: Smart<i64>,
+) -> StrResult<Datetime> {
+    Datetime::parse(&text, &format, offset.as_custom())
+}
+
+/// Creates a new duration.
+///
+/// You can specify the [duration]($type/duration) using weeks, days, hours,
+/// minutes, and seconds. All of them default to zero and may be negative.
+/// Durations can be added to and subtracted from each other and from
+/// [datetimes]($type/datetime), multiplied and divided by numbers, and
+/// compared with each other.
+///
+/// ## Example
+/// ```example
+/// #let sprint = duration(weeks: 2)
+/// #let review = duration(days: 1, hours: 12)
+/// #(sprint + review).days() days \
+/// #(datetime(year: 2023, month: 1, day: 30)
+///   + 3 * sprint).display()
+/// ```
+///
+/// Display: Duration
+/// Category: construct
+#[func]
+pub fn duration(
+    /// The number of seconds.
+    #[named]
+    #[default(0)]
+    seconds: i64,
+    /// The number of minutes.
+    #[named]
+    #[default(0)]
+    minutes: i64,
+    /// The number of hours.
+    #[named]
+    #[default(0)]
+    hours: i64,
+    /// The number of days.
+    #[named]
+    #[default(0)]
+    days: i64,
+    /// The number of weeks.
+    #[named]
+    #[default(0)]
+    weeks: i64,
+) -> StrResult<Duration> {
+    Duration::new(weeks, days, hours, minutes, seconds)
+        .ok_or_else(|| "duration is too large".into())
+}
+
 /// Creates a CMYK color.
 ///
 /// This is useful if you want to target a specific printer. The conversion
@@ -485,7 +659,10 @@ cast! {
 /// - Integers are formatted in base 10. This can be overridden with the
 ///   optional `base` parameter.
 /// - Floats are formatted in base 10 and never in exponential notation.
+/// - Decimals are formatted in base 10 with all their decimal places.
 /// - From labels the name is extracted.
+/// - Bytes are decoded as UTF-8.
+/// - From types the name is extracted.
 ///
 /// If you wish to convert from and to Unicode code points, see
 /// [`str.to-unicode`]($func/str.to-unicode) and
@@ -544,7 +721,14 @@ cast! {
     ToStr,
     v: i64 => Self::Int(v),
     v: f64 => Self::Str(format_str!("{}", v)),
+    v: Decimal => Self::Str(format_str!("{}", v)),
     v: Label => Self::Str(v.0.into()),
+    v: Type => Self::Str(v.name().into()),
+    v: Bytes => Self::Str(
+        std::str::from_utf8(&v)
+            .map_err(|_| "bytes are not valid utf-8")?
+            .into()
+    ),
     v: Str => Self::Str(v),
 }
 
@@ -633,6 +817,47 @@ cast! {
     },
 }
 
+/// Converts a value to bytes.
+///
+/// - Strings are encoded in UTF-8.
+/// - Arrays of integers between `{0}` and `{255}` are converted directly.
+///
+/// ## Example { #example }
+/// ```example
+/// #bytes("Hello 😃") \
+/// #bytes((123, 160, 22, 0)) \
+/// #bytes("Hello").slice(1, 3).len()
+/// ```
+///
+/// Display: Bytes
+/// Category: construct
+#[func]
+pub fn bytes(
+    /// The value that should be converted to bytes.
+    value: ToBytes,
+) -> Bytes {
+    value.0
+}
+
+/// A value that can be cast to bytes.
+pub struct ToBytes(Bytes);
+
+cast! {
+    ToBytes,
+    v: Str => Self(v.as_bytes().into()),
+    v: Array => Self(
+        v.into_iter()
+            .map(|item| match item {
+                Value::Int(byte @ 0..=255) => Ok(byte as u8),
+                Value::Int(_) => bail!("number must be between 0 and 255"),
+                value => bail!("expected integer, found {}", value.type_name()),
+            })
+            .collect::<StrResult<Vec<u8>>>()?
+            .into()
+    ),
+    v: Bytes => Self(v),
+}
+
 /// Creates a label from a string.
 ///
 /// Inserting a label into content attaches it to the closest previous element
@@ -662,6 +887,74 @@ pub fn label(
     Label(name)
 }
 
+/// Defines a new element.
+///
+/// User-defined elements work just like built-in ones: Calling the resulting
+/// element function produces content with the given fields, which you can
+/// access on the content and configure with set rules. Show rules, selectors,
+/// [queries]($func/query) and [counters]($func/counter) work with them, too.
+///
+/// When no show rule transforms an element, it is shown by calling the
+/// `display` function with the element. At that point, all fields that were
+/// not given when constructing the element are filled in from the active set
+/// rules or the fields' defaults.
+///
+/// ## Example { #example }
+/// ```example
+/// #let callout = element(
+///   "callout",
+///   it => block(
+///     fill: if it.kind == "warn" { yellow } else { aqua },
+///     inset: 8pt,
+///     it.body,
+///   ),
+///   fields: (
+///     body: (type: content, positional: true, required: true),
+///     kind: (type: str, default: "info"),
+///   ),
+/// )
+///
+/// #callout[Hello]
+/// #set callout(kind: "warn")
+/// #callout[Careful]
+/// #show callout: set text(red)
+/// #callout(kind: "info")[Hi]
+/// ```
+///
+/// Display: Element
+/// Category: construct
+#[func]
+pub fn element(
+    /// The name of the element.
+    name: Spanned<EcoString>,
+    /// The function that shows the element. It receives the element as
+    /// content and should return what the element looks like.
+    display: Func,
+    /// The element's fields.
+    ///
+    /// Each field is specified by a dictionary with the following optional
+    /// keys:
+    ///
+    /// - `type`: The [type]($type) the field accepts or an array of types.
+    ///   Type names are accepted, too. A field accepting `{content}` also
+    ///   accepts strings and symbols. If omitted, the field accepts any value.
+    /// - `default`: The value the field takes if it is neither given nor set.
+    ///   Defaults to `{none}`.
+    /// - `positional`: Whether the field is given positionally instead of by
+    ///   name. Defaults to `{false}`.
+    /// - `required`: Whether the field must be given when constructing the
+    ///   element. Required fields cannot be configured with set rules and
+    ///   cannot have a default. Defaults to `{false}`.
+    ///
+    /// Positional fields are filled in the order in which they are specified.
+    #[named]
+    #[default(Spanned::new(Dict::new(), Span::detached()))]
+    fields: Spanned<Dict>,
+) -> SourceResult<ElemFunc> {
+    let span = if fields.span.is_detached() { name.span } else { fields.span };
+    UserElemFunc::define(name.v, display, fields.v).at(span)
+}
+
 /// Creates a regular expression from a string.
 ///
 /// The result can be used as a
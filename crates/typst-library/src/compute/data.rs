@@ -1,11 +1,16 @@
 use typst::diag::{format_xml_like_error, FileError};
 use typst::eval::Datetime;
+use typst::util::Bytes;
 
 use crate::prelude::*;
 
-/// Reads plain text from a file.
+/// Reads plain text or data from a file.
 ///
-/// The file will be read and returned as a string.
+/// By default, the file will be read as UTF-8 and returned as a
+/// [string]($type/string).
+///
+/// If you specify `{encoding: none}`, this returns raw [bytes]($type/bytes)
+/// instead.
 ///
 /// ## Example { #example }
 /// ```example
@@ -13,6 +18,9 @@ use crate::prelude::*;
 ///
 /// An example for a HTML file:\
 /// #raw(text, lang: "html")
+///
+/// Raw bytes:
+/// #read("tiger.jpg", encoding: none)
 /// ```
 ///
 /// Display: Read
@@ -21,16 +29,34 @@ use crate::prelude::*;
 pub fn read(
     /// Path to a file.
     path: Spanned<EcoString>,
+    /// The encoding to read the file with.
+    ///
+    /// If set to `{none}`, this function returns raw bytes.
+    #[named]
+    #[default(Some(Encoding::Utf8))]
+    encoding: Option<Encoding>,
     /// The virtual machine.
     vm: &mut Vm,
-) -> SourceResult<Str> {
+) -> SourceResult<Readable> {
     let Spanned { v: path, span } = path;
     let id = vm.location().join(&path).at(span)?;
     let data = vm.world().file(id).at(span)?;
-    let text = std::str::from_utf8(&data)
-        .map_err(|_| "file is not valid utf-8")
-        .at(span)?;
-    Ok(text.into())
+    Ok(match encoding {
+        None => Readable::Bytes(data),
+        Some(Encoding::Utf8) => Readable::Str(
+            std::str::from_utf8(&data)
+                .map_err(|_| "file is not valid utf-8")
+                .at(span)?
+                .into(),
+        ),
+    })
+}
+
+/// An encoding of a file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum Encoding {
+    /// The Unicode UTF-8 encoding.
+    Utf8,
 }
 
 /// Reads structured data from a CSV file.
@@ -54,6 +80,10 @@ pub fn read(
 /// Display: CSV
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("decode", csv_decode_func());
+    scope
+)]
 pub fn csv(
     /// Path to a CSV file.
     path: Spanned<EcoString>,
@@ -68,7 +98,33 @@ pub fn csv(
     let Spanned { v: path, span } = path;
     let id = vm.location().join(&path).at(span)?;
     let data = vm.world().file(id).at(span)?;
+    csv_decode(Spanned::new(Readable::Bytes(data), span), delimiter)
+}
 
+/// Reads structured data from a CSV string or bytes.
+///
+/// Works like the [`csv`]($func/csv) function, but takes the data directly
+/// instead of a path.
+///
+/// ## Example { #example }
+/// ```example
+/// #let rows = csv.decode("a;b\n1;2", delimiter: ";")
+/// #rows.at(1).join(" and ")
+/// ```
+///
+/// Display: Decode CSV
+/// Category: data-loading
+#[func]
+pub fn csv_decode(
+    /// CSV data.
+    data: Spanned<Readable>,
+    /// The delimiter that separates columns in the CSV data.
+    /// Must be a single ASCII character.
+    #[named]
+    #[default]
+    delimiter: Delimiter,
+) -> SourceResult<Array> {
+    let Spanned { v: data, span } = data;
     let mut builder = csv::ReaderBuilder::new();
     builder.has_headers(false);
     builder.delimiter(delimiter.0 as u8);
@@ -170,6 +226,11 @@ fn format_csv_error(error: csv::Error, line: usize) -> EcoString {
 /// Display: JSON
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("decode", json_decode_func());
+    scope.define("encode", json_encode_func());
+    scope
+)]
 pub fn json(
     /// Path to a JSON file.
     path: Spanned<EcoString>,
@@ -179,11 +240,70 @@ pub fn json(
     let Spanned { v: path, span } = path;
     let id = vm.location().join(&path).at(span)?;
     let data = vm.world().file(id).at(span)?;
-    let value: serde_json::Value =
-        serde_json::from_slice(&data).map_err(format_json_error).at(span)?;
+    json_decode(Spanned::new(Readable::Bytes(data), span))
+}
+
+/// Reads structured data from a JSON string or bytes.
+///
+/// Works like the [`json`]($func/json) function, but takes the data directly
+/// instead of a path.
+///
+/// ## Example { #example }
+/// ```example
+/// #let data = json.decode("{\"name\": \"Debby\", \"age\": 4}")
+/// #data.name is #data.age years old.
+/// ```
+///
+/// Display: Decode JSON
+/// Category: data-loading
+#[func]
+pub fn json_decode(
+    /// JSON data.
+    data: Spanned<Readable>,
+) -> SourceResult<Value> {
+    let Spanned { v: data, span } = data;
+    let value: serde_json::Value = serde_json::from_slice(data.as_slice())
+        .map_err(format_json_error)
+        .at(span)?;
     Ok(convert_json(value))
 }
 
+/// Encodes structured data into a JSON string.
+///
+/// Dictionaries become objects, arrays become arrays, and `{none}` becomes
+/// `null`. Datetimes are encoded as strings. Values that have no JSON
+/// equivalent, like content or functions, cannot be encoded.
+///
+/// ## Example { #example }
+/// ```example
+/// #json.encode((name: "Debby", age: 4), pretty: false)
+/// ```
+///
+/// Display: Encode JSON
+/// Category: data-loading
+#[func]
+pub fn json_encode(
+    /// The value to encode.
+    value: Spanned<Value>,
+    /// Whether to pretty print the JSON with newlines and indentation.
+    #[named]
+    #[default(true)]
+    pretty: bool,
+) -> SourceResult<Str> {
+    let Spanned { v: value, span } = value;
+    let value = encode_json(value).at(span)?;
+    let text = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+
+    Ok(text
+        .map_err(|err| eco_format!("failed to encode value as json ({err})"))
+        .at(span)?
+        .into())
+}
+
 /// Convert a JSON value to a Typst value.
 fn convert_json(value: serde_json::Value) -> Value {
     match value {
@@ -205,6 +325,25 @@ fn convert_json(value: serde_json::Value) -> Value {
     }
 }
 
+/// Convert a Typst value to a JSON value.
+fn encode_json(value: Value) -> StrResult<serde_json::Value> {
+    Ok(match value {
+        Value::None => serde_json::Value::Null,
+        Value::Bool(v) => v.into(),
+        Value::Int(v) => v.into(),
+        Value::Float(v) => v.into(),
+        Value::Str(v) => v.as_str().into(),
+        Value::Symbol(v) => v.get().to_string().into(),
+        Value::Array(v) => v.into_iter().map(encode_json).collect::<StrResult<_>>()?,
+        Value::Dict(v) => serde_json::Value::Object(
+            v.into_iter()
+                .map(|(key, value)| Ok((key.into(), encode_json(value)?)))
+                .collect::<StrResult<_>>()?,
+        ),
+        v => encode_datetime(&v, "json")?.as_str().into(),
+    })
+}
+
 /// Format the user-facing JSON error message.
 fn format_json_error(error: serde_json::Error) -> EcoString {
     assert!(error.is_syntax() || error.is_eof());
@@ -236,6 +375,11 @@ fn format_json_error(error: serde_json::Error) -> EcoString {
 /// Display: TOML
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("decode", toml_decode_func());
+    scope.define("encode", toml_encode_func());
+    scope
+)]
 pub fn toml(
     /// Path to a TOML file.
     path: Spanned<EcoString>,
@@ -245,8 +389,29 @@ pub fn toml(
     let Spanned { v: path, span } = path;
     let id = vm.location().join(&path).at(span)?;
     let data = vm.world().file(id).at(span)?;
+    toml_decode(Spanned::new(Readable::Bytes(data), span))
+}
 
-    let raw = std::str::from_utf8(&data)
+/// Reads structured data from a TOML string or bytes.
+///
+/// Works like the [`toml`]($func/toml) function, but takes the data directly
+/// instead of a path.
+///
+/// ## Example { #example }
+/// ```example
+/// #let data = toml.decode("title = \"Report\"\nversion = 2")
+/// #data.title, version #data.version
+/// ```
+///
+/// Display: Decode TOML
+/// Category: data-loading
+#[func]
+pub fn toml_decode(
+    /// TOML data.
+    data: Spanned<Readable>,
+) -> SourceResult<Value> {
+    let Spanned { v: data, span } = data;
+    let raw = std::str::from_utf8(data.as_slice())
         .map_err(|_| "file is not valid utf-8")
         .at(span)?;
 
@@ -254,6 +419,43 @@ pub fn toml(
     Ok(convert_toml(value))
 }
 
+/// Encodes structured data into a TOML string.
+///
+/// The value must be a dictionary, which becomes the top-level table. Since
+/// TOML has no null value, dictionaries and arrays must not contain `{none}`.
+///
+/// ## Example { #example }
+/// ```example
+/// #raw(toml.encode((title: "Report", version: 2)), lang: "toml")
+/// ```
+///
+/// Display: Encode TOML
+/// Category: data-loading
+#[func]
+pub fn toml_encode(
+    /// The dictionary to encode.
+    value: Spanned<Value>,
+    /// Whether to pretty print the TOML. This puts every element of an array
+    /// on its own line.
+    #[named]
+    #[default(true)]
+    pretty: bool,
+) -> SourceResult<Str> {
+    let Spanned { v: value, span } = value;
+    if !matches!(value, Value::Dict(_)) {
+        bail!(span, "expected dictionary, found {}", value.type_name());
+    }
+
+    let value = encode_toml(value).at(span)?;
+    let text =
+        if pretty { toml::to_string_pretty(&value) } else { toml::to_string(&value) };
+
+    Ok(text
+        .map_err(|err| eco_format!("failed to encode value as toml ({err})"))
+        .at(span)?
+        .into())
+}
+
 /// Convert a TOML value to a Typst value.
 fn convert_toml(value: toml::Value) -> Value {
     match value {
@@ -290,6 +492,51 @@ fn convert_toml(value: toml::Value) -> Value {
     }
 }
 
+/// Convert a Typst value to a TOML value.
+fn encode_toml(value: Value) -> StrResult<toml::Value> {
+    Ok(match value {
+        Value::Bool(v) => v.into(),
+        Value::Int(v) => v.into(),
+        Value::Float(v) => v.into(),
+        Value::Str(v) => v.as_str().into(),
+        Value::Symbol(v) => v.get().to_string().into(),
+        Value::Array(v) => {
+            toml::Value::Array(v.into_iter().map(encode_toml).collect::<StrResult<_>>()?)
+        }
+        Value::Dict(v) => toml::Value::Table(
+            v.into_iter()
+                .map(|(key, value)| Ok((key.into(), encode_toml(value)?)))
+                .collect::<StrResult<_>>()?,
+        ),
+        Value::Dyn(ref dynamic) => match dynamic.downcast::<Datetime>() {
+            Some(datetime) => toml::Value::Datetime(encode_toml_datetime(datetime)?),
+            None => return Err(unencodable(&value, "toml")),
+        },
+        v => return Err(unencodable(&v, "toml")),
+    })
+}
+
+/// Convert a Typst datetime to a TOML datetime.
+fn encode_toml_datetime(datetime: &Datetime) -> StrResult<toml::value::Datetime> {
+    let date = match (datetime.year(), datetime.month(), datetime.day()) {
+        (Some(year), Some(month), Some(day)) => Some(toml::value::Date {
+            year: u16::try_from(year).map_err(|_| "year is out of range for toml")?,
+            month,
+            day,
+        }),
+        _ => None,
+    };
+
+    let time = match (datetime.hour(), datetime.minute(), datetime.second()) {
+        (Some(hour), Some(minute), Some(second)) => {
+            Some(toml::value::Time { hour, minute, second, nanosecond: 0 })
+        }
+        _ => None,
+    };
+
+    Ok(toml::value::Datetime { date, time, offset: None })
+}
+
 /// Format the user-facing TOML error message.
 fn format_toml_error(error: toml::de::Error) -> EcoString {
     if let Some(range) = error.span() {
@@ -345,6 +592,11 @@ fn format_toml_error(error: toml::de::Error) -> EcoString {
 /// Display: YAML
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("decode", yaml_decode_func());
+    scope.define("encode", yaml_encode_func());
+    scope
+)]
 pub fn yaml(
     /// Path to a YAML file.
     path: Spanned<EcoString>,
@@ -354,11 +606,59 @@ pub fn yaml(
     let Spanned { v: path, span } = path;
     let id = vm.location().join(&path).at(span)?;
     let data = vm.world().file(id).at(span)?;
-    let value: serde_yaml::Value =
-        serde_yaml::from_slice(&data).map_err(format_yaml_error).at(span)?;
+    yaml_decode(Spanned::new(Readable::Bytes(data), span))
+}
+
+/// Reads structured data from a YAML string or bytes.
+///
+/// Works like the [`yaml`]($func/yaml) function, but takes the data directly
+/// instead of a path.
+///
+/// ## Example { #example }
+/// ```example
+/// #let data = yaml.decode("tags: [draft, internal]")
+/// #data.tags.join(", ")
+/// ```
+///
+/// Display: Decode YAML
+/// Category: data-loading
+#[func]
+pub fn yaml_decode(
+    /// YAML data.
+    data: Spanned<Readable>,
+) -> SourceResult<Value> {
+    let Spanned { v: data, span } = data;
+    let value: serde_yaml::Value = serde_yaml::from_slice(data.as_slice())
+        .map_err(format_yaml_error)
+        .at(span)?;
     Ok(convert_yaml(value))
 }
 
+/// Encodes structured data into a YAML string.
+///
+/// Dictionaries become mappings, arrays become sequences, and `{none}`
+/// becomes `null`. Datetimes are encoded as strings.
+///
+/// ## Example { #example }
+/// ```example
+/// #raw(yaml.encode((tags: ("draft", "internal"))), lang: "yaml")
+/// ```
+///
+/// Display: Encode YAML
+/// Category: data-loading
+#[func]
+pub fn yaml_encode(
+    /// The value to encode.
+    value: Spanned<Value>,
+) -> SourceResult<Str> {
+    let Spanned { v: value, span } = value;
+    let value = encode_yaml(value).at(span)?;
+    Ok(serde_yaml::to_string(&value)
+        .map_err(|err| eco_format!("failed to encode value as yaml ({err})"))
+        .at(span)?
+        .into())
+}
+
 /// Convert a YAML value to a Typst value.
 fn convert_yaml(value: serde_yaml::Value) -> Value {
     match value {
@@ -391,11 +691,173 @@ fn convert_yaml_key(key: serde_yaml::Value) -> Option<Str> {
     }
 }
 
+/// Convert a Typst value to a YAML value.
+fn encode_yaml(value: Value) -> StrResult<serde_yaml::Value> {
+    Ok(match value {
+        Value::None => serde_yaml::Value::Null,
+        Value::Bool(v) => serde_yaml::Value::Bool(v),
+        Value::Int(v) => serde_yaml::Value::Number(v.into()),
+        Value::Float(v) => serde_yaml::Value::Number(v.into()),
+        Value::Str(v) => serde_yaml::Value::String(v.into()),
+        Value::Symbol(v) => serde_yaml::Value::String(v.get().to_string()),
+        Value::Array(v) => serde_yaml::Value::Sequence(
+            v.into_iter().map(encode_yaml).collect::<StrResult<_>>()?,
+        ),
+        Value::Dict(v) => serde_yaml::Value::Mapping(
+            v.into_iter()
+                .map(|(key, value)| {
+                    Ok((serde_yaml::Value::String(key.into()), encode_yaml(value)?))
+                })
+                .collect::<StrResult<_>>()?,
+        ),
+        v => serde_yaml::Value::String(encode_datetime(&v, "yaml")?.into()),
+    })
+}
+
 /// Format the user-facing YAML error message.
 fn format_yaml_error(error: serde_yaml::Error) -> EcoString {
     eco_format!("failed to parse yaml file: {}", error.to_string().trim())
 }
 
+/// Reads structured data from a CBOR file.
+///
+/// CBOR is a compact binary format for JSON-like data. Maps will be converted
+/// into Typst dictionaries and arrays into Typst arrays. Byte strings will be
+/// converted into bytes and the remaining values into their Typst
+/// equivalents, like for the [`json`]($func/json) function.
+///
+/// Note that map keys that are not strings cause the entry to be discarded.
+///
+/// Display: CBOR
+/// Category: data-loading
+#[func]
+#[scope(
+    scope.define("decode", cbor_decode_func());
+    scope.define("encode", cbor_encode_func());
+    scope
+)]
+pub fn cbor(
+    /// Path to a CBOR file.
+    path: Spanned<EcoString>,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> SourceResult<Value> {
+    let Spanned { v: path, span } = path;
+    let id = vm.location().join(&path).at(span)?;
+    let data = vm.world().file(id).at(span)?;
+    cbor_decode(Spanned::new(data, span))
+}
+
+/// Reads structured data from CBOR bytes.
+///
+/// Works like the [`cbor`]($func/cbor) function, but takes the data directly
+/// instead of a path.
+///
+/// Display: Decode CBOR
+/// Category: data-loading
+#[func]
+pub fn cbor_decode(
+    /// CBOR data.
+    data: Spanned<Bytes>,
+) -> SourceResult<Value> {
+    let Spanned { v: data, span } = data;
+    let value: ciborium::value::Value = ciborium::de::from_reader(data.as_slice())
+        .map_err(format_cbor_error)
+        .at(span)?;
+    Ok(convert_cbor(value))
+}
+
+/// Encodes structured data into CBOR bytes.
+///
+/// Dictionaries become maps, arrays become arrays, bytes become byte strings,
+/// and `{none}` becomes `null`. Datetimes are encoded as strings.
+///
+/// Display: Encode CBOR
+/// Category: data-loading
+#[func]
+pub fn cbor_encode(
+    /// The value to encode.
+    value: Spanned<Value>,
+) -> SourceResult<Bytes> {
+    let Spanned { v: value, span } = value;
+    let value = encode_cbor(value).at(span)?;
+    let mut buf = vec![];
+    ciborium::ser::into_writer(&value, &mut buf)
+        .map_err(|err| eco_format!("failed to encode value as cbor ({err})"))
+        .at(span)?;
+    Ok(buf.into())
+}
+
+/// Convert a CBOR value to a Typst value.
+fn convert_cbor(value: ciborium::value::Value) -> Value {
+    match value {
+        ciborium::value::Value::Null => Value::None,
+        ciborium::value::Value::Bool(v) => v.into_value(),
+        ciborium::value::Value::Integer(v) => match i64::try_from(v) {
+            Ok(int) => int.into_value(),
+            Err(_) => (i128::from(v) as f64).into_value(),
+        },
+        ciborium::value::Value::Float(v) => v.into_value(),
+        ciborium::value::Value::Text(v) => v.into_value(),
+        ciborium::value::Value::Bytes(v) => Value::Bytes(v.into()),
+        ciborium::value::Value::Array(v) => {
+            v.into_iter().map(convert_cbor).collect::<Array>().into_value()
+        }
+        ciborium::value::Value::Map(v) => v
+            .into_iter()
+            .filter_map(|(key, value)| match key {
+                ciborium::value::Value::Text(key) => {
+                    Some((key.into(), convert_cbor(value)))
+                }
+                _ => None,
+            })
+            .collect::<Dict>()
+            .into_value(),
+        ciborium::value::Value::Tag(_, v) => convert_cbor(*v),
+        _ => Value::None,
+    }
+}
+
+/// Convert a Typst value to a CBOR value.
+fn encode_cbor(value: Value) -> StrResult<ciborium::value::Value> {
+    Ok(match value {
+        Value::None => ciborium::value::Value::Null,
+        Value::Bool(v) => ciborium::value::Value::Bool(v),
+        Value::Int(v) => ciborium::value::Value::Integer(v.into()),
+        Value::Float(v) => ciborium::value::Value::Float(v),
+        Value::Str(v) => ciborium::value::Value::Text(v.into()),
+        Value::Symbol(v) => ciborium::value::Value::Text(v.get().to_string()),
+        Value::Bytes(v) => ciborium::value::Value::Bytes(v.to_vec()),
+        Value::Array(v) => ciborium::value::Value::Array(
+            v.into_iter().map(encode_cbor).collect::<StrResult<_>>()?,
+        ),
+        Value::Dict(v) => ciborium::value::Value::Map(
+            v.into_iter()
+                .map(|(key, value)| {
+                    Ok((ciborium::value::Value::Text(key.into()), encode_cbor(value)?))
+                })
+                .collect::<StrResult<_>>()?,
+        ),
+        v => ciborium::value::Value::Text(encode_datetime(&v, "cbor")?.into()),
+    })
+}
+
+/// Format the user-facing CBOR error message.
+fn format_cbor_error(error: ciborium::de::Error<std::io::Error>) -> EcoString {
+    match error {
+        ciborium::de::Error::Io(_) => "failed to parse cbor file: unexpected end".into(),
+        ciborium::de::Error::Syntax(offset) => {
+            eco_format!("failed to parse cbor file: syntax error at byte {offset}")
+        }
+        ciborium::de::Error::Semantic(_, message) => {
+            eco_format!("failed to parse cbor file: {message}")
+        }
+        ciborium::de::Error::RecursionLimitExceeded => {
+            "failed to parse cbor file: data is nested too deeply".into()
+        }
+    }
+}
+
 /// Reads structured data from an XML file.
 ///
 /// The XML file is parsed into an array of dictionaries and strings. XML nodes
@@ -430,7 +892,7 @@ fn format_yaml_error(error: serde_yaml::Error) -> EcoString {
 ///   ]
 ///
 ///   for p in pars.children {
-///     if (type(p) == "dictionary") {
+///     if (type(p) == dictionary) {
 ///       parbreak()
 ///       p.children.first()
 ///     }
@@ -439,7 +901,7 @@ fn format_yaml_error(error: serde_yaml::Error) -> EcoString {
 ///
 /// #let data = xml("example.xml")
 /// #for elem in data.first().children {
-///   if (type(elem) == "dictionary") {
+///   if (type(elem) == dictionary) {
 ///     article(elem)
 ///   }
 /// }
@@ -448,6 +910,10 @@ fn format_yaml_error(error: serde_yaml::Error) -> EcoString {
 /// Display: XML
 /// Category: data-loading
 #[func]
+#[scope(
+    scope.define("decode", xml_decode_func());
+    scope
+)]
 pub fn xml(
     /// Path to an XML file.
     path: Spanned<EcoString>,
@@ -457,7 +923,31 @@ pub fn xml(
     let Spanned { v: path, span } = path;
     let id = vm.location().join(&path).at(span)?;
     let data = vm.world().file(id).at(span)?;
-    let text = std::str::from_utf8(&data).map_err(FileError::from).at(span)?;
+    xml_decode(Spanned::new(Readable::Bytes(data), span))
+}
+
+/// Reads structured data from an XML string or bytes.
+///
+/// Works like the [`xml`]($func/xml) function, but takes the data directly
+/// instead of a path.
+///
+/// ## Example { #example }
+/// ```example
+/// #let root = xml.decode("<note to=\"Ann\">Hi</note>").first()
+/// #root.children.first() to #root.attrs.to
+/// ```
+///
+/// Display: Decode XML
+/// Category: data-loading
+#[func]
+pub fn xml_decode(
+    /// XML data.
+    data: Spanned<Readable>,
+) -> SourceResult<Value> {
+    let Spanned { v: data, span } = data;
+    let text = std::str::from_utf8(data.as_slice())
+        .map_err(FileError::from)
+        .at(span)?;
     let document = roxmltree::Document::parse(text).map_err(format_xml_error).at(span)?;
     Ok(convert_xml(document.root()))
 }
@@ -490,3 +980,67 @@ fn convert_xml(node: roxmltree::Node) -> Value {
 fn format_xml_error(error: roxmltree::Error) -> EcoString {
     format_xml_like_error("xml file", error)
 }
+
+/// Data that can be decoded: Either a string or bytes.
+pub enum Readable {
+    /// A decoded string.
+    Str(Str),
+    /// Raw bytes.
+    Bytes(Bytes),
+}
+
+impl Readable {
+    /// The raw bytes of the data.
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Str(v) => v.as_bytes(),
+            Self::Bytes(v) => v,
+        }
+    }
+
+    /// The data as a string, decoding bytes as UTF-8.
+    pub fn into_str(self) -> StrResult<Str> {
+        match self {
+            Self::Str(v) => Ok(v),
+            Self::Bytes(v) => std::str::from_utf8(&v)
+                .map(Into::into)
+                .map_err(|_| "bytes are not valid utf-8".into()),
+        }
+    }
+}
+
+cast! {
+    Readable,
+    self => match self {
+        Self::Str(v) => v.into_value(),
+        Self::Bytes(v) => v.into_value(),
+    },
+    v: Str => Self::Str(v),
+    v: Bytes => Self::Bytes(v),
+}
+
+impl From<Readable> for Bytes {
+    fn from(readable: Readable) -> Self {
+        match readable {
+            Readable::Str(v) => v.as_bytes().into(),
+            Readable::Bytes(v) => v,
+        }
+    }
+}
+
+/// Encode a datetime as a string for formats without a native datetime type.
+/// Fails for all other values.
+fn encode_datetime(value: &Value, format: &str) -> StrResult<EcoString> {
+    match value {
+        Value::Dyn(dynamic) => match dynamic.downcast::<Datetime>() {
+            Some(datetime) => datetime.display(None),
+            None => Err(unencodable(value, format)),
+        },
+        _ => Err(unencodable(value, format)),
+    }
+}
+
+/// The error message for a value that has no equivalent in a data format.
+fn unencodable(value: &Value, format: &str) -> EcoString {
+    eco_format!("cannot encode {} as {format}", value.type_name())
+}
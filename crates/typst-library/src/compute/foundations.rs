@@ -1,8 +1,13 @@
+use typst::eval::{Module, Plugin};
+
 use crate::prelude::*;
 
 /// Determines the type of a value.
 ///
-/// Returns the name of the value's type.
+/// Returns the value's [type]($type). Types can be compared with each other
+/// and called to construct values of the type (if they have a constructor).
+/// Their methods can also be accessed like functions on the type, e.g.
+/// `{str.len}`.
 ///
 /// ## Example { #example }
 /// ```example
@@ -11,7 +16,9 @@ use crate::prelude::*;
 /// #type("hello") \
 /// #type(none) \
 /// #type([Hi]) \
-/// #type(x => x + 1)
+/// #type(x => x + 1) \
+/// #(type(12) == int) \
+/// #(type("hi") == str)
 /// ```
 ///
 /// Display: Type
@@ -20,8 +27,8 @@ use crate::prelude::*;
 pub fn type_(
     /// The value whose type's to determine.
     value: Value,
-) -> Str {
-    value.type_name().into()
+) -> Type {
+    value.ty()
 }
 
 /// Returns the string representation of a value.
@@ -53,6 +60,9 @@ pub fn repr(
 
 /// Fails with an error.
 ///
+/// The error aborts compilation and cannot be recovered from with
+/// [`catch`]($func/catch).
+///
 /// ## Example { #example }
 /// The code below produces the error `panicked with: "this is wrong"`.
 /// ```typ
@@ -66,7 +76,9 @@ pub fn panic(
     /// The values to panic with.
     #[variadic]
     values: Vec<Value>,
-) -> StrResult<Never> {
+    /// The call span of this function.
+    span: Span,
+) -> SourceResult<Never> {
     let mut msg = EcoString::from("panicked");
     if !values.is_empty() {
         msg.push_str(" with: ");
@@ -77,7 +89,80 @@ pub fn panic(
             msg.push_str(&value.repr());
         }
     }
-    Err(msg)
+    bail!(error!(span, "{msg}").into_fatal())
+}
+
+/// Calls a function and recovers from errors that occur during the call.
+///
+/// If the function finishes successfully, its return value is returned.
+/// Otherwise, the `handler` is called with a dictionary describing the error
+/// and its return value is used instead. The dictionary has the following
+/// keys:
+///
+/// - `message`: The error message as a [string]($type/string).
+/// - `hints`: An [array]($type/array) of strings with hints on how to fix the
+///   error.
+/// - `line` and `column`: The position where the error occurred, starting at
+///   one, or `{none}` if the position is unknown.
+///
+/// Errors raised by [`panic`]($func/panic) and errors from exceeded resource
+/// limits, such as an infinite loop, cannot be caught.
+///
+/// ## Example { #example }
+/// ```example
+/// #let config = catch(
+///   () => json("missing.json"),
+///   err => (title: "Untitled"),
+/// )
+/// #config.title \
+/// #catch(() => int("twelve"), err => err.message)
+/// ```
+///
+/// Display: Catch
+/// Category: foundations
+#[func]
+pub fn catch(
+    /// The function to call. It is called without any arguments.
+    body: Func,
+    /// The function to call with the error if the call fails. If omitted, a
+    /// failed call results in `{none}`.
+    #[default]
+    handler: Option<Func>,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> SourceResult<Value> {
+    let errors = match body.call_vm(vm, Args::new::<Value>(body.span(), [])) {
+        Ok(value) => return Ok(value),
+        Err(errors) => errors,
+    };
+
+    if errors.iter().any(|error| error.fatal) {
+        return Err(errors);
+    }
+
+    let (Some(error), Some(handler)) = (errors.first(), handler) else {
+        return Ok(Value::None);
+    };
+
+    let position = (!error.span.is_detached())
+        .then(|| vm.world().source(error.span.id()).ok())
+        .flatten()
+        .and_then(|source| {
+            let offset = source.find(error.span)?.offset();
+            let line = source.byte_to_line(offset)?;
+            let column = source.byte_to_column(offset)?;
+            Some((line + 1, column + 1))
+        });
+
+    let hints: Array = error.hints.iter().cloned().map(IntoValue::into_value).collect();
+    let error = dict! {
+        "message" => error.message.clone(),
+        "hints" => hints,
+        "line" => position.map(|(line, _)| line),
+        "column" => position.map(|(_, column)| column),
+    };
+
+    handler.call_vm(vm, Args::new(handler.span(), [error]))
 }
 
 /// Ensures that a condition is fulfilled.
@@ -213,3 +298,48 @@ pub fn eval(
     let Spanned { v: text, span } = source;
     typst::eval::eval_string(vm.world(), &text, span)
 }
+
+/// Loads a WebAssembly plugin.
+///
+/// Returns a module with a function for each function the plugin exports.
+/// Plugin functions take any number of [bytes]($type/bytes) arguments and
+/// return bytes. They run in a sandbox with limited memory and instruction
+/// budget. Each call starts with a fresh instance of the plugin, so plugin
+/// functions cannot keep state between calls.
+///
+/// ## Example { #example }
+/// ```typ
+/// #let barcode = plugin("barcode.wasm")
+/// #image.decode(str(barcode.generate(bytes("1234567890"))))
+/// ```
+///
+/// ## Protocol { #protocol }
+/// A plugin must export its memory as `memory` and may import the following
+/// two functions from the `typst_env` module:
+///
+/// - `wasm_minimal_protocol_write_args_to_buffer(ptr: i32)` copies the
+///   concatenated arguments of the current call into the plugin's memory at
+///   `ptr`.
+/// - `wasm_minimal_protocol_send_result_to_host(ptr: i32, len: i32)` reports
+///   the `len` bytes at `ptr` as the output of the current call.
+///
+/// Each exported function that should be callable from Typst receives the
+/// lengths of its arguments as `i32` parameters and returns an `i32`: `0` if
+/// the call succeeded and `1` if the output is an error message.
+///
+/// Display: Plugin
+/// Category: foundations
+#[func]
+pub fn plugin(
+    /// Path to a WebAssembly file.
+    path: Spanned<EcoString>,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> SourceResult<Module> {
+    let Spanned { v: path, span } = path;
+    let id = vm.location().join(&path).at(span)?;
+    let data = vm.world().file(id).at(span)?;
+    let plugin = Plugin::new(data).at(span)?;
+    let name = id.path().file_stem().unwrap_or_default().to_string_lossy();
+    Ok(Module::new(name).with_scope(plugin.scope()))
+}
@@ -9,32 +9,54 @@ pub use self::construct::*;
 pub use self::data::*;
 pub use self::foundations::*;
 
+use typst::eval::{Datetime, Decimal, Duration, Module, Regex};
+use typst::util::Bytes;
+
 use crate::prelude::*;
 
 /// Hook up all compute definitions.
 pub(super) fn define(global: &mut Scope) {
-    global.define("type", type_func());
+    global.define("type", Type::of::<Type>());
     global.define("repr", repr_func());
     global.define("panic", panic_func());
+    global.define("catch", catch_func());
     global.define("assert", assert_func());
     global.define("eval", eval_func());
-    global.define("int", int_func());
-    global.define("float", float_func());
+    global.define("plugin", plugin_func());
+    global.define("bool", Type::of::<bool>());
+    global.define("int", Type::of::<i64>());
+    global.define("float", Type::of::<f64>());
+    global.define("decimal", Type::of::<Decimal>());
+    global.define("length", Type::of::<Length>());
+    global.define("angle", Type::of::<Angle>());
+    global.define("ratio", Type::of::<Ratio>());
+    global.define("relative", Type::of::<Rel<Length>>());
+    global.define("fraction", Type::of::<Fr>());
     global.define("luma", luma_func());
     global.define("rgb", rgb_func());
     global.define("cmyk", cmyk_func());
     global.define("color", color_module());
-    global.define("datetime", datetime_func());
-    global.define("symbol", symbol_func());
-    global.define("str", str_func());
-    global.define("label", label_func());
-    global.define("regex", regex_func());
+    global.define("datetime", Type::of::<Datetime>());
+    global.define("duration", Type::of::<Duration>());
+    global.define("symbol", Type::of::<Symbol>());
+    global.define("str", Type::of::<Str>());
+    global.define("bytes", Type::of::<Bytes>());
+    global.define("label", Type::of::<Label>());
+    global.define("content", Type::of::<Content>());
+    global.define("array", Type::of::<Array>());
+    global.define("dictionary", Type::of::<Dict>());
+    global.define("function", Type::of::<Func>());
+    global.define("arguments", Type::of::<Args>());
+    global.define("module", Type::of::<Module>());
+    global.define("element", element_func());
+    global.define("regex", Type::of::<Regex>());
     global.define("range", range_func());
     global.define("read", read_func());
     global.define("csv", csv_func());
     global.define("json", json_func());
     global.define("toml", toml_func());
     global.define("yaml", yaml_func());
+    global.define("cbor", cbor_func());
     global.define("xml", xml_func());
     global.define("calc", calc::module());
 }
@@ -326,8 +326,17 @@ pub struct BlockElem {
 
     /// Whether this block must stick to the following one.
     ///
-    /// Use this to prevent page breaks between e.g. a heading and its body.
-    #[internal]
+    /// When the content following a sticky block doesn't fit into the
+    /// remaining space of a page or column, the sticky block is moved along
+    /// with it. This prevents page breaks between e.g. a heading and its body.
+    /// Headings are sticky by default.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #v(50pt)
+    /// #block(sticky: true)[*Keep me with the box.*]
+    /// #rect(height: 40pt)
+    /// ```
     #[default(false)]
     pub sticky: bool,
 }
@@ -1,7 +1,8 @@
 use std::mem;
 
 use super::{
-    AlignElem, BlockElem, ColbreakElem, ColumnsElem, ParElem, PlaceElem, Spacing, VElem,
+    AlignElem, BlockElem, ColbreakElem, ColumnsElem, FloatPosition, ParElem, PlaceElem,
+    Spacing, VElem,
 };
 use crate::meta::{FootnoteElem, FootnoteEntry};
 use crate::prelude::*;
@@ -101,6 +102,12 @@ struct FlowLayouter<'a> {
     items: Vec<FlowItem>,
     /// A queue of floats.
     pending_floats: Vec<FlowItem>,
+    /// Floats waiting for a page of their own and their clearance.
+    page_floats: Vec<(Frame, Abs)>,
+    /// The number of floats at the top and bottom of the current region.
+    floats: usize,
+    /// The maximum number of floats at the top and bottom of a region.
+    float_limit: Option<usize>,
     /// Whether we have any footnotes in the current region.
     has_footnotes: bool,
     /// Footnote configuration.
@@ -127,8 +134,15 @@ enum FlowItem {
     /// item after it (for orphan prevention), and whether it is movable
     /// (to keep it together with its footnotes).
     Frame { frame: Frame, aligns: Axes<Align>, sticky: bool, movable: bool },
-    /// An absolutely placed frame.
-    Placed { frame: Frame, y_align: Smart<Option<Align>>, float: bool, clearance: Abs },
+    /// An absolutely placed frame and, for floats, the positions it may take
+    /// in order of preference.
+    Placed {
+        frame: Frame,
+        y_align: Smart<Option<Align>>,
+        float: bool,
+        clearance: Abs,
+        positions: Vec<FloatPosition>,
+    },
     /// A footnote frame (can also be the separator).
     Footnote(Frame),
 }
@@ -162,6 +176,9 @@ impl<'a> FlowLayouter<'a> {
             last_was_par: false,
             items: vec![],
             pending_floats: vec![],
+            page_floats: vec![],
+            floats: 0,
+            float_limit: PlaceElem::limit_in(styles),
             has_footnotes: false,
             footnote_config: FootnoteConfig {
                 separator: FootnoteEntry::separator_in(styles),
@@ -207,22 +224,9 @@ impl<'a> FlowLayouter<'a> {
             .layout(vt, styles, consecutive, self.regions.base(), self.regions.expand.x)?
             .into_frames();
 
-        let mut sticky = self.items.len();
-        for (i, item) in self.items.iter().enumerate().rev() {
-            match *item {
-                FlowItem::Absolute(_, _) => {}
-                FlowItem::Frame { sticky: true, .. } => sticky = i,
-                _ => break,
-            }
-        }
-
         if let Some(first) = lines.first() {
             if !self.regions.size.y.fits(first.height()) && !self.regions.in_last() {
-                let carry: Vec<_> = self.items.drain(sticky..).collect();
-                self.finish_region(vt)?;
-                for item in carry {
-                    self.layout_item(vt, item)?;
-                }
+                self.carry_sticky(vt)?;
             }
         }
 
@@ -253,6 +257,9 @@ impl<'a> FlowLayouter<'a> {
         let sticky = BlockElem::sticky_in(styles);
         let pod = Regions::one(self.regions.base(), Axes::splat(false));
         let frame = content.layout(vt, styles, pod)?.into_frame();
+        if !self.regions.size.y.fits(frame.height()) && !self.regions.in_last() {
+            self.carry_sticky(vt)?;
+        }
         self.layout_item(vt, FlowItem::Frame { frame, aligns, sticky, movable: true })?;
         self.last_was_par = false;
         Ok(())
@@ -269,9 +276,37 @@ impl<'a> FlowLayouter<'a> {
         if let Some(placed) = block.to::<PlaceElem>() {
             let float = placed.float(styles);
             let clearance = placed.clearance(styles);
-            let y_align = placed.alignment(styles).map(|align| align.y.resolve(styles));
+            let mut y_align =
+                placed.alignment(styles).map(|align| align.y.resolve(styles));
+            let mut positions = vec![];
+            if float {
+                positions = match placed.placement(styles) {
+                    Smart::Custom(placement) => {
+                        // An explicit placement takes precedence over the
+                        // closeness-based choice for automatic alignment.
+                        y_align = Smart::Custom(None);
+                        placement.0
+                    }
+                    Smart::Auto => match y_align {
+                        Smart::Custom(Some(Align::Bottom)) => vec![FloatPosition::Bottom],
+                        Smart::Custom(Some(Align::Top)) => vec![FloatPosition::Top],
+                        _ => vec![FloatPosition::Top, FloatPosition::Bottom],
+                    },
+                };
+
+                // Float pages only exist for root flows with pages of fixed
+                // height.
+                if !self.root || !self.regions.full.is_finite() {
+                    for position in &mut positions {
+                        if *position == FloatPosition::Page {
+                            *position = FloatPosition::Top;
+                        }
+                    }
+                }
+            }
+
             let frame = placed.layout_inner(vt, styles, self.regions)?.into_frame();
-            let item = FlowItem::Placed { frame, y_align, float, clearance };
+            let item = FlowItem::Placed { frame, y_align, float, clearance, positions };
             return self.layout_item(vt, item);
         }
 
@@ -286,7 +321,7 @@ impl<'a> FlowLayouter<'a> {
 
         if self.regions.is_full() {
             // Skip directly if region is already full.
-            self.finish_region(vt)?;
+            self.carry_sticky(vt)?;
         }
 
         // How to align the block.
@@ -299,9 +334,25 @@ impl<'a> FlowLayouter<'a> {
         }
         .resolve(styles);
 
+        // Whether the block sticks to the following one.
+        let sticky = if let Some(block) = block.to::<BlockElem>() {
+            block.sticky(styles)
+        } else {
+            BlockElem::sticky_in(styles)
+        };
+
         // Layout the block itself.
-        let sticky = BlockElem::sticky_in(styles);
-        let fragment = block.layout(vt, styles, self.regions)?;
+        let mut fragment = block.layout(vt, styles, self.regions)?;
+
+        // If nothing of the block fits into this region, move the sticky
+        // blocks before it (like headings) into the next region, too.
+        if fragment.len() > 1
+            && fragment.iter().next().map_or(false, |frame| frame.height().is_zero())
+            && self.can_carry_sticky()
+        {
+            self.carry_sticky(vt)?;
+            fragment = block.layout(vt, styles, self.regions)?;
+        }
 
         for (i, frame) in fragment.into_iter().enumerate() {
             // Find footnotes in the frame.
@@ -328,7 +379,7 @@ impl<'a> FlowLayouter<'a> {
 
     /// Layout a finished frame.
     #[tracing::instrument(name = "FlowLayouter::layout_item", skip_all)]
-    fn layout_item(&mut self, vt: &mut Vt, mut item: FlowItem) -> SourceResult<()> {
+    fn layout_item(&mut self, vt: &mut Vt, item: FlowItem) -> SourceResult<()> {
         match item {
             FlowItem::Absolute(v, weak) => {
                 if weak
@@ -364,51 +415,146 @@ impl<'a> FlowLayouter<'a> {
                 }
             }
             FlowItem::Placed { float: false, .. } => {}
-            FlowItem::Placed {
-                ref mut frame,
-                ref mut y_align,
-                float: true,
-                clearance,
-                ..
-            } => {
-                // If the float doesn't fit, queue it for the next region.
-                if !self.regions.size.y.fits(frame.height() + clearance)
-                    && !self.regions.in_last()
-                {
-                    self.pending_floats.push(item);
-                    return Ok(());
-                }
+            FlowItem::Placed { float: true, .. } => return self.layout_float(vt, item),
+            FlowItem::Footnote(_) => {}
+        }
 
-                // Select the closer placement, top or bottom.
-                if y_align.is_auto() {
-                    let ratio = (self.regions.size.y
-                        - (frame.height() + clearance) / 2.0)
-                        / self.regions.full;
-                    let better_align =
-                        if ratio <= 0.5 { Align::Bottom } else { Align::Top };
-                    *y_align = Smart::Custom(Some(better_align));
-                }
+        self.items.push(item);
+        Ok(())
+    }
 
-                // Add some clearance so that the float doesn't touch the main
-                // content.
-                frame.size_mut().y += clearance;
-                if *y_align == Smart::Custom(Some(Align::Bottom)) {
-                    frame.translate(Point::with_y(clearance));
-                }
+    /// Layout a floating frame at the first of its positions where it fits or
+    /// defer it to the next region if it fits nowhere.
+    fn layout_float(&mut self, vt: &mut Vt, mut item: FlowItem) -> SourceResult<()> {
+        let FlowItem::Placed { frame, y_align, clearance, positions, .. } = &mut item
+        else {
+            unreachable!()
+        };
+
+        // Keep floats in order: A float may not overtake an earlier float that
+        // is still waiting for a region.
+        if !self.pending_floats.is_empty() {
+            self.pending_floats.push(item);
+            return Ok(());
+        }
 
-                self.regions.size.y -= frame.height();
+        // Try the closer placement, top or bottom, first.
+        if y_align.is_auto() {
+            let ratio = (self.regions.size.y - (frame.height() + *clearance) / 2.0)
+                / self.regions.full;
+            let better =
+                if ratio <= 0.5 { FloatPosition::Bottom } else { FloatPosition::Top };
+            positions.sort_by_key(|&position| position != better);
+        }
 
-                // Find footnotes in the frame.
-                if self.root {
-                    let mut notes = vec![];
-                    find_footnotes(&mut notes, frame);
-                    self.try_handle_footnotes(vt, notes)?;
-                }
+        // Select the first position where the float fits. If the region is as
+        // empty as it gets, there is no point in deferring it any further.
+        let fits = self.regions.size.y.fits(frame.height() + *clearance);
+        let free = self.float_limit.map_or(true, |limit| self.floats < limit);
+        let selected = positions
+            .iter()
+            .copied()
+            .find(|position| match position {
+                FloatPosition::Here => fits,
+                FloatPosition::Top | FloatPosition::Bottom => fits && free,
+                FloatPosition::Page => true,
+            })
+            .or_else(|| positions.first().copied().filter(|_| self.regions.in_last()));
+
+        let Some(position) = selected else {
+            // A deferred float can't be placed where it was specified anymore.
+            positions.retain(|&position| position != FloatPosition::Here);
+            if positions.is_empty() {
+                positions.push(FloatPosition::Top);
             }
-            FlowItem::Footnote(_) => {}
+            self.pending_floats.push(item);
+            return Ok(());
+        };
+
+        let FlowItem::Placed { mut frame, clearance, .. } = item else { unreachable!() };
+
+        let align = match position {
+            FloatPosition::Here => {
+                // Lay the float out like a non-floating block.
+                self.layout_item(vt, FlowItem::Absolute(clearance / 2.0, true))?;
+                let aligns = Axes::new(Align::Left, Align::Top);
+                let item =
+                    FlowItem::Frame { frame, aligns, sticky: false, movable: true };
+                self.layout_item(vt, item)?;
+                return self.layout_item(vt, FlowItem::Absolute(clearance / 2.0, true));
+            }
+            FloatPosition::Page => {
+                self.page_floats.push((frame, clearance));
+                return Ok(());
+            }
+            FloatPosition::Top => Align::Top,
+            FloatPosition::Bottom => Align::Bottom,
+        };
+
+        // Add some clearance so that the float doesn't touch the main content.
+        frame.size_mut().y += clearance;
+        if align == Align::Bottom {
+            frame.translate(Point::with_y(clearance));
         }
 
-        self.items.push(item);
+        self.regions.size.y -= frame.height();
+        self.floats += 1;
+
+        // Find footnotes in the frame.
+        let mut notes = vec![];
+        if self.root {
+            find_footnotes(&mut notes, &frame);
+        }
+
+        self.items.push(FlowItem::Placed {
+            frame,
+            y_align: Smart::Custom(Some(align)),
+            float: true,
+            clearance,
+            positions: vec![position],
+        });
+
+        self.try_handle_footnotes(vt, notes)
+    }
+
+    /// The index of the first item in the run of sticky frames (and spacing
+    /// between them) at the end of the current region.
+    fn sticky_start(&self) -> usize {
+        let mut sticky = self.items.len();
+        for (i, item) in self.items.iter().enumerate().rev() {
+            match *item {
+                FlowItem::Absolute(_, _) => {}
+                FlowItem::Frame { sticky: true, .. } => sticky = i,
+                _ => break,
+            }
+        }
+        sticky
+    }
+
+    /// Whether the current region ends with sticky frames that can be moved
+    /// to the next region without leaving this region without any frame.
+    fn can_carry_sticky(&self) -> bool {
+        let sticky = self.sticky_start();
+        sticky < self.items.len()
+            && !self.regions.in_last()
+            && self.items[..sticky]
+                .iter()
+                .any(|item| matches!(item, FlowItem::Frame { .. }))
+    }
+
+    /// Finish the current region and, if possible, move the sticky frames at
+    /// its end into the next region to keep them together with what follows.
+    fn carry_sticky(&mut self, vt: &mut Vt) -> SourceResult<()> {
+        let carry: Vec<_> = if self.can_carry_sticky() {
+            let sticky = self.sticky_start();
+            self.items.drain(sticky..).collect()
+        } else {
+            vec![]
+        };
+        self.finish_region(vt)?;
+        for item in carry {
+            self.layout_item(vt, item)?;
+        }
         Ok(())
     }
 
@@ -531,6 +677,12 @@ impl<'a> FlowLayouter<'a> {
         self.regions.next();
         self.initial = self.regions.size;
         self.has_footnotes = false;
+        self.floats = 0;
+
+        // Put the floats that want a page of their own onto float pages.
+        if !self.page_floats.is_empty() {
+            self.finish_float_pages();
+        }
 
         // Try to place floats.
         for item in mem::take(&mut self.pending_floats) {
@@ -540,6 +692,36 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// Create regions that only contain floats, with as many floats per region
+    /// as fit and centered vertically.
+    fn finish_float_pages(&mut self) {
+        let mut floats = mem::take(&mut self.page_floats).into_iter().peekable();
+        while floats.peek().is_some() {
+            let size = self.initial;
+            let mut used = Abs::zero();
+            let mut frames = vec![];
+            while let Some((frame, clearance)) = floats.next_if(|(frame, clearance)| {
+                frames.is_empty() || size.y.fits(used + *clearance + frame.height())
+            }) {
+                if !frames.is_empty() {
+                    used += clearance;
+                }
+                frames.push((used, frame));
+                used += frames.last().unwrap().1.height();
+            }
+
+            let mut output = Frame::new(size);
+            let start = ((size.y - used) / 2.0).max(Abs::zero());
+            for (y, frame) in frames {
+                output.push_frame(Point::with_y(start + y), frame);
+            }
+
+            self.finished.push(output);
+            self.regions.next();
+            self.initial = self.regions.size;
+        }
+    }
+
     /// Finish layouting and return the resulting fragment.
     fn finish(mut self, vt: &mut Vt) -> SourceResult<Fragment> {
         if self.expand.y {
@@ -672,7 +854,9 @@ fn find_footnotes(notes: &mut Vec<FootnoteElem>, frame: &Frame) {
                 if !notes.iter().any(|note| note.0.location() == content.location()) =>
             {
                 let Some(footnote) = content.to::<FootnoteElem>() else { continue };
-                notes.push(footnote.clone());
+                if !footnote.is_endnote() {
+                    notes.push(footnote.clone());
+                }
             }
             _ => {}
         }
@@ -1,10 +1,15 @@
 use std::ptr;
 use std::str::FromStr;
 
-use super::{AlignElem, ColumnsElem};
-use crate::meta::{Counter, CounterKey, Numbering};
+use typst::util::option_eq;
+
+use super::{AlignElem, ColumnsElem, ParLine};
+use crate::meta::{
+    Counter, CounterKey, FootnoteElem, FootnoteReset, LocalName, MarginNoteElem,
+    Numbering,
+};
 use crate::prelude::*;
-use crate::text::TextElem;
+use crate::text::{TextDir, TextElem};
 
 /// Layouts its child onto one or multiple pages.
 ///
@@ -336,10 +341,11 @@ impl PageElem {
             .relative_to(size);
 
         // Determine the binding.
+        let vertical = TextElem::is_vertical_in(styles);
         let binding =
             self.binding(styles)
                 .unwrap_or_else(|| match TextElem::dir_in(styles) {
-                    Dir::LTR => Binding::Left,
+                    Dir::LTR if !vertical => Binding::Left,
                     _ => Binding::Right,
                 });
 
@@ -350,12 +356,37 @@ impl PageElem {
             child = ColumnsElem::new(child).with_count(columns).pack();
         }
 
+        // Vertical pages are laid out in a transposed area, in which lines run
+        // from left to right and blocks flow downwards. The resulting frames
+        // are then rotated clockwise, so that lines run from top to bottom and
+        // follow each other from right to left.
         let area = size - margin.sum_by_axis();
-        let mut regions = Regions::repeat(area, area.map(Abs::is_finite));
+        let mut pod = area;
+        if vertical {
+            child = child.styled(TextElem::set_vertical(true));
+            std::mem::swap(&mut pod.x, &mut pod.y);
+        }
+
+        // Everything outside of the body isn't rotated, so it is set
+        // horizontally.
+        let horizontal = Styles::from(TextElem::set_dir(TextDir(Smart::Auto)));
+        let outer = if vertical { styles.chain(&horizontal) } else { styles };
+
+        let mut regions = Regions::repeat(pod, pod.map(Abs::is_finite));
         regions.root = true;
 
         // Layout the child.
         let mut frames = child.layout(vt, styles, regions)?.into_frames();
+        if vertical {
+            for frame in &mut frames {
+                let size = frame.size();
+                frame.transform(
+                    Transform::translate(size.y, Abs::zero())
+                        .pre_concat(Transform::rotate(Angle::deg(90.0))),
+                );
+                frame.set_size(Size::new(size.y, size.x));
+            }
+        }
 
         // Align the child to the pagebreak's parity.
         if self.clear_to(styles).is_some_and(|p| !p.matches(number.get())) {
@@ -386,6 +417,9 @@ impl PageElem {
             Size::zero(),
         );
 
+        let restarts_footnotes =
+            FootnoteElem::reset_in(styles) == Some(FootnoteReset::Page);
+
         // Post-process pages.
         for frame in frames.iter_mut() {
             tracing::info!("Layouting page #{number}");
@@ -396,9 +430,18 @@ impl PageElem {
             // If two sided, left becomes inside and right becomes outside.
             // Thus, for left-bound pages, we want to swap on even pages and
             // for right-bound pages, we want to swap on odd pages.
+            //
+            // Without two-sided margins, the inside is the bound side. With
+            // them, it is wherever the inside margin ends up.
             let mut margin = margin;
+            let mut inside = match binding {
+                _ if two_sided => Side::Left,
+                Binding::Left => Side::Left,
+                Binding::Right => Side::Right,
+            };
             if two_sided && binding.swap(number) {
                 std::mem::swap(&mut margin.left, &mut margin.right);
+                inside = inside.inv();
             }
 
             // Realize margins.
@@ -406,6 +449,10 @@ impl PageElem {
             frame.translate(Point::new(margin.left, margin.top));
             frame.push(Point::zero(), numbering_meta.clone());
 
+            // Realize line numbers and margin notes.
+            layout_line_numbers(vt, outer, frame, margin, inside)?;
+            layout_margin_notes(vt, outer, frame, margin, inside)?;
+
             // The page size with margins.
             let size = frame.size();
 
@@ -441,7 +488,8 @@ impl PageElem {
                 let sub = content
                     .clone()
                     .styled(AlignElem::set_alignment(align))
-                    .layout(vt, styles, pod)?
+                    .styled(ParLine::set_numbering(None))
+                    .layout(vt, outer, pod)?
                     .into_frame();
 
                 if ptr::eq(marginal, &header) || ptr::eq(marginal, &background) {
@@ -451,6 +499,13 @@ impl PageElem {
                 }
             }
 
+            // Restart the footnote numbering before anything else on the page.
+            if restarts_footnotes {
+                let pod = Regions::one(Size::zero(), Axes::splat(false));
+                let sub = FootnoteElem::reset_counter().layout(vt, outer, pod)?;
+                frame.prepend_frame(Point::zero(), sub.into_frame());
+            }
+
             if let Some(fill) = &fill {
                 frame.fill(fill.clone());
             }
@@ -462,6 +517,206 @@ impl PageElem {
     }
 }
 
+/// Lay out the margin notes anchored in the page's body into its margins.
+fn layout_margin_notes(
+    vt: &mut Vt,
+    styles: StyleChain,
+    frame: &mut Frame,
+    margin: Sides<Abs>,
+    inside: Side,
+) -> SourceResult<()> {
+    let mut notes = vec![];
+    find_elems::<MarginNoteElem>(&mut notes, frame, Transform::identity());
+    if notes.is_empty() {
+        return Ok(());
+    }
+
+    notes.sort_by_key(|&(point, _)| point.y);
+
+    let clearance = MarginNoteElem::clearance_in(styles);
+    let gap = MarginNoteElem::gap_in(styles);
+    let size = frame.size();
+
+    for side in [Side::Left, Side::Right] {
+        let width = (margin.get(side) - 2.0 * clearance).max(Abs::zero());
+        let x = match side {
+            Side::Left => clearance,
+            _ => size.x - margin.right + clearance,
+        };
+
+        // Stack the notes downwards so that they don't overlap.
+        let mut placed: Vec<(Abs, Frame)> = vec![];
+        for (anchor, note) in &notes {
+            let note = note.to::<MarginNoteElem>().unwrap();
+            if note.side(StyleChain::default()).resolve(inside) != side {
+                continue;
+            }
+
+            let pod = Regions::one(Size::new(width, Abs::inf()), Axes::new(true, false));
+            let sub = note
+                .entry(vt)?
+                .styled(ParLine::set_numbering(None))
+                .layout(vt, styles, pod)?
+                .into_frame();
+
+            // Align the first baseline of the note with the anchor's line.
+            let mut y = anchor.y - first_baseline(&sub).unwrap_or_default();
+            if let Some((prev, above)) = placed.last() {
+                y.set_max(*prev + above.height() + gap);
+            }
+
+            placed.push((y, sub));
+        }
+
+        // Shift notes that run past the bottom margin upwards.
+        let mut limit = size.y - margin.bottom;
+        for (y, sub) in placed.iter_mut().rev() {
+            if *y + sub.height() <= limit {
+                break;
+            }
+            *y = limit - sub.height();
+            limit = *y - gap;
+        }
+
+        for (y, sub) in placed {
+            frame.push_frame(Point::new(x, y), sub);
+        }
+    }
+
+    Ok(())
+}
+
+/// Lay out the numbers of the lines on a page into its margins.
+fn layout_line_numbers(
+    vt: &mut Vt,
+    styles: StyleChain,
+    frame: &mut Frame,
+    margin: Sides<Abs>,
+    inside: Side,
+) -> SourceResult<()> {
+    let mut lines = vec![];
+    find_elems::<ParLine>(&mut lines, frame, Transform::identity());
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    lines.sort_by_key(|&(point, _)| (point.y, point.x));
+
+    let numbers = ParLine::numbers(vt.introspector);
+    let clearance = ParLine::clearance_in(styles);
+    let size = frame.size();
+    let pod = Regions::one(Size::splat(Abs::inf()), Axes::splat(false));
+
+    // Where the last number on each side ends, to avoid overlaps between the
+    // numbers of lines at the same height, e.g. in different columns.
+    let mut bottoms = Sides::splat(-Abs::inf());
+    for (point, elem) in lines {
+        let Some(&number) = elem.location().and_then(|loc| numbers.get(&loc)) else {
+            continue;
+        };
+
+        let line = elem.to::<ParLine>().unwrap();
+        if number % line.every(StyleChain::default()).get() != 0 {
+            continue;
+        }
+
+        let side = line.side(StyleChain::default()).resolve(inside);
+        let Some(numbering) = line.numbering(StyleChain::default()) else { continue };
+        let sub = numbering
+            .apply_vt(vt, &[number])?
+            .display()
+            .styled(ParLine::set_numbering(None))
+            .layout(vt, styles, pod)?
+            .into_frame();
+
+        let y = point.y - first_baseline(&sub).unwrap_or(sub.height());
+        let bottom = bottoms.get_mut(side);
+        if y < *bottom {
+            continue;
+        }
+        *bottom = y + sub.height();
+
+        let x = match side {
+            Side::Left => margin.left - clearance - sub.width(),
+            _ => size.x - margin.right + clearance,
+        };
+
+        frame.push_frame(Point::new(x, y), sub);
+    }
+
+    Ok(())
+}
+
+/// Finds all elements of the given kind in the frame together with their
+/// positions.
+fn find_elems<T: Element>(
+    found: &mut Vec<(Point, Content)>,
+    frame: &Frame,
+    ts: Transform,
+) {
+    for (pos, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => {
+                let ts = ts
+                    .pre_concat(Transform::translate(pos.x, pos.y))
+                    .pre_concat(group.transform);
+                find_elems::<T>(found, &group.frame, ts);
+            }
+            FrameItem::Meta(Meta::Elem(content), _)
+                if content.is::<T>()
+                    && !found
+                        .iter()
+                        .any(|(_, elem)| elem.location() == content.location()) =>
+            {
+                found.push((pos.transform(ts), content.clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The position of the first baseline in the frame, if any.
+fn first_baseline(frame: &Frame) -> Option<Abs> {
+    frame.items().find_map(|(pos, item)| match item {
+        FrameItem::Group(group) if group.frame.has_baseline() => {
+            Some(pos.y + group.frame.baseline())
+        }
+        FrameItem::Group(group) => first_baseline(&group.frame).map(|y| pos.y + y),
+        _ => None,
+    })
+}
+
+impl LocalName for PageElem {
+    fn local_name(&self, lang: Lang, region: Option<Region>) -> &'static str {
+        match lang {
+            Lang::ALBANIAN => "faqe",
+            Lang::ARABIC => "صفحة",
+            Lang::BOKMÅL => "side",
+            Lang::CHINESE if option_eq(region, "TW") => "頁",
+            Lang::CHINESE => "页",
+            Lang::CZECH => "strana",
+            Lang::DANISH => "side",
+            Lang::DUTCH => "pagina",
+            Lang::FILIPINO => "pahina",
+            Lang::FRENCH => "page",
+            Lang::GERMAN => "Seite",
+            Lang::ITALIAN => "pagina",
+            Lang::NYNORSK => "side",
+            Lang::POLISH => "strona",
+            Lang::PORTUGUESE => "página",
+            Lang::RUSSIAN => "страница",
+            Lang::SLOVENIAN => "stran",
+            Lang::SPANISH => "página",
+            Lang::SWEDISH => "sida",
+            Lang::TURKISH => "sayfa",
+            Lang::UKRAINIAN => "сторінка",
+            Lang::VIETNAMESE => "trang",
+            Lang::JAPANESE => "ページ",
+            Lang::ENGLISH | _ => "page",
+        }
+    }
+}
+
 /// Specification of the page's margins.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Margin {
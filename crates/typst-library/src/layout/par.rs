@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use ecow::eco_vec;
 use icu_properties::{maps::CodePointMapData, LineBreak};
 use icu_provider::AsDeserializingBufferProvider;
 use icu_provider_adapters::fork::ForkByKeyProvider;
@@ -6,16 +9,18 @@ use icu_segmenter::{LineBreakIteratorUtf8, LineSegmenter};
 use once_cell::sync::Lazy;
 use typst::eval::Tracer;
 use typst::model::DelayedErrors;
+use typst::util::hash128;
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
 
 use super::{BoxElem, HElem, Sizing, Spacing};
 use crate::layout::AlignElem;
 use crate::math::EquationElem;
+use crate::meta::{HeadingElem, LocalName, MarginSide, Numbering};
 use crate::prelude::*;
 use crate::text::{
-    is_gb_style, shape, LinebreakElem, Quoter, Quotes, ShapedText, SmartQuoteElem,
-    SpaceElem, TextElem,
+    add_cjk_latin_spacing, is_gb_style, shape, LinebreakElem, Quoter, Quotes, ShapedText,
+    SmartQuoteElem, SpaceElem, TextElem,
 };
 
 /// Arranges text, spacing and inline-level elements into a paragraph.
@@ -47,6 +52,10 @@ use crate::text::{
 /// Display: Paragraph
 /// Category: layout
 #[element(Construct)]
+#[scope(
+    scope.define("line", ParLine::func());
+    scope
+)]
 pub struct ParElem {
     /// The spacing between lines.
     #[resolve]
@@ -91,6 +100,66 @@ pub struct ParElem {
     #[default]
     pub linebreaks: Smart<Linebreaks>,
 
+    /// The costs of various layout choices when breaking lines in
+    /// [optimized]($func/par.linebreaks) mode.
+    ///
+    /// Costs are given as ratios of built-in base costs. A cost of `{0%}`
+    /// disables the penalty and `{100%}` applies the default penalty. The
+    /// following costs can be configured in a dictionary:
+    ///
+    /// - `hyphenation`: The cost of ending a line with a hyphen. Defaults to
+    ///   `{100%}`.
+    /// - `consecutive-hyphenation`: The extra cost of ending two successive
+    ///   lines with hyphens or dashes. Defaults to `{100%}`.
+    /// - `runt`: The cost of a last line consisting of a single word (a runt).
+    ///   Runts are not penalized by default.
+    ///
+    /// ```example
+    /// #set page(width: 200pt)
+    /// #set par(justify: true)
+    /// #set text(hyphenate: true)
+    ///
+    /// #set par(costs: (hyphenation: 0%))
+    /// A justified paragraph in which hyphens are
+    /// not considered bad at all.
+    ///
+    /// #set par(costs: (hyphenation: 400%))
+    /// A justified paragraph in which hyphens are
+    /// considered really bad.
+    /// ```
+    #[fold]
+    pub costs: Costs,
+
+    /// How much the spacing between words and characters may be adjusted to
+    /// justify a line or to shrink a line that is slightly too long.
+    ///
+    /// Takes a dictionary with the keys `spacing` and `tracking`, each of which
+    /// is a dictionary with `min` and `max` keys:
+    ///
+    /// - `spacing`: The bounds for the width of a space. Given relative to the
+    ///   normal width of the space. Defaults to `{(min: 66.67%, max: 150%)}`.
+    /// - `tracking`: The bounds for additional space between characters.
+    ///   Defaults to `{(min: 0em, max: 0em)}`, which disables adjustment of
+    ///   the tracking.
+    ///
+    /// When a justified line cannot be filled within these limits, the
+    /// remaining space is still distributed, but the line is considered bad by
+    /// the optimized line breaker.
+    ///
+    /// ```example
+    /// #set page(width: 200pt)
+    /// #set par(justify: true)
+    ///
+    /// #set par(justification-limits: (
+    ///   spacing: (min: 90%, max: 110%),
+    ///   tracking: (min: -0.01em, max: 0.02em),
+    /// ))
+    /// A justified paragraph in which words may only
+    /// move a little, but letters may spread apart.
+    /// ```
+    #[fold]
+    pub justification_limits: JustificationLimits,
+
     /// The indent the first line of a paragraph should have.
     ///
     /// Only the first line of a consecutive paragraph will be indented (not
@@ -107,6 +176,39 @@ pub struct ParElem {
     #[resolve]
     pub hanging_indent: Length,
 
+    /// The minimum number of lines of a paragraph that must be kept together
+    /// at the end of a page or column when the paragraph is split across them.
+    ///
+    /// A single line of a paragraph left behind at the bottom of a page is
+    /// called an _orphan._ Setting this to `{1}` disables orphan prevention.
+    ///
+    /// ```example
+    /// #set page(height: 60pt)
+    /// #set par(orphans: 3)
+    ///
+    /// #v(30pt)
+    /// This paragraph's first three lines always end up on the same page.
+    /// ```
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub orphans: NonZeroUsize,
+
+    /// The minimum number of lines of a paragraph that must be kept together
+    /// at the start of a page or column when the paragraph is split across
+    /// them.
+    ///
+    /// A single line of a paragraph carried over to the top of a page is
+    /// called a _widow._ Setting this to `{1}` disables widow prevention.
+    ///
+    /// ```example
+    /// #set page(height: 60pt)
+    /// #set par(widows: 3)
+    ///
+    /// #v(10pt)
+    /// This paragraph's last three lines always end up on the same page.
+    /// ```
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub widows: NonZeroUsize,
+
     /// The contents of the paragraph.
     #[external]
     #[required]
@@ -213,6 +315,396 @@ pub enum Linebreaks {
     Optimized,
 }
 
+/// The costs of layout choices for optimized line breaking, relative to the
+/// built-in base costs.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Costs {
+    hyphenation: Option<Ratio>,
+    consecutive_hyphenation: Option<Ratio>,
+    runt: Option<Ratio>,
+}
+
+impl Costs {
+    /// The cost of ending a line with a hyphen.
+    pub fn hyphenation(&self) -> Ratio {
+        self.hyphenation.unwrap_or(Ratio::one())
+    }
+
+    /// The extra cost of two successive lines ending with a hyphen or dash.
+    pub fn consecutive_hyphenation(&self) -> Ratio {
+        self.consecutive_hyphenation.unwrap_or(Ratio::one())
+    }
+
+    /// The cost of a last line consisting of a single word.
+    pub fn runt(&self) -> Ratio {
+        self.runt.unwrap_or(Ratio::zero())
+    }
+}
+
+impl Fold for Costs {
+    type Output = Self;
+
+    fn fold(self, outer: Self::Output) -> Self::Output {
+        Self {
+            hyphenation: self.hyphenation.or(outer.hyphenation),
+            consecutive_hyphenation: self
+                .consecutive_hyphenation
+                .or(outer.consecutive_hyphenation),
+            runt: self.runt.or(outer.runt),
+        }
+    }
+}
+
+cast! {
+    Costs,
+    self => {
+        let mut dict = Dict::new();
+        let mut handle = |key: &str, component: Option<Ratio>| {
+            if let Some(ratio) = component {
+                dict.insert(key.into(), ratio.into_value());
+            }
+        };
+
+        handle("hyphenation", self.hyphenation);
+        handle("consecutive-hyphenation", self.consecutive_hyphenation);
+        handle("runt", self.runt);
+
+        Value::Dict(dict)
+    },
+    mut dict: Dict => {
+        let mut take = |key| dict.take(key).ok().map(Value::cast).transpose();
+
+        let hyphenation = take("hyphenation")?;
+        let consecutive_hyphenation = take("consecutive-hyphenation")?;
+        let runt = take("runt")?;
+
+        dict.finish(&["hyphenation", "consecutive-hyphenation", "runt"])?;
+
+        Self { hyphenation, consecutive_hyphenation, runt }
+    },
+}
+
+/// Bounds for the adjustment of spacing and tracking during justification.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct JustificationLimits {
+    spacing_min: Option<Rel<Length>>,
+    spacing_max: Option<Rel<Length>>,
+    tracking_min: Option<Length>,
+    tracking_max: Option<Length>,
+}
+
+impl JustificationLimits {
+    /// The minimum width of a space, relative to its normal width.
+    pub fn spacing_min(&self) -> Rel<Length> {
+        self.spacing_min.unwrap_or(Ratio::new(2.0 / 3.0).into())
+    }
+
+    /// The maximum width of a space, relative to its normal width.
+    pub fn spacing_max(&self) -> Rel<Length> {
+        self.spacing_max.unwrap_or(Ratio::new(1.5).into())
+    }
+
+    /// The minimum additional space between characters.
+    pub fn tracking_min(&self) -> Length {
+        self.tracking_min.unwrap_or_default()
+    }
+
+    /// The maximum additional space between characters.
+    pub fn tracking_max(&self) -> Length {
+        self.tracking_max.unwrap_or_default()
+    }
+}
+
+impl Fold for JustificationLimits {
+    type Output = Self;
+
+    fn fold(self, outer: Self::Output) -> Self::Output {
+        Self {
+            spacing_min: self.spacing_min.or(outer.spacing_min),
+            spacing_max: self.spacing_max.or(outer.spacing_max),
+            tracking_min: self.tracking_min.or(outer.tracking_min),
+            tracking_max: self.tracking_max.or(outer.tracking_max),
+        }
+    }
+}
+
+cast! {
+    JustificationLimits,
+    self => {
+        let bounds = |min: Option<Value>, max: Option<Value>| {
+            let mut dict = Dict::new();
+            if let Some(min) = min {
+                dict.insert("min".into(), min);
+            }
+            if let Some(max) = max {
+                dict.insert("max".into(), max);
+            }
+            dict
+        };
+
+        let mut dict = Dict::new();
+        let spacing = bounds(
+            self.spacing_min.map(IntoValue::into_value),
+            self.spacing_max.map(IntoValue::into_value),
+        );
+        if !spacing.is_empty() {
+            dict.insert("spacing".into(), spacing.into_value());
+        }
+
+        let tracking = bounds(
+            self.tracking_min.map(IntoValue::into_value),
+            self.tracking_max.map(IntoValue::into_value),
+        );
+        if !tracking.is_empty() {
+            dict.insert("tracking".into(), tracking.into_value());
+        }
+
+        Value::Dict(dict)
+    },
+    mut dict: Dict => {
+        let mut limits = Self::default();
+
+        if let Ok(spacing) = dict.take("spacing") {
+            let mut spacing = spacing.cast::<Dict>()?;
+            let mut take = |key| spacing.take(key).ok().map(Value::cast).transpose();
+            limits.spacing_min = take("min")?;
+            limits.spacing_max = take("max")?;
+            spacing.finish(&["min", "max"])?;
+        }
+
+        if let Ok(tracking) = dict.take("tracking") {
+            let mut tracking = tracking.cast::<Dict>()?;
+            let mut take = |key| tracking.take(key).ok().map(Value::cast).transpose();
+            limits.tracking_min = take("min")?;
+            limits.tracking_max = take("max")?;
+            tracking.finish(&["min", "max"])?;
+        }
+
+        dict.finish(&["spacing", "tracking"])?;
+
+        limits
+    },
+}
+
+/// Numbers the lines of paragraphs in the page margin.
+///
+/// This function is not intended to be called directly. Instead, it is used
+/// in set rules to enable and configure line numbering. Once a
+/// [numbering]($func/par.line.numbering) is set, every line of a paragraph
+/// is numbered.
+///
+/// ## Example { #example }
+/// ```example
+/// #set page(margin: (left: 2cm))
+/// #set par.line(numbering: "1")
+///
+/// Roses are red. \
+/// Violets are blue. \
+/// Typst is there for you.
+/// ```
+///
+/// ## Excluding lines { #excluding-lines }
+/// To exclude the lines of some elements from the numbering, disable line
+/// numbering for them with a show-set rule. Display equations are not
+/// paragraphs and are never numbered.
+///
+/// ```example
+/// #set page(margin: (left: 2cm))
+/// #set par.line(numbering: "1")
+/// #show figure: set par.line(numbering: none)
+///
+/// Numbered.
+/// #figure(
+///   [Not numbered.],
+///   caption: [Numbered],
+/// )
+/// ```
+///
+/// ## Referencing lines { #referencing-lines }
+/// To refer to the line an element is on, create a reference with the
+/// [`form`]($func/ref.form) `{"line"}`. Any labelled element in a numbered
+/// line can be targeted, for example an empty [metadata]($func/metadata).
+///
+/// ```example
+/// #set page(margin: (left: 2cm))
+/// #set par.line(numbering: "1")
+///
+/// The parties agree \
+/// to disagree. #metadata(none) <agree>
+///
+/// See #ref(<agree>, form: "line").
+/// ```
+///
+/// Display: Paragraph Line
+/// Category: layout
+#[element(Locatable, Show, LocalName)]
+pub struct ParLine {
+    /// How to number the lines.
+    ///
+    /// By default, lines are not numbered.
+    pub numbering: Option<Numbering>,
+
+    /// Only display the number of every n-th line.
+    ///
+    /// The lines in between are still counted.
+    ///
+    /// ```example
+    /// #set page(margin: (left: 2cm))
+    /// #set par.line(numbering: "1", every: 2)
+    ///
+    /// #lorem(20)
+    /// ```
+    #[default(NonZeroUsize::ONE)]
+    pub every: NonZeroUsize,
+
+    /// When to restart the line numbers.
+    ///
+    /// ```example
+    /// #set page(margin: (left: 2cm))
+    /// #set par.line(numbering: "1", reset: "section")
+    ///
+    /// = Introduction
+    /// #lorem(8)
+    ///
+    /// = Background
+    /// #lorem(8)
+    /// ```
+    #[default(LineReset::Document)]
+    pub reset: LineReset,
+
+    /// In which margin to place the line numbers.
+    ///
+    /// Can be `{left}` (the default), `{right}`, `{"inside"}` or
+    /// `{"outside"}`. Inside and outside are determined by the page's
+    /// [binding]($func/page.binding).
+    #[default(MarginSide::Left)]
+    pub side: MarginSide,
+
+    /// The horizontal distance between the line numbers and the page body.
+    ///
+    /// This property can only be configured for the whole page with a set
+    /// rule.
+    #[default(Em::new(1.0).into())]
+    #[resolve]
+    pub clearance: Length,
+}
+
+impl Show for ParLine {
+    #[tracing::instrument(name = "ParLine::show", skip(self))]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+impl LocalName for ParLine {
+    fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
+        match lang {
+            Lang::ALBANIAN => "rresht",
+            Lang::ARABIC => "سطر",
+            Lang::BOKMÅL => "linje",
+            Lang::CHINESE => "行",
+            Lang::CZECH => "řádek",
+            Lang::DANISH => "linje",
+            Lang::DUTCH => "regel",
+            Lang::FILIPINO => "linya",
+            Lang::FRENCH => "ligne",
+            Lang::GERMAN => "Zeile",
+            Lang::ITALIAN => "riga",
+            Lang::NYNORSK => "linje",
+            Lang::POLISH => "wiersz",
+            Lang::PORTUGUESE => "linha",
+            Lang::RUSSIAN => "строка",
+            Lang::SLOVENIAN => "vrstica",
+            Lang::SPANISH => "línea",
+            Lang::SWEDISH => "rad",
+            Lang::TURKISH => "satır",
+            Lang::UKRAINIAN => "рядок",
+            Lang::VIETNAMESE => "dòng",
+            Lang::JAPANESE => "行",
+            Lang::ENGLISH | _ => "line",
+        }
+    }
+}
+
+impl ParLine {
+    /// Create the marker for a numbered line with the given styles.
+    fn marker(vt: &mut Vt, text: &str, styles: StyleChain) -> Option<Content> {
+        let numbering = Self::numbering_in(styles)?;
+        let mut marker = Self::new()
+            .with_numbering(Some(numbering))
+            .with_every(Self::every_in(styles))
+            .with_reset(Self::reset_in(styles))
+            .with_side(Self::side_in(styles))
+            .pack();
+        marker.set_location(vt.locator.locate(hash128(text)));
+        marker.mark_prepared();
+        Some(marker)
+    }
+
+    /// Determine the number of every numbered line in the document.
+    pub fn numbers(introspector: Tracked<Introspector>) -> HashMap<Location, usize> {
+        let selector = Selector::Or(eco_vec![
+            Selector::Elem(Self::func(), None),
+            Selector::Elem(HeadingElem::func(), None),
+        ]);
+
+        let mut numbers = HashMap::new();
+        let mut number = 0;
+        let mut prev_page = None;
+        let mut new_section = false;
+        for elem in introspector.query(&selector) {
+            let Some(line) = elem.to::<Self>() else {
+                new_section = true;
+                continue;
+            };
+
+            let location = elem.location().unwrap();
+            let page = introspector.page(location);
+            let restart = match line.reset(StyleChain::default()) {
+                LineReset::Document => false,
+                LineReset::Page => prev_page != Some(page),
+                LineReset::Section => new_section,
+            };
+
+            number = if restart { 1 } else { number + 1 };
+            numbers.insert(location, number);
+            prev_page = Some(page);
+            new_section = false;
+        }
+
+        numbers
+    }
+
+    /// Find the numbered line the given location is on.
+    pub fn find(introspector: Tracked<Introspector>, location: Location) -> Option<Self> {
+        let Position { page, point } = introspector.position(location);
+        let epsilon = Abs::pt(0.01);
+        introspector
+            .query(&Selector::Elem(Self::func(), None))
+            .into_iter()
+            .filter_map(|elem| {
+                let position = introspector.position(elem.location()?);
+                (position.page == page
+                    && position.point.x <= point.x + epsilon
+                    && position.point.y <= point.y + epsilon)
+                    .then(|| (position.point, elem))
+            })
+            .max_by_key(|(point, _)| (point.y, point.x))
+            .map(|(_, elem)| elem.to::<Self>().unwrap().clone())
+    }
+}
+
+/// When to restart line numbers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum LineReset {
+    /// Number the lines throughout the whole document.
+    Document,
+    /// Restart the numbering on each page.
+    Page,
+    /// Restart the numbering at each heading.
+    Section,
+}
+
 /// A paragraph break.
 ///
 /// This starts a new paragraph. Especially useful when used within code like
@@ -247,6 +739,14 @@ type Range = std::ops::Range<usize>;
 const SPACING_REPLACE: char = ' '; // Space
 const OBJ_REPLACE: char = '\u{FFFC}'; // Object Replacement Character
 
+// CJK punctuation that can be compressed at line ends and starts.
+const END_CJK_PUNCTUATION: [char; 20] = [
+    '”', '’', '，', '。', '、', '：', '；', '》', '）', '』', '」', '〕', '］', '｝',
+    '〉', '】', '〙', '〗', '〟', '．',
+];
+const START_CJK_PUNCTUATION: [char; 14] =
+    ['“', '‘', '《', '（', '『', '「', '〔', '［', '｛', '〈', '【', '〘', '〖', '〝'];
+
 /// A paragraph representation in which children are already layouted and text
 /// is already preshaped.
 ///
@@ -575,6 +1075,12 @@ fn collect<'a>(
             full.push(' ');
             Segment::Text(1)
         } else if let Some(elem) = child.to::<TextElem>() {
+            // Vertical text is realized by rotating whole pages, so it can't
+            // be mixed into a horizontal page.
+            if TextElem::is_vertical_in(styles) && !TextElem::vertical_in(styles) {
+                bail!(child.span(), "vertical text must be set up for the whole page");
+            }
+
             let prev = full.len();
             if let Some(case) = TextElem::case_in(styles) {
                 full.push_str(&case.apply(&elem.text()));
@@ -727,6 +1233,8 @@ fn prepare<'a>(
         cursor = end;
     }
 
+    add_cjk_latin_spacing_between_items(&mut items);
+
     Ok(Preparation {
         bidi,
         items,
@@ -788,6 +1296,64 @@ fn shape_range<'a>(
     process(cursor..range.end, prev_level);
 }
 
+/// Add spacing between CJK and Latin characters at the boundaries of adjacent
+/// text items. Within text items, this is already done during shaping.
+fn add_cjk_latin_spacing_between_items(items: &mut [Item]) {
+    let mut prev: Option<usize> = None;
+    for i in 0..items.len() {
+        match &items[i] {
+            Item::Text(_) => {}
+            Item::Meta(_) => continue,
+            _ => {
+                prev = None;
+                continue;
+            }
+        }
+
+        if let Some(j) = prev {
+            let (before, after) = items.split_at_mut(i);
+            if let (Item::Text(a), Item::Text(b)) = (&mut before[j], &mut after[0]) {
+                add_cjk_latin_spacing_between(a, b);
+            }
+        }
+
+        prev = Some(i);
+    }
+}
+
+/// Add spacing between the last glyph of `a` and the first glyph of `b`.
+fn add_cjk_latin_spacing_between(a: &mut ShapedText, b: &mut ShapedText) {
+    let enabled = |shaped: &ShapedText| {
+        shaped.dir.is_positive()
+            && TextElem::cjk_latin_spacing_in(shaped.styles).is_auto()
+    };
+
+    if !enabled(a) || !enabled(b) {
+        return;
+    }
+
+    // Work on copies so that the glyphs are only cloned when spacing is
+    // actually added.
+    let (Some(mut last), Some(mut first)) =
+        (a.glyphs.last().cloned(), b.glyphs.first().cloned())
+    else {
+        return;
+    };
+
+    let amount = add_cjk_latin_spacing(&mut last, &mut first);
+    if amount.is_zero() {
+        return;
+    }
+
+    if !last.autospace.1.is_zero() {
+        *a.glyphs.to_mut().last_mut().unwrap() = last;
+        a.width += amount.at(a.size);
+    } else {
+        *b.glyphs.to_mut().first_mut().unwrap() = first;
+        b.width += amount.at(b.size);
+    }
+}
+
 /// Whether this is not a specific script.
 fn is_generic_script(script: Script) -> bool {
     matches!(script, Script::Unknown | Script::Common | Script::Inherited)
@@ -901,10 +1467,18 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
 
     // Cost parameters.
     const HYPH_COST: Cost = 0.5;
+    const RUNT_COST: Cost = 0.5;
     const CONSECUTIVE_DASH_COST: Cost = 300.0;
     const MAX_COST: Cost = 1_000_000.0;
     const MIN_RATIO: f64 = -1.0;
 
+    // Scale the base costs by the user-configured ratios.
+    let costs = ParElem::costs_in(p.styles);
+    let hyph_cost = HYPH_COST * costs.hyphenation().get();
+    let runt_cost = RUNT_COST * costs.runt().get();
+    let consecutive_dash_cost =
+        CONSECUTIVE_DASH_COST * costs.consecutive_hyphenation().get();
+
     // Dynamic programming table.
     let mut active = 0;
     let mut table = vec![Entry {
@@ -979,18 +1553,28 @@ fn linebreak_optimized<'a>(vt: &Vt, p: &'a Preparation<'a>, width: Abs) -> Vec<L
 
             // Penalize hyphens.
             if hyphen {
-                cost += HYPH_COST;
+                cost += hyph_cost;
+            }
+
+            // Penalize runts, i.e. a last line that consists of just a single
+            // word. Counting breakpoints isn't enough here because hyphenation
+            // opportunities are breakpoints, too.
+            if eof && i > 0 {
+                let text = &p.bidi.text[attempt.trimmed.clone()];
+                if is_single_word(p, text) {
+                    cost += runt_cost;
+                }
             }
 
             // In Knuth paper, cost = (1 + 100|r|^3 + p)^2 + a,
             // where r is the ratio, p=50 is penaty, and a=3000 is consecutive penaty.
             // We divide the whole formula by 10, resulting (0.01 + |r|^3 + p)^2 + a,
-            // where p=0.5 and a=300
+            // where p=0.5 and a=300 by default
             cost = (0.01 + cost).powi(2);
 
             // Penalize two consecutive dashes (not necessarily hyphens) extra.
             if attempt.dash && pred.line.dash {
-                cost += CONSECUTIVE_DASH_COST;
+                cost += consecutive_dash_cost;
             }
 
             // The total cost of this line and its chain of predecessors.
@@ -1062,6 +1646,21 @@ static LINEBREAK_DATA: Lazy<CodePointMapData<LineBreak>> = Lazy::new(|| {
     icu_properties::maps::load_line_break(&deser_provider).unwrap()
 });
 
+/// Whether the text contains no line break opportunity apart from its start
+/// and end, i.e. whether it consists of a single word.
+///
+/// Uses the same segmenter as [`breakpoints`] so that this also works for
+/// scripts that don't separate words with spaces.
+fn is_single_word(p: &Preparation, text: &str) -> bool {
+    let segmenter = if matches!(p.lang, Some(Lang::CHINESE | Lang::JAPANESE)) {
+        &*CJ_SEGMENTER
+    } else {
+        &*SEGMENTER
+    };
+
+    segmenter.segment_str(text).all(|i| i == 0 || i == text.len())
+}
+
 /// Determine all possible points in the text where lines can broken.
 ///
 /// Returns for each breakpoint the text index, whether the break is mandatory
@@ -1230,10 +1829,11 @@ fn line<'a>(
         dash = hyphen || shy || trimmed.ends_with(['-', '–', '—']);
         justify |= text.ends_with('\u{2028}');
 
-        // Deal with CJK punctuation at line ends.
+        // Deal with CJK punctuation and CJK-Latin spacing at line ends.
         let gb_style = is_gb_style(shaped.lang, shaped.region);
-        let end_cjk_punct = trimmed
-            .ends_with(['”', '’', '，', '。', '、', '：', '；', '》', '）', '』', '」']);
+        let end_cjk_punct = trimmed.ends_with(END_CJK_PUNCTUATION);
+        let end_autospace = start + shaped.text.len() == range.end
+            && shaped.glyphs.last().map_or(false, |g| !g.autospace.1.is_zero());
 
         // Usually, we don't want to shape an empty string because:
         // - We don't want the height of trimmed whitespace in a different
@@ -1244,9 +1844,20 @@ fn line<'a>(
         // need the shaped empty string to make the line the appropriate
         // height. That is the case exactly if the string is empty and there
         // are no other items in the line.
-        if hyphen || start + shaped.text.len() > range.end || end_cjk_punct {
+        if hyphen
+            || start + shaped.text.len() > range.end
+            || end_cjk_punct
+            || end_autospace
+        {
             if hyphen || start < range.end || before.is_empty() {
                 let mut reshaped = shaped.reshape(vt, &p.spans, start..range.end);
+                if let Some(last) = reshaped.glyphs.last() {
+                    if !last.autospace.1.is_zero() {
+                        let last = reshaped.glyphs.to_mut().last_mut().unwrap();
+                        let amount = last.trim_autospace_right();
+                        reshaped.width -= amount.at(reshaped.size);
+                    }
+                }
                 if hyphen || shy {
                     reshaped.push_hyphen(vt);
                 }
@@ -1267,9 +1878,13 @@ fn line<'a>(
         }
     }
 
-    // Deal with CJK punctuation at line starts.
+    // Deal with CJK punctuation and CJK-Latin spacing at line starts.
     let text = &p.bidi.text[range.start..end];
-    let start_cjk_punct = text.starts_with(['“', '‘', '《', '（', '『', '「']);
+    let start_cjk_punct = text.starts_with(START_CJK_PUNCTUATION);
+    let start_autospace = matches!(
+        inner.first(),
+        Some(Item::Text(shaped)) if shaped.glyphs.first().map_or(false, |g| !g.autospace.0.is_zero())
+    );
 
     // Reshape the start item if it's split in half.
     let mut first = None;
@@ -1279,9 +1894,16 @@ fn line<'a>(
         let end = range.end.min(base + shaped.text.len());
 
         // Reshape if necessary.
-        if range.start + shaped.text.len() > end || start_cjk_punct {
+        if range.start + shaped.text.len() > end || start_cjk_punct || start_autospace {
             if range.start < end || start_cjk_punct {
-                let reshaped = shaped.reshape(vt, &p.spans, range.start..end);
+                let mut reshaped = shaped.reshape(vt, &p.spans, range.start..end);
+                if let Some(first) = reshaped.glyphs.first() {
+                    if !first.autospace.0.is_zero() {
+                        let first = reshaped.glyphs.to_mut().first_mut().unwrap();
+                        let amount = first.trim_autospace_left();
+                        reshaped.width -= amount.at(reshaped.size);
+                    }
+                }
                 width += reshaped.width;
                 first = Some(Item::Text(reshaped));
             }
@@ -1342,23 +1964,41 @@ fn finalize(
         region.x
     };
 
-    // Stack the lines into one frame per region.
+    // Stack the lines into one frame per region and mark them for line
+    // numbering.
     let mut frames: Vec<Frame> = lines
         .iter()
-        .map(|line| commit(vt, p, line, width, region.y))
+        .map(|line| {
+            let mut frame = commit(vt, p, line, width, region.y)?;
+            let text = &p.bidi.text[line.trimmed.clone()];
+            if let Some(marker) = ParLine::marker(vt, text, p.styles) {
+                let pos = Point::with_y(frame.baseline());
+                frame.push(pos, FrameItem::Meta(Meta::Elem(marker), Size::zero()));
+            }
+            Ok(frame)
+        })
         .collect::<SourceResult<_>>()?;
 
-    // Prevent orphans.
+    // Prevent orphans by merging the first lines into one frame, which the
+    // flow layouter won't split.
     let leading = ParElem::leading_in(p.styles);
-    if frames.len() >= 2 && !frames[1].is_empty() {
+    let orphans = ParElem::orphans_in(p.styles).get();
+    for _ in 1..orphans {
+        if frames.len() < 2 || frames[1].is_empty() {
+            break;
+        }
         let second = frames.remove(1);
         let first = &mut frames[0];
         merge(first, second, leading);
     }
 
-    // Prevent widows.
-    let len = frames.len();
-    if len >= 2 && !frames[len - 2].is_empty() {
+    // Prevent widows in the same way with the last lines.
+    let widows = ParElem::widows_in(p.styles).get();
+    for _ in 1..widows {
+        let len = frames.len();
+        if len < 2 || frames[len - 2].is_empty() {
+            break;
+        }
         let second = frames.pop().unwrap();
         let first = frames.last_mut().unwrap();
         merge(first, second, leading);
@@ -28,7 +28,9 @@ pub struct PlaceElem {
     /// Relative to which position in the parent container to place the content.
     ///
     /// Cannot be `{auto}` if `float` is `{false}` and must be either
-    /// `{auto}`, `{top}`, or `{bottom}` if `float` is `{true}`.
+    /// `{auto}`, `{top}`, or `{bottom}` if `float` is `{true}`. When a floating
+    /// element has an explicit [placement]($func/place.placement), the
+    /// alignment may also be purely horizontal.
     ///
     /// When an axis of the page is `{auto}` sized, all alignments relative to
     /// that axis will be ignored, instead, the item will be placed in the
@@ -60,11 +62,60 @@ pub struct PlaceElem {
     /// ```
     pub float: bool,
 
+    /// Where a floating element may be placed, in order of preference.
+    ///
+    /// - `{auto}`: The element is placed according to its
+    ///   [alignment]($func/place.alignment). This is the default.
+    /// - `{top}` or `{bottom}`: At the top or bottom of the page.
+    /// - `{"here"}`: In the flow, exactly where it was specified, like a
+    ///   non-floating element.
+    /// - `{"page"}`: On a page that contains only floats, following the
+    ///   current one.
+    /// - An array of these: Each of the positions is tried in order until the
+    ///   element fits.
+    ///
+    /// If the element fits nowhere, it is deferred to the next page, where
+    /// the positions are tried again, except for `{"here"}`. Floats are never
+    /// placed before earlier floats that were deferred, so that they stay in
+    /// order.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #let note(body) = place(
+    ///   float: true,
+    ///   placement: ("here", bottom),
+    ///   clearance: 6pt,
+    ///   rect(body),
+    /// )
+    ///
+    /// #lorem(10)
+    /// #note[Here]
+    /// #lorem(30)
+    /// #note[Bottom]
+    /// #lorem(10)
+    /// ```
+    pub placement: Smart<Placement>,
+
     /// The amount of clearance the placed element has in a floating layout.
     #[default(Em::new(1.5).into())]
     #[resolve]
     pub clearance: Length,
 
+    /// The maximum number of floats at the top and bottom of a page.
+    ///
+    /// Further floats are deferred to the next page. This property can only be
+    /// configured for the whole page with a set rule.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #set place(limit: 1)
+    ///
+    /// #place(top, float: true, rect[A])
+    /// #place(top, float: true, rect[B])
+    /// #lorem(10)
+    /// ```
+    pub limit: Option<usize>,
+
     /// The horizontal displacement of the placed content.
     ///
     /// ```example
@@ -121,6 +172,7 @@ impl PlaceElem {
 
         let float = self.float(styles);
         let alignment = self.alignment(styles);
+        let placed = self.placement(styles).is_custom();
         if float
             && !matches!(
                 alignment,
@@ -130,6 +182,7 @@ impl PlaceElem {
                         ..
                     })
             )
+            && !(placed && matches!(alignment, Smart::Custom(Axes { y: None, .. })))
         {
             bail!(self.span(), "floating placement must be `auto`, `top`, or `bottom`");
         } else if !float && alignment.is_auto() {
@@ -154,3 +207,54 @@ impl Behave for PlaceElem {
         Behaviour::Ignorant
     }
 }
+
+/// A position a floating element can take.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FloatPosition {
+    /// In the flow, where the element was specified.
+    Here,
+    /// At the top of the page.
+    Top,
+    /// At the bottom of the page.
+    Bottom,
+    /// On a page of its own.
+    Page,
+}
+
+cast! {
+    FloatPosition,
+    self => match self {
+        Self::Here => "here".into_value(),
+        Self::Top => GenAlign::Specific(Align::Top).into_value(),
+        Self::Bottom => GenAlign::Specific(Align::Bottom).into_value(),
+        Self::Page => "page".into_value(),
+    },
+    /// In the flow, where the element was specified.
+    "here" => Self::Here,
+    /// On a page that contains only floats.
+    "page" => Self::Page,
+    v: GenAlign => match v {
+        GenAlign::Specific(Align::Top) => Self::Top,
+        GenAlign::Specific(Align::Bottom) => Self::Bottom,
+        _ => bail!("must be `top` or `bottom`"),
+    },
+}
+
+/// The positions a floating element may take, in order of preference.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Placement(pub Vec<FloatPosition>);
+
+cast! {
+    Placement,
+    self => match self.0.as_slice() {
+        [position] => position.into_value(),
+        _ => self.0.into_value(),
+    },
+    v: FloatPosition => Self(vec![v]),
+    v: Array => {
+        if v.is_empty() {
+            bail!("placement must contain at least one position");
+        }
+        Self(v.into_iter().map(Value::cast).collect::<StrResult<_>>()?)
+    },
+}
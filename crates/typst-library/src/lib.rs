@@ -14,10 +14,13 @@ pub mod symbols;
 pub mod text;
 pub mod visualize;
 
-use typst::diag::At;
-use typst::eval::{LangItems, Library, Module, Scope};
+use typst::eval::{
+    Datetime, Decimal, Duration, LangItems, Library, Module, Regex, Scope, Str, Symbol,
+    Type,
+};
 use typst::geom::Smart;
-use typst::model::{Element, Styles};
+use typst::model::{Element, Label, Styles};
+use typst::util::Bytes;
 
 use self::layout::LayoutRoot;
 
@@ -131,15 +134,33 @@ fn items() -> LangItems {
         math_root: |index, radicand| {
             math::RootElem::new(radicand).with_index(index).pack()
         },
-        library_method: |vm, dynamic, method, args, span| {
-            if let Some(counter) = dynamic.downcast::<meta::Counter>().cloned() {
-                counter.call_method(vm, method, args, span)
-            } else if let Some(state) = dynamic.downcast::<meta::State>().cloned() {
-                state.call_method(vm, method, args, span)
-            } else {
-                Err(format!("type {} has no method `{method}`", dynamic.type_name()))
-                    .at(span)
-            }
+        library_methods: |ty| {
+            [
+                (Type::of::<meta::Counter>(), meta::Counter::METHODS),
+                (Type::of::<meta::State>(), meta::State::METHODS),
+            ]
+            .into_iter()
+            .find(|&(other, _)| other == ty)
+            .map(|(_, methods)| methods)
+            .unwrap_or_default()
+        },
+        type_constructor: |ty| {
+            [
+                (Type::of::<Type>(), compute::type_func()),
+                (Type::of::<i64>(), compute::int_func()),
+                (Type::of::<f64>(), compute::float_func()),
+                (Type::of::<Decimal>(), compute::decimal_func()),
+                (Type::of::<Str>(), compute::str_func()),
+                (Type::of::<Bytes>(), compute::bytes_func()),
+                (Type::of::<Label>(), compute::label_func()),
+                (Type::of::<Symbol>(), compute::symbol_func()),
+                (Type::of::<Datetime>(), compute::datetime_func()),
+                (Type::of::<Duration>(), compute::duration_func()),
+                (Type::of::<Regex>(), compute::regex_func()),
+            ]
+            .into_iter()
+            .find(|&(other, _)| other == ty)
+            .map(|(_, func)| func)
         },
     }
 }
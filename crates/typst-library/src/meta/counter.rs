@@ -307,32 +307,21 @@ impl Counter {
         Self::new(CounterKey::Selector(Selector::Elem(func, None)))
     }
 
-    /// Call a method on counter.
-    #[tracing::instrument(skip(vm))]
-    pub fn call_method(
-        self,
-        vm: &mut Vm,
-        method: &str,
-        mut args: Args,
-        span: Span,
-    ) -> SourceResult<Value> {
-        let value = match method {
-            "display" => self
-                .display(args.eat()?, args.named("both")?.unwrap_or(false))
-                .into_value(),
-            "step" => self
-                .update(CounterUpdate::Step(
-                    args.named("level")?.unwrap_or(NonZeroUsize::ONE),
-                ))
-                .into_value(),
-            "update" => self.update(args.expect("value or function")?).into_value(),
-            "at" => self.at(&mut vm.vt, args.expect("location")?)?.into_value(),
-            "final" => self.final_(&mut vm.vt, args.expect("location")?)?.into_value(),
-            _ => bail!(span, "type counter has no method `{}`", method),
-        };
-        args.finish()?;
-        Ok(value)
-    }
+    /// The methods available on counters.
+    pub const METHODS: &'static [NativeMethod] = native_methods! {
+        |vm, counter: Counter, args, span|
+        "display"(..) => counter
+            .display(args.eat()?, args.named("both")?.unwrap_or(false))
+            .into_value(),
+        "step"(..) => counter
+            .update(CounterUpdate::Step(
+                args.named("level")?.unwrap_or(NonZeroUsize::ONE),
+            ))
+            .into_value(),
+        "update"(..) => counter.update(args.expect("value or function")?).into_value(),
+        "at"(..) => counter.at(&mut vm.vt, args.expect("location")?)?.into_value(),
+        "final"(..) => counter.final_(&mut vm.vt, args.expect("location")?)?.into_value(),
+    };
 
     /// Display the current value of the counter.
     pub fn display(self, numbering: Option<Numbering>, both: bool) -> Content {
@@ -633,15 +622,15 @@ impl Show for DisplayElem {
             let numbering = self
                 .numbering()
                 .or_else(|| {
-                    let CounterKey::Selector(Selector::Elem(func, _)) = counter.0 else {
+                    let CounterKey::Selector(Selector::Elem(func, _)) = &counter.0 else {
                         return None;
                     };
 
-                    if func == HeadingElem::func() {
+                    if *func == HeadingElem::func() {
                         HeadingElem::numbering_in(styles)
-                    } else if func == FigureElem::func() {
+                    } else if *func == FigureElem::func() {
                         FigureElem::numbering_in(styles)
-                    } else if func == EquationElem::func() {
+                    } else if *func == EquationElem::func() {
                         EquationElem::numbering_in(styles)
                     } else {
                         None
@@ -47,6 +47,7 @@ impl LayoutRoot for DocumentElem {
     fn layout_root(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Document> {
         tracing::info!("Document layout");
 
+        let limits = vt.world.limits();
         let mut pages = vec![];
 
         for mut child in &self.children() {
@@ -61,6 +62,11 @@ impl LayoutRoot for DocumentElem {
                 let number = NonZeroUsize::ONE.saturating_add(pages.len());
                 let fragment = page.layout(vt, styles, number)?;
                 pages.extend(fragment);
+
+                if pages.len() > limits.pages {
+                    let span = vt.world.main().root().span();
+                    bail!(error!(span, "maximum number of pages exceeded").into_fatal());
+                }
             } else {
                 bail!(child.span(), "unexpected document child");
             }
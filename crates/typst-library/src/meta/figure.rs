@@ -3,7 +3,7 @@ use std::str::FromStr;
 use super::{
     Count, Counter, CounterKey, CounterUpdate, LocalName, Numbering, NumberingPattern,
 };
-use crate::layout::{BlockElem, PlaceElem, VElem};
+use crate::layout::{BlockElem, PlaceElem, Placement, VElem};
 use crate::meta::{Outlinable, Refable, Supplement};
 use crate::prelude::*;
 use crate::text::TextElem;
@@ -91,6 +91,11 @@ pub struct FigureElem {
     ///   is closer.
     /// - `{top}`: The figure floats to the top of the page.
     /// - `{bottom}`: The figure floats to the bottom of the page.
+    /// - `{"here"}`: The figure stays in-flow if it fits and floats otherwise.
+    /// - `{"page"}`: The figure is put onto a page that only contains floats.
+    /// - An array of these: The positions are tried in order of preference.
+    ///   See the [place function's placement]($func/place.placement) for
+    ///   details on how floats are deferred.
     ///
     /// ```example
     /// #set page(height: 200pt)
@@ -103,7 +108,7 @@ pub struct FigureElem {
     /// )
     /// #lorem(60)
     /// ```
-    pub placement: Option<Smart<VerticalAlign>>,
+    pub placement: Option<Smart<Placement>>,
 
     /// The figure's caption.
     pub caption: Option<Content>,
@@ -226,7 +231,7 @@ impl Synthesize for FigureElem {
                 // Default to the local name for the kind, if available.
                 let name = match &kind {
                     FigureKind::Elem(func) => {
-                        let empty = Content::new(*func);
+                        let empty = Content::new(func.clone());
                         empty.with::<dyn LocalName>().map(|c| {
                             TextElem::packed(c.local_name(
                                 TextElem::lang_in(styles),
@@ -247,10 +252,11 @@ impl Synthesize for FigureElem {
             Smart::Custom(Some(supplement)) => {
                 // Resolve the supplement with the first descendant of the kind or
                 // just the body, if none was found.
-                let descendant = match kind {
-                    FigureKind::Elem(func) => {
-                        self.body().query_first(Selector::Elem(func, None)).cloned()
-                    }
+                let descendant = match &kind {
+                    FigureKind::Elem(func) => self
+                        .body()
+                        .query_first(Selector::Elem(func.clone(), None))
+                        .cloned(),
                     FigureKind::Name(_) => None,
                 };
 
@@ -305,12 +311,11 @@ impl Show for FigureElem {
             .aligned(Axes::with_x(Some(Align::Center.into())));
 
         // Wrap in a float.
-        if let Some(align) = self.placement(styles) {
+        if let Some(placement) = self.placement(styles) {
             realized = PlaceElem::new(realized)
+                .with_alignment(Smart::Auto)
                 .with_float(true)
-                .with_alignment(align.map(|VerticalAlign(align)| {
-                    Axes::new(Some(Align::Center.into()), Some(align))
-                }))
+                .with_placement(placement)
                 .pack();
         }
 
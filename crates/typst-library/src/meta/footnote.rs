@@ -1,8 +1,9 @@
 use comemo::Prehashed;
+use smallvec::smallvec;
 use std::str::FromStr;
 
-use super::{Counter, Numbering, NumberingPattern};
-use crate::layout::{HElem, ParElem};
+use super::{Counter, CounterState, HeadingElem, LocalName, Numbering, NumberingPattern};
+use crate::layout::{HElem, ParElem, ParbreakElem};
 use crate::meta::{Count, CounterUpdate};
 use crate::prelude::*;
 use crate::text::{SuperElem, TextElem, TextSize};
@@ -48,6 +49,10 @@ cast! {
 /// there is a space before it in the markup. To force space, you can use the
 /// string `[#" "]` or explicit [horizontal spacing]($func/h).
 ///
+/// Footnotes can also be collected as endnotes at the end of a chapter or the
+/// document instead. See the [`placement`]($func/footnote.placement) property
+/// and the [`endnotes`]($func/endnotes) function for details.
+///
 /// By giving a label to a footnote, you can have multiple references to it.
 ///
 /// ```example
@@ -73,9 +78,8 @@ pub struct FootnoteElem {
     /// How to number footnotes.
     ///
     /// By default, the footnote numbering continues throughout your document.
-    /// If you prefer per-page footnote numbering, you can reset the footnote
-    /// [counter]($func/counter) in the page [header]($func/page.header). In the
-    /// future, there might be a simpler way to achieve this.
+    /// To restart it on each page or chapter, see the
+    /// [`reset`]($func/footnote.reset) property.
     ///
     /// ```example
     /// #set footnote(numbering: "*")
@@ -87,6 +91,46 @@ pub struct FootnoteElem {
     #[default(Numbering::Pattern(NumberingPattern::from_str("1").unwrap()))]
     pub numbering: Numbering,
 
+    /// Where to place the footnote's content.
+    ///
+    /// - `{"page"}`: At the bottom of the page. This is the default.
+    /// - `{"endnote"}`: In the list of the next [`endnotes`]($func/endnotes)
+    ///   element.
+    ///
+    /// ```example
+    /// #set footnote(placement: "endnote")
+    ///
+    /// Ravens#footnote[_Corvus corax_]
+    /// and crows#footnote[_Corvus corone_]
+    /// are related.
+    ///
+    /// #endnotes()
+    /// ```
+    #[default(FootnotePlacement::Page)]
+    pub placement: FootnotePlacement,
+
+    /// When to restart the footnote numbering.
+    ///
+    /// - `{none}`: Never. This is the default.
+    /// - `{"page"}`: On each page. For this to take effect, the property must
+    ///   be set before the page starts, e.g. at the top of the document.
+    /// - An integer: At each heading of the given level or a higher one. With
+    ///   `{1}`, the numbering restarts with every top-level heading.
+    ///
+    /// The restart is a regular update of the footnote
+    /// [counter]($func/counter), so `{counter(footnote)}` reflects it.
+    ///
+    /// ```example
+    /// #set footnote(reset: 1)
+    ///
+    /// = Cats
+    /// Cats purr.#footnote[Mostly.]
+    ///
+    /// = Dogs
+    /// Dogs bark.#footnote[Loudly.]
+    /// ```
+    pub reset: Option<FootnoteReset>,
+
     /// The content to put into the footnote. Can also be the label of another
     /// footnote this one should point to.
     #[required]
@@ -132,9 +176,28 @@ impl FootnoteElem {
     }
 }
 
+impl FootnoteElem {
+    /// Whether this footnote is placed into a list of endnotes.
+    pub fn is_endnote(&self) -> bool {
+        self.placement(StyleChain::default()) == FootnotePlacement::Endnote
+    }
+
+    /// The number of the footnote declared at the given location.
+    fn number(vt: &mut Vt, location: Location) -> SourceResult<CounterState> {
+        Counter::of(Self::func()).at(vt, location)
+    }
+
+    /// A counter update that restarts the footnote numbering.
+    pub(crate) fn reset_counter() -> Content {
+        Counter::of(Self::func()).update(CounterUpdate::Set(CounterState(smallvec![0])))
+    }
+}
+
 impl Synthesize for FootnoteElem {
     fn synthesize(&mut self, _vt: &mut Vt, styles: StyleChain) -> SourceResult<()> {
         self.push_numbering(self.numbering(styles));
+        self.push_placement(self.placement(styles));
+        self.push_reset(self.reset(styles));
         Ok(())
     }
 }
@@ -145,8 +208,7 @@ impl Show for FootnoteElem {
         Ok(vt.delayed(|vt| {
             let loc = self.declaration_location(vt).at(self.span())?;
             let numbering = self.numbering(styles);
-            let counter = Counter::of(Self::func());
-            let num = counter.at(vt, loc)?.display(vt, &numbering)?;
+            let num = Self::number(vt, loc)?.display(vt, &numbering)?;
             let sup = SuperElem::new(num).pack();
             let hole = HElem::new(Abs::zero().into()).with_weak(true).pack();
             let loc = loc.variant(1);
@@ -267,9 +329,8 @@ impl Show for FootnoteEntry {
         let note = self.note();
         let number_gap = Em::new(0.05);
         let numbering = note.numbering(StyleChain::default());
-        let counter = Counter::of(FootnoteElem::func());
         let loc = note.0.location().unwrap();
-        let num = counter.at(vt, loc)?.display(vt, &numbering)?;
+        let num = FootnoteElem::number(vt, loc)?.display(vt, &numbering)?;
         let sup = SuperElem::new(num)
             .pack()
             .linked(Destination::Location(loc))
@@ -297,3 +358,159 @@ cast! {
     FootnoteElem,
     v: Content => v.to::<Self>().cloned().unwrap_or_else(|| Self::with_content(v.clone())),
 }
+
+/// Where a footnote's content is placed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum FootnotePlacement {
+    /// At the bottom of the page.
+    Page,
+    /// In the list of the next `endnotes` element.
+    Endnote,
+}
+
+/// When to restart the footnote numbering.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FootnoteReset {
+    /// On each page.
+    Page,
+    /// At each heading of the given level or a higher one.
+    Heading(NonZeroUsize),
+}
+
+cast! {
+    FootnoteReset,
+    self => match self {
+        Self::Page => "page".into_value(),
+        Self::Heading(level) => level.into_value(),
+    },
+    /// Restart on each page.
+    "page" => Self::Page,
+    v: NonZeroUsize => Self::Heading(v),
+}
+
+/// A list of endnotes.
+///
+/// Lists the content of all footnotes with the `{"endnote"}`
+/// [placement]($func/footnote.placement) since the previous list of endnotes.
+/// To collect the notes at the end of each chapter, put an `endnotes` element
+/// at the end of each chapter. Notes that aren't followed by any list of
+/// endnotes are not displayed.
+///
+/// The entries can be customized just like the ones of footnotes with the
+/// [`footnote.entry`]($func/footnote.entry) element.
+///
+/// ## Example { #example }
+/// ```example
+/// #set footnote(placement: "endnote", reset: 1)
+///
+/// = Cats
+/// Cats purr.#footnote[Mostly.]
+/// #endnotes()
+///
+/// = Dogs
+/// Dogs bark.#footnote[Loudly.]
+/// #endnotes()
+/// ```
+///
+/// Display: Endnotes
+/// Category: meta
+#[element(Locatable, Show, Finalize, LocalName)]
+pub struct EndnotesElem {
+    /// The title of the list.
+    ///
+    /// - When set to `{auto}`, an appropriate title for the
+    ///   [text language]($func/text.lang) will be used. This is the default.
+    /// - When set to `{none}`, the list will not have a title.
+    /// - A custom title can be set by passing content.
+    ///
+    /// The title is shown as a heading of the level given by
+    /// [`level`]($func/endnotes.level).
+    #[default(Some(Smart::Auto))]
+    pub title: Option<Smart<Content>>,
+
+    /// The level of the title's heading.
+    #[default(NonZeroUsize::new(2).unwrap())]
+    pub level: NonZeroUsize,
+}
+
+impl Show for EndnotesElem {
+    #[tracing::instrument(name = "EndnotesElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let mut seq = vec![ParbreakElem::new().pack()];
+        if let Some(title) = self.title(styles) {
+            let title = title.unwrap_or_else(|| {
+                TextElem::packed(self.local_name_in(styles)).spanned(self.span())
+            });
+
+            seq.push(HeadingElem::new(title).with_level(self.level(styles)).pack());
+        }
+
+        let location = self.0.location().unwrap();
+        seq.push(vt.delayed(|vt| {
+            let previous = vt
+                .introspector
+                .query(&Selector::Elem(Self::func(), None).before(location, false))
+                .last()
+                .and_then(|elem| elem.location());
+
+            let mut selector =
+                Selector::Elem(FootnoteElem::func(), None).before(location, false);
+            if let Some(previous) = previous {
+                selector = selector.after(previous, false);
+            }
+
+            let mut entries = vec![];
+            for elem in vt.introspector.query(&selector) {
+                let note = elem.to::<FootnoteElem>().unwrap();
+                if note.is_endnote() && !note.is_ref() {
+                    entries.push(FootnoteEntry::new(note.clone()).pack());
+                    entries.push(ParbreakElem::new().pack());
+                }
+            }
+
+            Ok(Content::sequence(entries))
+        }));
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+impl Finalize for EndnotesElem {
+    fn finalize(&self, realized: Content, _: StyleChain) -> Content {
+        // The title's heading neither gets a number nor restarts the footnote
+        // numbering.
+        realized
+            .styled(HeadingElem::set_numbering(None))
+            .styled(FootnoteElem::set_reset(None))
+    }
+}
+
+impl LocalName for EndnotesElem {
+    fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
+        match lang {
+            Lang::ALBANIAN => "Shënime",
+            Lang::ARABIC => "ملاحظات",
+            Lang::BOKMÅL => "Noter",
+            Lang::CHINESE => "注释",
+            Lang::CZECH => "Poznámky",
+            Lang::DANISH => "Noter",
+            Lang::DUTCH => "Noten",
+            Lang::FILIPINO => "Mga Tala",
+            Lang::FRENCH => "Notes",
+            Lang::GERMAN => "Anmerkungen",
+            Lang::ITALIAN => "Note",
+            Lang::NYNORSK => "Notar",
+            Lang::POLISH => "Przypisy",
+            Lang::PORTUGUESE => "Notas",
+            Lang::RUSSIAN => "Примечания",
+            Lang::SLOVENIAN => "Opombe",
+            Lang::SPANISH => "Notas",
+            Lang::SWEDISH => "Noter",
+            Lang::TURKISH => "Notlar",
+            Lang::UKRAINIAN => "Примітки",
+            Lang::VIETNAMESE => "Ghi chú",
+            Lang::JAPANESE => "注",
+            Lang::ENGLISH | _ => "Notes",
+        }
+    }
+}
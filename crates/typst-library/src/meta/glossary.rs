@@ -0,0 +1,264 @@
+use super::{collator, page_ranges, Counter, CounterKey, HeadingElem, LocalName};
+use crate::layout::{ParbreakElem, TermItem, TermsElem};
+use crate::prelude::*;
+use crate::text::TextElem;
+
+/// A glossary of the terms used in the document.
+///
+/// Terms are declared with [`glossary.entry`]($func/glossary.entry) and used by
+/// referencing their key, either with reference syntax (`[@tls]`) or with
+/// [`glossary.term`]($func/glossary.term). The first use of a term with a long
+/// form shows both forms, like "Transport Layer Security (TLS)", while later
+/// uses only show the short form.
+///
+/// The glossary lists only the terms that are used in the document, sorted
+/// alphabetically, together with the pages they are used on.
+///
+/// ## Example { #example }
+/// ```example
+/// #glossary.entry(
+///   "tls",
+///   short: "TLS",
+///   long: [Transport Layer Security],
+///   description: [A protocol for secure connections.],
+/// )
+///
+/// Servers speak @tls. Every @tls
+/// connection starts with a handshake.
+///
+/// #glossary()
+/// ```
+///
+/// ## Restarting first uses { #restarting-first-uses }
+/// Uses of a term are counted with a [counter]($func/counter). To make the
+/// next use of a term show its long form again, for example in a new chapter,
+/// reset its counter:
+/// `{counter(glossary.term.where(key: "tls")).update(0)}`.
+///
+/// Display: Glossary
+/// Category: meta
+/// Keywords: Acronyms
+#[element(Show, Finalize, LocalName, Locatable)]
+#[scope(
+    scope.define("entry", GlossaryEntry::func());
+    scope.define("term", GlossaryTerm::func());
+    scope
+)]
+pub struct GlossaryElem {
+    /// The title of the glossary.
+    ///
+    /// - When set to `{auto}`, an appropriate title for the
+    ///   [text language]($func/text.lang) will be used. This is the default.
+    /// - When set to `{none}`, the glossary will not have a title.
+    /// - A custom title can be set by passing content.
+    #[default(Some(Smart::Auto))]
+    pub title: Option<Smart<Content>>,
+}
+
+impl Show for GlossaryElem {
+    #[tracing::instrument(name = "GlossaryElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let mut seq = vec![ParbreakElem::new().pack()];
+        if let Some(title) = self.title(styles) {
+            let title = title.unwrap_or_else(|| {
+                TextElem::packed(self.local_name_in(styles)).spanned(self.span())
+            });
+
+            seq.push(HeadingElem::new(title).with_level(NonZeroUsize::ONE).pack());
+        }
+
+        // Collect the used entries together with the locations of their uses.
+        let mut used = vec![];
+        for elem in vt.introspector.query(&Selector::Elem(GlossaryEntry::func(), None)) {
+            let entry = elem.to::<GlossaryEntry>().unwrap().clone();
+            let uses = vt.introspector.query(&GlossaryTerm::selector(&entry.key()));
+            if !uses.is_empty() {
+                let locations = uses.iter().filter_map(|elem| elem.location()).collect();
+                used.push((entry.short().plain_text(), entry, locations));
+            }
+        }
+
+        let collator = collator(styles).at(self.span())?;
+        used.sort_by(|(a, ..), (b, ..)| collator.compare(a, b).then_with(|| a.cmp(b)));
+
+        let mut items = vec![];
+        for (_, entry, locations) in used {
+            let mut description = vec![];
+            if let Some(long) = entry.long(StyleChain::default()) {
+                description.push(long);
+            }
+
+            if let Some(more) = entry.description(StyleChain::default()) {
+                if !description.is_empty() {
+                    description.push(TextElem::packed(": "));
+                }
+                description.push(more);
+            }
+
+            for page in page_ranges(vt, &locations)? {
+                if !description.is_empty() {
+                    description.push(TextElem::packed(", "));
+                }
+                description.push(page);
+            }
+
+            items.push(TermItem::new(entry.short(), Content::sequence(description)));
+        }
+
+        seq.push(TermsElem::new(items).pack());
+        seq.push(ParbreakElem::new().pack());
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+impl Finalize for GlossaryElem {
+    fn finalize(&self, realized: Content, _: StyleChain) -> Content {
+        realized.styled(HeadingElem::set_numbering(None))
+    }
+}
+
+impl LocalName for GlossaryElem {
+    fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
+        match lang {
+            Lang::ALBANIAN => "Fjalorth",
+            Lang::ARABIC => "مسرد المصطلحات",
+            Lang::BOKMÅL => "Ordliste",
+            Lang::CHINESE => "术语表",
+            Lang::CZECH => "Slovník pojmů",
+            Lang::DANISH => "Ordliste",
+            Lang::DUTCH => "Woordenlijst",
+            Lang::FILIPINO => "Talahuluganan",
+            Lang::FRENCH => "Glossaire",
+            Lang::GERMAN => "Glossar",
+            Lang::ITALIAN => "Glossario",
+            Lang::NYNORSK => "Ordliste",
+            Lang::POLISH => "Słowniczek",
+            Lang::PORTUGUESE => "Glossário",
+            Lang::RUSSIAN => "Глоссарий",
+            Lang::SLOVENIAN => "Slovar pojmov",
+            Lang::SPANISH => "Glosario",
+            Lang::SWEDISH => "Ordlista",
+            Lang::TURKISH => "Sözlük",
+            Lang::UKRAINIAN => "Глосарій",
+            Lang::VIETNAMESE => "Bảng thuật ngữ",
+            Lang::JAPANESE => "用語集",
+            Lang::ENGLISH | _ => "Glossary",
+        }
+    }
+}
+
+/// Declares a term for the [glossary]($func/glossary).
+///
+/// The declaration itself is invisible and can be placed anywhere in the
+/// document.
+///
+/// Display: Glossary Entry
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct GlossaryEntry {
+    /// The key with which the term is referenced.
+    #[required]
+    pub key: EcoString,
+
+    /// The short form of the term, like "TLS".
+    #[required]
+    pub short: Content,
+
+    /// The long form of the term, like "Transport Layer Security".
+    ///
+    /// If given, the first use of the term shows the long form followed by the
+    /// short form in parentheses.
+    pub long: Option<Content>,
+
+    /// A description of the term for the glossary.
+    pub description: Option<Content>,
+}
+
+impl Show for GlossaryEntry {
+    #[tracing::instrument(name = "GlossaryEntry::show", skip(self))]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+impl GlossaryEntry {
+    /// Find the entry with the given key.
+    pub fn find(introspector: Tracked<Introspector>, key: &str) -> StrResult<Self> {
+        let selector = Selector::Elem(Self::func(), Some(dict! { "key" => key }));
+        let entries = introspector.query(&selector);
+        match entries.as_slice() {
+            [entry] => Ok(entry.to::<Self>().unwrap().clone()),
+            [] => bail!("glossary entry `{key}` does not exist"),
+            _ => bail!("glossary entry `{key}` is declared multiple times"),
+        }
+    }
+}
+
+/// A use of a term from the [glossary]($func/glossary).
+///
+/// Referencing the key of a glossary entry with `[@key]` syntax produces this
+/// element, too. Its first use shows the entry's long form followed by the
+/// short form, while later uses only show the short form. The term links to
+/// the glossary if the document has one.
+///
+/// ```example
+/// #glossary.entry(
+///   "html",
+///   short: "HTML",
+///   long: [HyperText Markup Language],
+/// )
+///
+/// #glossary.term("html") and
+/// #glossary.term("html").
+/// ```
+///
+/// Display: Glossary Term
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct GlossaryTerm {
+    /// The key of the glossary entry.
+    #[required]
+    pub key: EcoString,
+}
+
+impl GlossaryTerm {
+    /// Selects all uses of the entry with the given key.
+    fn selector(key: &str) -> Selector {
+        Selector::Elem(Self::func(), Some(dict! { "key" => key }))
+    }
+}
+
+impl Show for GlossaryTerm {
+    #[tracing::instrument(name = "GlossaryTerm::show", skip_all)]
+    fn show(&self, vt: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(vt.delayed(|vt| {
+            let key = self.key();
+            let entry = GlossaryEntry::find(vt.introspector, &key).at(self.span())?;
+            let location = self.0.location().unwrap();
+
+            let uses = Counter::new(CounterKey::Selector(Self::selector(&key)))
+                .at(vt, location)?
+                .first();
+
+            let short = entry.short();
+            let content = match entry.long(StyleChain::default()) {
+                Some(long) if uses <= 1 => {
+                    long + TextElem::packed(" (") + short + TextElem::packed(")")
+                }
+                _ => short,
+            };
+
+            let glossary = vt
+                .introspector
+                .query(&Selector::Elem(GlossaryElem::func(), None))
+                .first()
+                .and_then(|glossary| glossary.location());
+
+            Ok(match glossary {
+                Some(location) => content.linked(Destination::Location(location)),
+                None => content,
+            })
+        }))
+    }
+}
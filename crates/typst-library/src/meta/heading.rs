@@ -1,7 +1,10 @@
 use typst::font::FontWeight;
 use typst::util::option_eq;
 
-use super::{Counter, CounterUpdate, LocalName, Numbering, Outlinable, Refable};
+use super::{
+    Counter, CounterUpdate, FootnoteElem, FootnoteReset, LocalName, Numbering,
+    Outlinable, Refable,
+};
 use crate::layout::{BlockElem, HElem, VElem};
 use crate::meta::{Count, Supplement};
 use crate::prelude::*;
@@ -161,7 +164,13 @@ impl Show for HeadingElem {
 
 impl Finalize for HeadingElem {
     fn finalize(&self, realized: Content, styles: StyleChain) -> Content {
-        let level = self.level(styles).get();
+        let level = self.level(styles);
+        let restarts_footnotes = matches!(
+            FootnoteElem::reset_in(styles),
+            Some(FootnoteReset::Heading(max)) if level <= max,
+        );
+
+        let level = level.get();
         let scale = match level {
             1 => 1.4,
             2 => 1.2,
@@ -178,7 +187,13 @@ impl Finalize for HeadingElem {
         styles.set(BlockElem::set_above(VElem::block_around(above.into())));
         styles.set(BlockElem::set_below(VElem::block_around(below.into())));
         styles.set(BlockElem::set_sticky(true));
-        realized.styled_with_map(styles)
+
+        let realized = realized.styled_with_map(styles);
+        if restarts_footnotes {
+            FootnoteElem::reset_counter() + realized
+        } else {
+            realized
+        }
     }
 }
 
@@ -0,0 +1,362 @@
+use std::str::FromStr;
+
+use icu_collator::{Collator, CollatorOptions};
+use icu_provider::DataLocale;
+
+use super::{
+    Counter, CounterKey, CounterState, HeadingElem, LinkElem, LocalName, Numbering,
+    NumberingPattern,
+};
+use crate::layout::{HElem, ParbreakElem};
+use crate::prelude::*;
+use crate::text::{EmphElem, LinebreakElem, TextElem};
+
+/// Marks a term for inclusion in the index.
+///
+/// The marker itself is invisible. It records the page on which it ends up so
+/// that [`make-index`]($func/make-index) can list it. Place the marker right
+/// next to the discussion of the term.
+///
+/// ## Example { #example }
+/// ```example
+/// = Animals
+/// Cats#index("cat") purr,
+/// dogs#index("dog") bark, and
+/// kittens#index("cat", sub: "kitten")
+/// are small cats.
+/// #index("pet", see-also: ("cat", "dog"))
+///
+/// #make-index()
+/// ```
+///
+/// Display: Index
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct IndexElem {
+    /// The term under which the page is listed.
+    #[required]
+    pub term: EcoString,
+
+    /// A sub-term, listed below the main term.
+    pub sub: Option<EcoString>,
+
+    /// Other terms the reader should also look at.
+    ///
+    /// A marker with cross-references only adds them to its term and does not
+    /// list its own page.
+    pub see_also: Vec<EcoString>,
+}
+
+impl Show for IndexElem {
+    #[tracing::instrument(name = "IndexElem::show", skip(self))]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// Generates an index of all terms marked with [`index`]($func/index).
+///
+/// Terms and sub-terms are sorted alphabetically following the conventions of
+/// the [text language]($func/text.lang). Each term lists the pages it was
+/// marked on, with consecutive pages merged into ranges. Every page number
+/// links to the marker's position in the document.
+///
+/// ## Example { #example }
+/// ```example
+/// Bananas#index("banana") are
+/// yellow. Apples#index("apple")
+/// come in many varieties, like
+/// Boskoop#index("apple", sub: "Boskoop").
+///
+/// #make-index()
+/// ```
+///
+/// Display: Make Index
+/// Category: meta
+/// Keywords: Index
+#[element(Show, Finalize, LocalName)]
+pub struct MakeIndexElem {
+    /// The title of the index.
+    ///
+    /// - When set to `{auto}`, an appropriate title for the
+    ///   [text language]($func/text.lang) will be used. This is the default.
+    /// - When set to `{none}`, the index will not have a title.
+    /// - A custom title can be set by passing content.
+    #[default(Some(Smart::Auto))]
+    pub title: Option<Smart<Content>>,
+
+    /// How far to indent sub-terms.
+    ///
+    /// ```example
+    /// #set make-index(indent: 2em)
+    /// Rust#index("rust", sub: "iron oxide")
+    /// #make-index(title: none)
+    /// ```
+    #[default(Em::new(1.0).into())]
+    pub indent: Length,
+
+    /// The content introducing the terms a term refers to with `see-also`.
+    #[default(EmphElem::new(TextElem::packed("see also")).pack())]
+    pub see_also: Content,
+}
+
+impl Show for MakeIndexElem {
+    #[tracing::instrument(name = "MakeIndexElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let mut seq = vec![ParbreakElem::new().pack()];
+        if let Some(title) = self.title(styles) {
+            let title = title.unwrap_or_else(|| {
+                TextElem::packed(self.local_name_in(styles)).spanned(self.span())
+            });
+
+            seq.push(HeadingElem::new(title).with_level(NonZeroUsize::ONE).pack());
+        }
+
+        // Group the markers by term and sub-term.
+        let mut entries: Vec<IndexEntry> = vec![];
+        for elem in vt.introspector.query(&Selector::Elem(IndexElem::func(), None)) {
+            let location = elem.location().unwrap();
+            let marker = elem.to::<IndexElem>().unwrap();
+            let term = marker.term();
+            let see_also = marker.see_also(StyleChain::default());
+
+            let i = match entries.iter().position(|entry| entry.term == term) {
+                Some(i) => i,
+                None => {
+                    entries.push(IndexEntry::new(term));
+                    entries.len() - 1
+                }
+            };
+
+            let entry = &mut entries[i];
+            let only_refers = !see_also.is_empty();
+            for other in see_also {
+                if !entry.see_also.contains(&other) {
+                    entry.see_also.push(other);
+                }
+            }
+
+            match marker.sub(StyleChain::default()) {
+                Some(sub) => {
+                    match entry.subs.iter_mut().find(|sub_entry| sub_entry.term == sub) {
+                        Some(sub_entry) => sub_entry.locations.push(location),
+                        None => {
+                            let mut sub_entry = IndexEntry::new(sub);
+                            sub_entry.locations.push(location);
+                            entry.subs.push(sub_entry);
+                        }
+                    }
+                }
+                None if !only_refers => entry.locations.push(location),
+                None => {}
+            }
+        }
+
+        // Sort the entries following the conventions of the text language.
+        let collator = collator(styles).at(self.span())?;
+        let compare = |a: &IndexEntry, b: &IndexEntry| {
+            collator.compare(&a.term, &b.term).then_with(|| a.term.cmp(&b.term))
+        };
+
+        entries.sort_by(compare);
+        for entry in &mut entries {
+            entry.subs.sort_by(compare);
+        }
+
+        let indent = HElem::new(self.indent(styles).into()).pack();
+        for entry in &entries {
+            seq.push(entry.layout(vt, Some(self.see_also(styles)))?);
+            seq.push(LinebreakElem::new().pack());
+
+            for sub_entry in &entry.subs {
+                seq.push(indent.clone());
+                seq.push(sub_entry.layout(vt, None)?);
+                seq.push(LinebreakElem::new().pack());
+            }
+        }
+
+        seq.push(ParbreakElem::new().pack());
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+impl Finalize for MakeIndexElem {
+    fn finalize(&self, realized: Content, _: StyleChain) -> Content {
+        realized.styled(HeadingElem::set_numbering(None))
+    }
+}
+
+impl LocalName for MakeIndexElem {
+    fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
+        match lang {
+            Lang::ALBANIAN => "Indeksi",
+            Lang::ARABIC => "الفهرس",
+            Lang::BOKMÅL => "Register",
+            Lang::CHINESE => "索引",
+            Lang::CZECH => "Rejstřík",
+            Lang::DANISH => "Register",
+            Lang::DUTCH => "Index",
+            Lang::FILIPINO => "Indeks",
+            Lang::FRENCH => "Index",
+            Lang::GERMAN => "Stichwortverzeichnis",
+            Lang::ITALIAN => "Indice analitico",
+            Lang::NYNORSK => "Register",
+            Lang::POLISH => "Indeks",
+            Lang::PORTUGUESE => "Índice remissivo",
+            Lang::RUSSIAN => "Предметный указатель",
+            Lang::SLOVENIAN => "Stvarno kazalo",
+            Lang::SPANISH => "Índice alfabético",
+            Lang::SWEDISH => "Register",
+            Lang::TURKISH => "Dizin",
+            Lang::UKRAINIAN => "Предметний покажчик",
+            Lang::VIETNAMESE => "Chỉ mục",
+            Lang::JAPANESE => "索引",
+            Lang::ENGLISH | _ => "Index",
+        }
+    }
+}
+
+/// A term in the index together with the places where it was marked.
+struct IndexEntry {
+    /// The term itself.
+    term: EcoString,
+    /// The locations of the term's markers, in document order.
+    locations: Vec<Location>,
+    /// Terms the reader should also look at.
+    see_also: Vec<EcoString>,
+    /// The sub-terms of the term.
+    subs: Vec<IndexEntry>,
+}
+
+impl IndexEntry {
+    /// Create an entry for a term without any locations.
+    fn new(term: EcoString) -> Self {
+        Self {
+            term,
+            locations: vec![],
+            see_also: vec![],
+            subs: vec![],
+        }
+    }
+
+    /// Produce the entry's line, without its sub-terms.
+    fn layout(&self, vt: &mut Vt, see_also: Option<Content>) -> SourceResult<Content> {
+        let mut seq = vec![TextElem::packed(self.term.clone())];
+        for range in page_ranges(vt, &self.locations)? {
+            seq.push(TextElem::packed(", "));
+            seq.push(range);
+        }
+
+        if let Some(prefix) = see_also.filter(|_| !self.see_also.is_empty()) {
+            let mut others = self.see_also.clone();
+            others.sort();
+            seq.push(TextElem::packed("; "));
+            seq.push(prefix);
+            seq.push(TextElem::packed(eco_format!(" {}", others.join(", "))));
+        }
+
+        Ok(Content::sequence(seq))
+    }
+}
+
+/// Produce linked page numbers for the given locations, which must be in
+/// document order. Consecutive pages are merged into ranges like "12–14".
+pub(super) fn page_ranges(
+    vt: &mut Vt,
+    locations: &[Location],
+) -> SourceResult<Vec<Content>> {
+    let mut pages: Vec<IndexPage> = vec![];
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for &location in locations {
+        let page = IndexPage::new(vt, location)?;
+        if pages.last().map_or(false, |last| last.page == page.page) {
+            continue;
+        }
+
+        match ranges.last_mut() {
+            Some((_, end)) if pages[*end].is_followed_by(&page) => {
+                *end = pages.len();
+            }
+            _ => ranges.push((pages.len(), pages.len())),
+        }
+
+        pages.push(page);
+    }
+
+    let mut seq = vec![];
+    for (start, end) in ranges {
+        let mut range = pages[start].display(vt)?;
+        if end > start {
+            range += TextElem::packed("\u{2013}") + pages[end].display(vt)?;
+        }
+        seq.push(range);
+    }
+
+    Ok(seq)
+}
+
+/// A page on which a marker ended up.
+struct IndexPage {
+    /// The location of the first marker on the page.
+    location: Location,
+    /// The physical page number.
+    page: NonZeroUsize,
+    /// The value of the page counter.
+    number: CounterState,
+    /// The page numbering in effect on the page.
+    numbering: Value,
+}
+
+impl IndexPage {
+    /// Determine the page of a marker.
+    fn new(vt: &mut Vt, location: Location) -> SourceResult<Self> {
+        Ok(Self {
+            location,
+            page: vt.introspector.page(location),
+            number: Counter::new(CounterKey::Page).at(vt, location)?,
+            numbering: vt.introspector.page_numbering(location),
+        })
+    }
+
+    /// Whether the other page directly follows this one with the same
+    /// numbering, so that both can be merged into a range.
+    fn is_followed_by(&self, other: &Self) -> bool {
+        other.page.get() == self.page.get() + 1
+            && other.number.first() == self.number.first() + 1
+            && other.numbering == self.numbering
+    }
+
+    /// Display the page number, linked to the marker.
+    fn display(&self, vt: &mut Vt) -> SourceResult<Content> {
+        let numbering = self
+            .numbering
+            .clone()
+            .cast::<Option<Numbering>>()
+            .unwrap()
+            .unwrap_or_else(|| {
+                Numbering::Pattern(NumberingPattern::from_str("1").unwrap())
+            });
+
+        let body = self.number.display(vt, &numbering)?;
+        Ok(LinkElem::new(Destination::Location(self.location).into(), body).pack())
+    }
+}
+
+/// Create a collator for the text language.
+///
+/// Uses the collation data compiled into `icu_collator`. Locales without
+/// tailored rules fall back to the root collation order.
+pub(super) fn collator(styles: StyleChain) -> StrResult<Collator> {
+    let mut tag = EcoString::from(TextElem::lang_in(styles).as_str());
+    if let Some(region) = TextElem::region_in(styles) {
+        tag.push('-');
+        tag.push_str(region.as_str());
+    }
+
+    let locale = tag
+        .parse::<DataLocale>()
+        .map_err(|_| eco_format!("invalid locale: {tag}"))?;
+    Collator::try_new(&locale, CollatorOptions::new())
+        .map_err(|_| eco_format!("no collation data for locale {tag}"))
+}
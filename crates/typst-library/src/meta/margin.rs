@@ -0,0 +1,187 @@
+use super::{Count, Counter, CounterUpdate, Numbering};
+use crate::layout::{HElem, ParElem};
+use crate::prelude::*;
+use crate::text::{SuperElem, TextElem, TextSize};
+
+/// A note in the page margin.
+///
+/// The note is placed in the margin next to the line in which it appears. By
+/// default, it goes into the outer margin, that is the margin opposite to the
+/// page's [binding]($func/page.binding). In a
+/// [two-sided]($func/page.margin) document, the outer margin alternates
+/// between the right and the left side.
+///
+/// When notes would overlap, later notes are moved down until they don't. If
+/// the notes then run past the bottom margin, they are shifted upwards as far
+/// as necessary.
+///
+/// ## Example { #example }
+/// ```example
+/// #set page(margin: (right: 3.5cm))
+///
+/// Typst is a new markup-based
+/// typesetting system.
+/// #margin-note[Written in Rust.]
+/// It is designed to be as
+/// powerful as LaTeX while being
+/// much easier to learn and use.
+/// ```
+///
+/// ## Numbered notes { #numbered-notes }
+/// When a [numbering]($func/margin-note.numbering) is set, the note inserts a
+/// superscript number into the text, just like a [footnote]($func/footnote),
+/// and repeats it in front of the note. Numbered margin notes are counted with
+/// their own counter: `{counter(margin-note)}`.
+///
+/// ```example
+/// #set page(margin: (right: 3.5cm))
+/// #set margin-note(numbering: "a")
+///
+/// Ravens#margin-note[_Corvus corax_]
+/// and crows#margin-note[_Corvus corone_]
+/// are related.
+/// ```
+///
+/// Display: Margin Note
+/// Category: meta
+#[element(Locatable, Synthesize, Show, Count)]
+pub struct MarginNoteElem {
+    /// How to number the note.
+    ///
+    /// By default, margin notes are not numbered.
+    pub numbering: Option<Numbering>,
+
+    /// In which margin to place the note.
+    ///
+    /// Can be `{"outside"}` (the default), `{"inside"}`, `{left}` or `{right}`.
+    /// Inside and outside are determined by the page's binding.
+    ///
+    /// ```example
+    /// #set page(margin: (x: 3cm))
+    ///
+    /// Left#margin-note(side: left)[Here]
+    /// and right#margin-note(side: right)[There].
+    /// ```
+    #[default(MarginSide::Outside)]
+    pub side: MarginSide,
+
+    /// The horizontal distance between a note and the page body as well as the
+    /// edge of the page.
+    ///
+    /// This property can only be configured for the whole page with a set
+    /// rule.
+    #[default(Em::new(1.0).into())]
+    #[resolve]
+    pub clearance: Length,
+
+    /// The minimal vertical distance between two notes in the same margin.
+    ///
+    /// This property can only be configured for the whole page with a set
+    /// rule.
+    #[default(Em::new(0.5).into())]
+    #[resolve]
+    pub gap: Length,
+
+    /// The content to put into the margin.
+    #[required]
+    pub body: Content,
+}
+
+impl MarginNoteElem {
+    /// The content that is placed in the margin.
+    pub fn entry(&self, vt: &mut Vt) -> SourceResult<Content> {
+        let mut seq = vec![];
+        if let Some(numbering) = self.numbering(StyleChain::default()) {
+            let loc = self.0.location().unwrap();
+            let num = Counter::of(Self::func()).at(vt, loc)?.display(vt, &numbering)?;
+            let sup = SuperElem::new(num)
+                .pack()
+                .linked(Destination::Location(loc))
+                .backlinked(loc.variant(1));
+            seq.push(sup);
+            seq.push(HElem::new(Em::new(0.05).into()).with_weak(true).pack());
+        }
+        seq.push(self.body());
+
+        Ok(Content::sequence(seq)
+            .styled(ParElem::set_leading(Em::new(0.5).into()))
+            .styled(TextElem::set_size(TextSize(Em::new(0.85).into()))))
+    }
+}
+
+impl Synthesize for MarginNoteElem {
+    fn synthesize(&mut self, _: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        self.push_numbering(self.numbering(styles));
+        self.push_side(self.side(styles));
+        Ok(())
+    }
+}
+
+impl Show for MarginNoteElem {
+    #[tracing::instrument(name = "MarginNoteElem::show", skip_all)]
+    fn show(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<Content> {
+        let Some(numbering) = self.numbering(styles) else {
+            return Ok(Content::empty());
+        };
+
+        Ok(vt.delayed(|vt| {
+            let loc = self.0.location().unwrap();
+            let num = Counter::of(Self::func()).at(vt, loc)?.display(vt, &numbering)?;
+            let sup = SuperElem::new(num).pack();
+            let hole = HElem::new(Abs::zero().into()).with_weak(true).pack();
+            Ok(hole + sup.linked(Destination::Location(loc.variant(1))))
+        }))
+    }
+}
+
+impl Count for MarginNoteElem {
+    fn update(&self) -> Option<CounterUpdate> {
+        self.numbering(StyleChain::default())
+            .is_some()
+            .then(|| CounterUpdate::Step(NonZeroUsize::ONE))
+    }
+}
+
+/// In which margin a margin note is placed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MarginSide {
+    /// The margin at the inner side of the page (where the binding is).
+    Inside,
+    /// The margin at the outer side of the page (opposite to the binding).
+    Outside,
+    /// The left margin.
+    Left,
+    /// The right margin.
+    Right,
+}
+
+cast! {
+    MarginSide,
+    self => match self {
+        Self::Inside => "inside".into_value(),
+        Self::Outside => "outside".into_value(),
+        Self::Left => GenAlign::Specific(Align::Left).into_value(),
+        Self::Right => GenAlign::Specific(Align::Right).into_value(),
+    },
+    /// The margin at the inner side of the page (where the binding is).
+    "inside" => Self::Inside,
+    /// The margin at the outer side of the page (opposite to the binding).
+    "outside" => Self::Outside,
+    v: GenAlign => match v {
+        GenAlign::Specific(Align::Left) => Self::Left,
+        GenAlign::Specific(Align::Right) => Self::Right,
+        _ => bail!("must be `left` or `right`"),
+    },
+}
+
+impl MarginSide {
+    /// Resolve to a physical side, given the inner side of the page.
+    pub fn resolve(self, inside: Side) -> Side {
+        match self {
+            Self::Inside => inside,
+            Self::Outside => inside.inv(),
+            Self::Left => Side::Left,
+            Self::Right => Side::Right,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+
+/// Exposes a value to the query system without producing visible content.
+///
+/// This element can be retrieved with the [`query`]($func/query) function.
+/// Its value is available through the `value` field. This is useful for
+/// passing data from one part of a document to another.
+///
+/// ## Example { #example }
+/// ```example
+/// // Put metadata somewhere.
+/// #metadata("This is a note") <note>
+///
+/// // And find it from anywhere else.
+/// #locate(loc => {
+///   query(<note>, loc).first().value
+/// })
+/// ```
+///
+/// Display: Metadata
+/// Category: meta
+#[element(Locatable, Show)]
+pub struct MetadataElem {
+    /// The value to embed into the document.
+    #[required]
+    pub value: Value,
+}
+
+impl Show for MetadataElem {
+    #[tracing::instrument(name = "MetadataElem::show", skip(self))]
+    fn show(&self, _: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
@@ -6,8 +6,12 @@ mod counter;
 mod document;
 mod figure;
 mod footnote;
+mod glossary;
 mod heading;
+mod index;
 mod link;
+mod margin;
+mod metadata;
 mod numbering;
 mod outline;
 mod query;
@@ -20,8 +24,12 @@ pub use self::counter::*;
 pub use self::document::*;
 pub use self::figure::*;
 pub use self::footnote::*;
+pub use self::glossary::*;
 pub use self::heading::*;
+pub use self::index::*;
 pub use self::link::*;
+pub use self::margin::*;
+pub use self::metadata::*;
 pub use self::numbering::*;
 pub use self::outline::*;
 pub use self::query::*;
@@ -40,8 +48,13 @@ pub(super) fn define(global: &mut Scope) {
     global.define("heading", HeadingElem::func());
     global.define("figure", FigureElem::func());
     global.define("footnote", FootnoteElem::func());
+    global.define("endnotes", EndnotesElem::func());
+    global.define("margin-note", MarginNoteElem::func());
     global.define("cite", CiteElem::func());
     global.define("bibliography", BibliographyElem::func());
+    global.define("glossary", GlossaryElem::func());
+    global.define("index", IndexElem::func());
+    global.define("make-index", MakeIndexElem::func());
     global.define("locate", locate_func());
     global.define("style", style_func());
     global.define("layout", layout_func());
@@ -49,6 +62,8 @@ pub(super) fn define(global: &mut Scope) {
     global.define("numbering", numbering_func());
     global.define("state", state_func());
     global.define("query", query_func());
+    global.define("marks", marks_func());
+    global.define("metadata", MetadataElem::func());
     global.define("selector", selector_func());
 }
 
@@ -1,3 +1,5 @@
+use comemo::Prehashed;
+
 use crate::prelude::*;
 
 /// Finds elements in the document.
@@ -74,7 +76,8 @@ use crate::prelude::*;
 /// Typst sees that the query's result has changed and processes it again. This
 /// time, `count` is `2` and two `Fake` headings are generated. This goes on and
 /// on. As we can see, the output has five headings. This is because Typst
-/// simply gives up after five attempts.
+/// simply gives up after five attempts and warns about the elements whose
+/// results kept changing.
 ///
 /// In general, you should try not to write queries that affect themselves.
 /// The same words of caution also apply to other introspection features like
@@ -128,6 +131,84 @@ pub fn query(
         .collect()
 }
 
+/// Finds the marks of a page for running headers and footers.
+///
+/// Headers of dictionaries and manuals often show the first and last entry or
+/// section that appears on the current page. This function finds them for the
+/// page the given location is on. It returns a dictionary with the following
+/// keys:
+///
+/// - `first`: The first matching element that starts on the page.
+/// - `last`: The last matching element that starts on the page.
+/// - `previous`: The last matching element that starts before the page.
+///
+/// When no element starts on the page, `first` and `last` are the `previous`
+/// element instead, as the page still belongs to it. All of them are `{none}`
+/// if there is no such element.
+///
+/// To find all elements on a page, use the [`page`]($type/selector.page)
+/// method of selectors instead.
+///
+/// ## Example { #example }
+/// ```example
+/// >>> #set page(
+/// >>>   width: 240pt,
+/// >>>   height: 180pt,
+/// >>>   margin: (top: 35pt, rest: 15pt),
+/// >>>   header-ascent: 12pt,
+/// >>> )
+/// #set page(header: locate(loc => {
+///   let marks = marks(heading, loc)
+///   if marks.first != none {
+///     emph(marks.first.body)
+///     h(1fr)
+///     emph(marks.last.body)
+///   }
+/// }))
+///
+/// = Aardvark
+/// #lorem(20)
+///
+/// = Abacus
+/// #lorem(20)
+///
+/// = Abbey
+/// #lorem(30)
+/// ```
+///
+/// Display: Marks
+/// Category: meta
+#[func]
+pub fn marks(
+    /// The elements to find, like `heading` or `{heading.where(level: 1)}`.
+    target: LocatableSelector,
+    /// Any location on the page whose marks to find.
+    location: Location,
+    /// The virtual machine.
+    vm: &mut Vm,
+) -> Dict {
+    let introspector = vm.vt.introspector;
+    let page = introspector.page(location);
+    let on_page = introspector.query(&target.0.clone().on_page(page));
+    let previous = introspector
+        .query(&target.0)
+        .iter()
+        .rev()
+        .find(|elem| introspector.page(elem.location().unwrap()) < page)
+        .cloned();
+
+    let mark = |elem: Option<&Prehashed<Content>>| {
+        elem.or(previous.as_ref())
+            .map_or(Value::None, |elem| Value::Content(elem.clone().into_inner()))
+    };
+
+    dict! {
+        "first" => mark(on_page.first()),
+        "last" => mark(on_page.last()),
+        "previous" => mark(None),
+    }
+}
+
 /// Turns a value into a selector. The following values are accepted:
 /// - An element function like a `heading` or `figure`.
 /// - A `{<label>}`.
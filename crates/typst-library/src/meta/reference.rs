@@ -1,4 +1,10 @@
-use super::{BibliographyElem, CiteElem, Counter, Figurable, Numbering};
+use std::str::FromStr;
+
+use super::{
+    BibliographyElem, CiteElem, Counter, CounterKey, Figurable, GlossaryEntry,
+    GlossaryTerm, LocalName, Numbering, NumberingPattern,
+};
+use crate::layout::{PageElem, ParLine};
 use crate::meta::FootnoteElem;
 use crate::prelude::*;
 use crate::text::TextElem;
@@ -13,10 +19,12 @@ use crate::text::TextElem;
 ///
 /// Referenceable elements include [headings]($func/heading),
 /// [figures]($func/figure), [equations]($func/math.equation), and
-/// [footnotes]($func/footnote). To create a custom referenceable element like a
-/// theorem, you can create a figure of a custom [`kind`]($func/figure.kind) and
-/// write a show rule for it. In the future, there might be a more direct way to
-/// define a custom referenceable element.
+/// [footnotes]($func/footnote). A reference to the key of a
+/// [glossary entry]($func/glossary.entry) uses the glossary's term. To create a
+/// custom referenceable element like a theorem, you can create a figure of a
+/// custom [`kind`]($func/figure.kind) and write a show rule for it. In the
+/// future, there might be a more direct way to define a custom referenceable
+/// element.
 ///
 /// If you just want to link to a labelled element and not get an automatic
 /// textual reference, consider using the [`link`]($func/link) function instead.
@@ -53,6 +61,21 @@ use crate::text::TextElem;
 /// To customize the supplement, add content in square brackets after the
 /// reference: `[@intro[Chapter]]`.
 ///
+/// ## Page references { #page-references }
+/// To refer to the page an element is on instead of the element itself, set
+/// the reference's [`form`]($func/ref.form) to `{"page"}`. The page number is
+/// displayed with the page numbering in effect on the referenced page.
+///
+/// ```example
+/// #set page(numbering: "1")
+/// #set heading(numbering: "1.")
+///
+/// = Basics <basics>
+/// #pagebreak()
+/// As explained in @basics on
+/// #ref(<basics>, form: "page"), ...
+/// ```
+///
 /// ## Customization { #customization }
 /// If you write a show rule for references, you can access the referenced
 /// element through the `element` field of the reference. The `element` may
@@ -94,7 +117,8 @@ pub struct RefElem {
     /// A supplement for the reference.
     ///
     /// For references to headings or figures, this is added before the
-    /// referenced number. For citations, this can be used to add a page number.
+    /// referenced number. For page references, this is added before the page
+    /// number. For citations, this can be used to add a page number.
     ///
     /// If a function is specified, it is passed the referenced element and
     /// should return content.
@@ -117,6 +141,31 @@ pub struct RefElem {
     /// ```
     pub supplement: Smart<Option<Supplement>>,
 
+    /// What the reference refers to.
+    ///
+    /// A `{"page"}` reference can target any labelled element, even one that
+    /// isn't numbered. Its supplement defaults to the word "page" in the
+    /// current language. A `{"line"}` reference targets the
+    /// [numbered line]($func/par.line) the element is on and defaults to the
+    /// word "line" as its supplement. Use a show-set rule like
+    /// `{show ref.where(form: "page"): set ref(supplement: [p.])}` to
+    /// customize the supplement of page references only.
+    ///
+    /// ```example
+    /// #set page(numbering: "i")
+    /// #figure(
+    ///   rect[Robot],
+    ///   caption: [A robot],
+    /// ) <robot>
+    ///
+    /// #set page(numbering: "1")
+    /// #counter(page).update(1)
+    /// #pagebreak()
+    /// See @robot on #ref(<robot>, form: "page").
+    /// ```
+    #[default(RefForm::Normal)]
+    pub form: RefForm,
+
     /// A synthesized citation.
     #[synthesized]
     pub citation: Option<CiteElem>,
@@ -152,15 +201,78 @@ impl Show for RefElem {
             let elem = vt.introspector.query_label(&self.target());
             let span = self.span();
 
+            let form = self.form(styles);
+
             if BibliographyElem::has(vt, &target.0) {
                 if elem.is_ok() {
                     bail!(span, "label occurs in the document and its bibliography");
                 }
 
+                match form {
+                    RefForm::Normal => {}
+                    RefForm::Page => {
+                        bail!(span, "cannot reference the page of a bibliography entry")
+                    }
+                    RefForm::Line => {
+                        bail!(span, "cannot reference the line of a bibliography entry")
+                    }
+                }
+
                 return Ok(self.to_citation(vt, styles)?.pack().spanned(span));
             }
 
+            // References to glossary entries are uses of the term.
+            if elem.is_err()
+                && form == RefForm::Normal
+                && GlossaryEntry::find(vt.introspector, &target.0).is_ok()
+            {
+                return Ok(GlossaryTerm::new(target.0).pack().spanned(span));
+            }
+
             let elem = elem.at(span)?;
+            let location = elem.location().unwrap();
+
+            if form == RefForm::Page {
+                let numbering = vt
+                    .introspector
+                    .page_numbering(location)
+                    .cast::<Option<Numbering>>()
+                    .unwrap()
+                    .unwrap_or_else(|| {
+                        Numbering::Pattern(NumberingPattern::from_str("1").unwrap())
+                    });
+
+                let page = Counter::new(CounterKey::Page)
+                    .at(vt, location)?
+                    .display(vt, &numbering)?;
+
+                let name = PageElem::new(Content::empty()).local_name_in(styles);
+                let supplement = self
+                    .resolve_supplement(vt, styles, &elem, || TextElem::packed(name))?;
+
+                return Ok(Self::assemble(supplement, page, location));
+            }
+
+            if form == RefForm::Line {
+                let line = ParLine::find(vt.introspector, location)
+                    .ok_or("referenced element is not in a numbered line")
+                    .hint("you can enable line numbering with `#set par.line(numbering: \"1\")`")
+                    .at(span)?;
+
+                let number = ParLine::numbers(vt.introspector)
+                    .get(&line.0.location().unwrap())
+                    .copied()
+                    .unwrap_or(1);
+
+                let numbering = line.numbering(StyleChain::default()).unwrap();
+                let number = numbering.apply_vt(vt, &[number])?.display();
+
+                let name = ParLine::new().local_name_in(styles);
+                let supplement = self
+                    .resolve_supplement(vt, styles, &elem, || TextElem::packed(name))?;
+
+                return Ok(Self::assemble(supplement, number, location));
+            }
 
             if elem.func() == FootnoteElem::func() {
                 return Ok(FootnoteElem::with_label(target).pack().spanned(span));
@@ -196,28 +308,43 @@ impl Show for RefElem {
 
             let numbers = refable
                 .counter()
-                .at(vt, elem.location().unwrap())?
+                .at(vt, location)?
                 .display(vt, &numbering.trimmed())?;
 
-            let supplement = match self.supplement(styles) {
-                Smart::Auto => refable.supplement(),
-                Smart::Custom(None) => Content::empty(),
-                Smart::Custom(Some(supplement)) => {
-                    supplement.resolve(vt, [(*elem).clone()])?
-                }
-            };
-
-            let mut content = numbers;
-            if !supplement.is_empty() {
-                content = supplement + TextElem::packed("\u{a0}") + content;
-            }
+            let supplement =
+                self.resolve_supplement(vt, styles, &elem, || refable.supplement())?;
 
-            Ok(content.linked(Destination::Location(elem.location().unwrap())))
+            Ok(Self::assemble(supplement, numbers, location))
         }))
     }
 }
 
 impl RefElem {
+    /// Resolve the supplement, falling back to the given default.
+    fn resolve_supplement(
+        &self,
+        vt: &mut Vt,
+        styles: StyleChain,
+        elem: &Content,
+        default: impl FnOnce() -> Content,
+    ) -> SourceResult<Content> {
+        Ok(match self.supplement(styles) {
+            Smart::Auto => default(),
+            Smart::Custom(None) => Content::empty(),
+            Smart::Custom(Some(supplement)) => supplement.resolve(vt, [elem.clone()])?,
+        })
+    }
+
+    /// Combine the supplement and number into a link to the target.
+    fn assemble(supplement: Content, number: Content, location: Location) -> Content {
+        let mut content = number;
+        if !supplement.is_empty() {
+            content = supplement + TextElem::packed("\u{a0}") + content;
+        }
+
+        content.linked(Destination::Location(location))
+    }
+
     /// Turn the reference into a citation.
     pub fn to_citation(&self, vt: &mut Vt, styles: StyleChain) -> SourceResult<CiteElem> {
         let mut elem = CiteElem::new(vec![self.target().0]);
@@ -232,6 +359,18 @@ impl RefElem {
     }
 }
 
+/// The form of a reference.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum RefForm {
+    /// A reference to the element itself, like "Figure 3".
+    #[default]
+    Normal,
+    /// A reference to the page the element is on, like "page 12".
+    Page,
+    /// A reference to the numbered line the element is on, like "line 7".
+    Line,
+}
+
 /// Additional content for a reference.
 pub enum Supplement {
     Content(Content),
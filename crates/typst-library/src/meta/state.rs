@@ -166,7 +166,8 @@ use crate::prelude::*;
 /// this. We initialize our state with `1` and then update it to its own final
 /// value plus 1. So it should be `2`, but then its final value is `2`, so it
 /// should be `3`, and so on. This example display `4` because Typst simply
-/// gives up after a few attempts.
+/// gives up after a few attempts and warns about the results that kept
+/// changing.
 ///
 /// ```example
 /// #let s = state("x", 1)
@@ -255,25 +256,14 @@ pub struct State {
 }
 
 impl State {
-    /// Call a method on a state.
-    #[tracing::instrument(skip(vm))]
-    pub fn call_method(
-        self,
-        vm: &mut Vm,
-        method: &str,
-        mut args: Args,
-        span: Span,
-    ) -> SourceResult<Value> {
-        let value = match method {
-            "display" => self.display(args.eat()?).into_value(),
-            "at" => self.at(&mut vm.vt, args.expect("location")?)?,
-            "final" => self.final_(&mut vm.vt, args.expect("location")?)?,
-            "update" => self.update(args.expect("value or function")?).into_value(),
-            _ => bail!(span, "type state has no method `{}`", method),
-        };
-        args.finish()?;
-        Ok(value)
-    }
+    /// The methods available on states.
+    pub const METHODS: &'static [NativeMethod] = native_methods! {
+        |vm, state: State, args, span|
+        "display"(..) => state.display(args.eat()?).into_value(),
+        "at"(..) => state.at(&mut vm.vt, args.expect("location")?)?,
+        "final"(..) => state.final_(&mut vm.vt, args.expect("location")?)?,
+        "update"(..) => state.update(args.expect("value or function")?).into_value(),
+    };
 
     /// Display the current value of the state.
     pub fn display(self, func: Option<Func>) -> Content {
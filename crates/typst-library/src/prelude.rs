@@ -15,8 +15,9 @@ pub use typst::diag::{bail, error, At, Hint, SourceResult, StrResult};
 pub use typst::doc::*;
 #[doc(no_inline)]
 pub use typst::eval::{
-    array, cast, dict, format_str, func, Args, Array, AutoValue, Cast, Dict, FromValue,
-    Func, IntoValue, Never, NoneValue, Scope, Str, Symbol, Type, Value, Vm,
+    array, cast, dict, format_str, func, native_methods, Args, Array, AutoValue, Cast,
+    Dict, FromValue, Func, IntoValue, NativeMethod, Never, NoneValue, Scope, Str, Symbol,
+    Type, Value, Vm,
 };
 #[doc(no_inline)]
 pub use typst::file::FileId;
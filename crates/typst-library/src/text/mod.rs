@@ -14,6 +14,8 @@ pub use self::raw::*;
 pub use self::shaping::*;
 pub use self::shift::*;
 
+use std::hash::{Hash, Hasher};
+
 use rustybuzz::Tag;
 use typst::font::{FontMetrics, FontStretch, FontStyle, FontWeight, VerticalFontMetric};
 
@@ -121,7 +123,8 @@ pub struct TextElem {
     /// The desired thickness of the font's glyphs. Accepts an integer between
     /// `{100}` and `{900}` or one of the predefined weight names. When the
     /// desired weight is not available, Typst selects the font from the family
-    /// that is closest in weight.
+    /// that is closest in weight. For variable fonts with a weight axis, the
+    /// exact weight is used, so a weight like `{450}` works as expected.
     ///
     /// If you want to strongly emphasize your text, you should do so using the
     /// [strong]($func/strong) function instead. This makes it easy to adapt the
@@ -142,7 +145,8 @@ pub struct TextElem {
     /// The desired width of the glyphs. Accepts a ratio between `{50%}` and
     /// `{200%}`. When the desired weight is not available, Typst selects the
     /// font from the family that is closest in stretch. This will only stretch
-    /// the text if a condensed or expanded version of the font is available.
+    /// the text if a condensed or expanded version of the font is available or
+    /// if the font is a variable font with a width axis.
     ///
     /// If you want to adjust the amount of space between characters instead of
     /// stretching the glyphs itself, use the [`tracking`]($func/text.tracking)
@@ -204,6 +208,24 @@ pub struct TextElem {
     #[default(Rel::one())]
     pub spacing: Rel<Length>,
 
+    /// Whether to automatically insert spacing between CJK and Latin
+    /// characters.
+    ///
+    /// When `{auto}`, a quarter of an em is inserted between Chinese or
+    /// Japanese characters and adjacent Latin letters or digits. During
+    /// justification, this spacing may shrink to half or grow to double its
+    /// size. It is left out at the start and end of lines. When `{none}`, no
+    /// extra spacing is inserted.
+    ///
+    /// ```example
+    /// #set text(font: ("Linux Libertine", "Noto Serif CJK SC"))
+    /// 第4章介绍Typst的排版 \
+    /// #set text(cjk-latin-spacing: auto)
+    /// 第4章介绍Typst的排版
+    /// ```
+    #[default(Smart::Custom(None))]
+    pub cjk_latin_spacing: Smart<Option<Never>>,
+
     /// An amount to shift the text baseline by.
     ///
     /// ```example
@@ -294,6 +316,12 @@ pub struct TextElem {
     /// - `{auto}`: Automatically infer the direction from the `lang` property.
     /// - `{ltr}`: Layout text from left to right.
     /// - `{rtl}`: Layout text from right to left.
+    /// - `{ttb}`: Layout text from top to bottom in lines that progress from
+    ///   right to left. This vertical writing mode is used for Chinese,
+    ///   Japanese and Korean text. It must be set for whole pages, i.e. before
+    ///   any content or in a page set rule, and only affects the page body. See
+    ///   the [`orientation`]($func/text.orientation) property to control how
+    ///   characters are oriented in vertical text.
     ///
     /// When writing in right-to-left scripts like Arabic or Hebrew, you should
     /// set the [text language]($func/text.lang) or direction. While individual
@@ -316,6 +344,37 @@ pub struct TextElem {
     #[resolve]
     pub dir: TextDir,
 
+    /// How characters are oriented in vertical text.
+    ///
+    /// This only has an effect if the text [direction]($func/text.dir) is
+    /// `{ttb}`.
+    ///
+    /// ```example
+    /// #set page(width: 80pt, height: 100pt)
+    /// #set text(dir: ttb, lang: "ja", font: "Noto Serif CJK JP")
+    /// 縦書きのTypst \
+    /// #text(orientation: "upright")[ABC]
+    /// ```
+    #[default(TextOrientation::Mixed)]
+    pub orientation: TextOrientation,
+
+    /// The maximum number of consecutive digits that are combined into a
+    /// single upright character cell in vertical text, also known as
+    /// tate-chu-yoko. Set to `{none}` to rotate all digits like other Latin
+    /// text.
+    ///
+    /// This only has an effect if the text [direction]($func/text.dir) is
+    /// `{ttb}` and the [orientation]($func/text.orientation) is `{"mixed"}`.
+    ///
+    /// ```example
+    /// #set page(width: 80pt, height: 100pt)
+    /// #set text(dir: ttb, lang: "ja", font: "Noto Serif CJK JP")
+    /// 令和5年12月 \
+    /// #text(combine-upright: none)[令和5年12月]
+    /// ```
+    #[default(NonZeroUsize::new(2))]
+    pub combine_upright: Option<NonZeroUsize>,
+
     /// Whether to hyphenate text to improve line breaking. When `{auto}`, text
     /// will be hyphenated if and only if justification is enabled.
     ///
@@ -476,6 +535,24 @@ pub struct TextElem {
     #[fold]
     pub features: FontFeatures,
 
+    /// Values for the variation axes of variable fonts.
+    ///
+    /// Maps four-letter axis tags to numbers. The `wght`, `wdth`, `ital`,
+    /// `slnt` and `opsz` axes are already derived from the text's
+    /// [weight]($func/text.weight), [stretch]($func/text.stretch),
+    /// [style]($func/text.style) and [size]($func/text.size), but can be
+    /// overridden here. Custom axes like `GRAD` can only be set through this
+    /// property. Axes that the font doesn't have are ignored and values
+    /// outside of an axis' range are clamped.
+    ///
+    /// ```example
+    /// // Use a higher grade and a fixed optical size.
+    /// #set text(variations: ("GRAD": 100, "opsz": 12))
+    /// Variable fonts
+    /// ```
+    #[fold]
+    pub variations: FontVariations,
+
     /// Content in which all text is styled according to the other arguments.
     #[external]
     #[required]
@@ -510,6 +587,11 @@ pub struct TextElem {
     #[internal]
     #[default(false)]
     pub smallcaps: bool,
+
+    /// Whether the text is part of a page that is set vertically.
+    #[internal]
+    #[default(false)]
+    pub vertical: bool,
 }
 
 impl TextElem {
@@ -517,6 +599,14 @@ impl TextElem {
     pub fn packed(text: impl Into<EcoString>) -> Content {
         Self::new(text.into()).pack()
     }
+
+    /// Whether the text direction in the given style chain is vertical.
+    ///
+    /// This can't be determined through [`dir_in`](Self::dir_in) because the
+    /// resolved direction of vertical text is left-to-right.
+    pub fn is_vertical_in(styles: StyleChain) -> bool {
+        styles.get(Self::func(), "dir", None, TextDir::default).is_vertical()
+    }
 }
 
 impl Construct for TextElem {
@@ -643,24 +733,46 @@ cast! {
     TextDir,
     self => self.0.into_value(),
     v: Smart<Dir> => {
-        if v.map_or(false, |dir| dir.axis() == Axis::Y) {
-            bail!("text direction must be horizontal");
+        if v == Smart::Custom(Dir::BTT) {
+            bail!("text direction must be horizontal or top-to-bottom");
         }
         Self(v)
     },
 }
 
+impl TextDir {
+    /// Whether this is the vertical top-to-bottom direction.
+    pub fn is_vertical(self) -> bool {
+        self.0 == Smart::Custom(Dir::TTB)
+    }
+}
+
 impl Resolve for TextDir {
     type Output = Dir;
 
     fn resolve(self, styles: StyleChain) -> Self::Output {
         match self.0 {
             Smart::Auto => TextElem::lang_in(styles).dir(),
+            // Vertical text is laid out in left-to-right lines which are
+            // rotated by the page.
+            Smart::Custom(Dir::TTB) => Dir::LTR,
             Smart::Custom(dir) => dir,
         }
     }
 }
 
+/// How characters are oriented in vertical text.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum TextOrientation {
+    /// Set CJK characters upright and rotate other characters, like Latin
+    /// letters, by 90 degrees clockwise.
+    Mixed,
+    /// Set all characters upright.
+    Upright,
+    /// Rotate all characters by 90 degrees clockwise.
+    Sideways,
+}
+
 /// Whether to hyphenate text.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Hyphenate(pub Smart<bool>);
@@ -727,6 +839,50 @@ pub enum NumberWidth {
     Tabular,
 }
 
+/// Variable font axis settings.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FontVariations(pub Vec<(Tag, f32)>);
+
+cast! {
+    FontVariations,
+    self => self.0
+        .into_iter()
+        .map(|(tag, value)| {
+            let bytes = tag.to_bytes();
+            let key = std::str::from_utf8(&bytes).unwrap_or_default();
+            (key.into(), (value as f64).into_value())
+        })
+        .collect::<Dict>()
+        .into_value(),
+    values: Dict => Self(values
+        .into_iter()
+        .map(|(k, v)| {
+            let value = v.cast::<f64>()?;
+            let tag = Tag::from_bytes_lossy(k.as_bytes());
+            Ok((tag, value as f32))
+        })
+        .collect::<StrResult<_>>()?),
+}
+
+impl Hash for FontVariations {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for (tag, value) in &self.0 {
+            tag.hash(state);
+            value.to_bits().hash(state);
+        }
+    }
+}
+
+impl Fold for FontVariations {
+    type Output = Self;
+
+    fn fold(self, mut outer: Self::Output) -> Self::Output {
+        // Inner values come last so that they take precedence.
+        outer.0.extend(self.0);
+        outer
+    }
+}
+
 /// OpenType font features settings.
 #[derive(Debug, Default, Clone, Eq, PartialEq, Hash)]
 pub struct FontFeatures(pub Vec<(Tag, u32)>);
@@ -12,6 +12,7 @@ use typst::util::Bytes;
 use super::{
     FontFamily, FontList, Hyphenate, LinebreakElem, SmartQuoteElem, TextElem, TextSize,
 };
+use crate::compute::Readable;
 use crate::layout::BlockElem;
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
@@ -65,7 +66,14 @@ pub struct RawElem {
     /// 1 + 2 + 3 + 4 + 5
     /// ```
     /// ````
+    ///
+    /// The text can also be given as bytes, which must be valid UTF-8.
     #[required]
+    #[parse(
+        let Spanned { v: text, span } =
+            args.expect::<Spanned<Readable>>("text")?;
+        text.into_str().at(span)?.into()
+    )]
     pub text: EcoString,
 
     /// Whether the raw text is displayed as a separate block.
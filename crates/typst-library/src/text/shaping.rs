@@ -8,8 +8,8 @@ use typst::font::{Font, FontStyle, FontVariant};
 use typst::util::SliceExt;
 use unicode_script::{Script, UnicodeScript};
 
-use super::{decorate, FontFamily, NumberType, NumberWidth, TextElem};
-use crate::layout::SpanMapper;
+use super::{decorate, FontFamily, NumberType, NumberWidth, TextElem, TextOrientation};
+use crate::layout::{ParElem, SpanMapper};
 use crate::prelude::*;
 
 /// The result of shaping text.
@@ -73,6 +73,27 @@ pub struct ShapedGlyph {
     pub c: char,
     /// The source code location of the glyph and its byte offset within it.
     pub span: (Span, u16),
+    /// How the glyph is oriented in vertical text.
+    pub orientation: GlyphOrientation,
+    /// The space added to the left and right of the glyph to separate CJK
+    /// from Latin text. It is part of the advance and offset.
+    pub autospace: (Em, Em),
+}
+
+/// How a glyph is oriented in vertical text.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GlyphOrientation {
+    /// The glyph follows the direction of the line. This is always the case
+    /// in horizontal text. In vertical text, it appears rotated by 90 degrees
+    /// clockwise.
+    #[default]
+    Rotated,
+    /// The glyph stands upright in its own cell and its advance is the
+    /// vertical advance from the font.
+    Upright,
+    /// The glyph is part of a short horizontal run that stands upright in a
+    /// single cell (tate-chu-yoko).
+    Combined,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -105,6 +126,16 @@ impl ShapedGlyph {
         matches!(self.c.script(), Hiragana | Katakana | Han) || self.c == '\u{30FC}'
     }
 
+    /// Whether the glyph is a Latin, Greek or Cyrillic letter or a digit, which
+    /// is separated from CJK characters by extra space.
+    pub fn is_latin_or_digit(&self) -> bool {
+        use Script::*;
+        self.orientation != GlyphOrientation::Combined
+            && (self.c.is_ascii_digit()
+                || (self.c.is_alphanumeric()
+                    && matches!(self.c.script(), Latin | Greek | Cyrillic)))
+    }
+
     pub fn is_cjk_punctuation(&self) -> bool {
         self.is_cjk_left_aligned_punctuation(true)
             || self.is_cjk_right_aligned_punctuation()
@@ -125,7 +156,8 @@ impl ShapedGlyph {
             return true;
         }
 
-        matches!(self.c, '》' | '）' | '』' | '」')
+        matches!(self.c, '》' | '）' | '』' | '」' | '〕' | '］' | '｝')
+            || matches!(self.c, '〉' | '】' | '〙' | '〗' | '〟' | '．')
     }
 
     /// See <https://www.w3.org/TR/clreq/#punctuation_width_adjustment>
@@ -138,7 +170,8 @@ impl ShapedGlyph {
             return true;
         }
 
-        matches!(self.c, '《' | '（' | '『' | '「')
+        matches!(self.c, '《' | '（' | '『' | '「' | '〔' | '［')
+            || matches!(self.c, '｛' | '〈' | '【' | '〘' | '〖' | '〝')
     }
 
     /// See <https://www.w3.org/TR/clreq/#punctuation_width_adjustment>
@@ -203,6 +236,68 @@ impl ShapedGlyph {
         self.adjustability.shrinkability.1 -= amount;
         self.adjustability.stretchability.1 += amount;
     }
+
+    /// Add CJK-Latin spacing on the left side of the glyph. During
+    /// justification, the spacing may shrink to half and grow to double its
+    /// size.
+    pub fn autospace_left(&mut self, amount: Em) {
+        self.x_offset += amount;
+        self.x_advance += amount;
+        self.autospace.0 += amount;
+        self.adjustability.shrinkability.0 += amount / 2.0;
+        self.adjustability.stretchability.0 += amount;
+    }
+
+    /// Add CJK-Latin spacing on the right side of the glyph.
+    pub fn autospace_right(&mut self, amount: Em) {
+        self.x_advance += amount;
+        self.autospace.1 += amount;
+        self.adjustability.shrinkability.1 += amount / 2.0;
+        self.adjustability.stretchability.1 += amount;
+    }
+
+    /// Remove the CJK-Latin spacing on the left side of the glyph and return
+    /// its amount.
+    pub fn trim_autospace_left(&mut self) -> Em {
+        let amount = std::mem::take(&mut self.autospace.0);
+        self.x_offset -= amount;
+        self.x_advance -= amount;
+        self.adjustability.shrinkability.0 -= amount / 2.0;
+        self.adjustability.stretchability.0 -= amount;
+        amount
+    }
+
+    /// Remove the CJK-Latin spacing on the right side of the glyph and return
+    /// its amount.
+    pub fn trim_autospace_right(&mut self) -> Em {
+        let amount = std::mem::take(&mut self.autospace.1);
+        self.x_advance -= amount;
+        self.adjustability.shrinkability.1 -= amount / 2.0;
+        self.adjustability.stretchability.1 -= amount;
+        amount
+    }
+}
+
+/// The amount of space between CJK and Latin text.
+const CJK_LATIN_SPACING: Em = Em::new(0.25);
+
+/// Add spacing between two adjacent glyphs if one of them is a CJK character
+/// and the other one is a Latin letter or digit. The spacing is always added
+/// to the CJK glyph. Returns the added amount.
+pub fn add_cjk_latin_spacing(prev: &mut ShapedGlyph, next: &mut ShapedGlyph) -> Em {
+    if prev.range.start == next.range.start {
+        return Em::zero();
+    }
+
+    if prev.is_cjk_script() && next.is_latin_or_digit() {
+        prev.autospace_right(CJK_LATIN_SPACING);
+        CJK_LATIN_SPACING
+    } else if prev.is_latin_or_digit() && next.is_cjk_script() {
+        next.autospace_left(CJK_LATIN_SPACING);
+        CJK_LATIN_SPACING
+    } else {
+        Em::zero()
+    }
 }
 
 /// A side you can go toward.
@@ -236,8 +331,10 @@ impl<'a> ShapedText<'a> {
         let decos = TextElem::deco_in(self.styles);
         let fill = TextElem::fill_in(self.styles);
 
-        for ((font, y_offset), group) in
-            self.glyphs.as_ref().group_by_key(|g| (g.font.clone(), g.y_offset))
+        for ((font, y_offset, orientation), group) in self
+            .glyphs
+            .as_ref()
+            .group_by_key(|g| (g.font.clone(), g.y_offset, g.orientation))
         {
             let mut range = group[0].range.clone();
             for glyph in group {
@@ -245,28 +342,27 @@ impl<'a> ShapedText<'a> {
                 range.end = range.end.max(glyph.range.end);
             }
 
+            if orientation != GlyphOrientation::Rotated {
+                offset += self.build_upright(
+                    &mut frame,
+                    group,
+                    offset,
+                    top + shift,
+                    justification_ratio,
+                    extra_justification,
+                );
+                continue;
+            }
+
             let pos = Point::new(offset, top + shift - y_offset.at(self.size));
             let glyphs = group
                 .iter()
                 .map(|glyph| {
-                    let adjustability_left = if justification_ratio < 0.0 {
-                        glyph.shrinkability().0
-                    } else {
-                        glyph.stretchability().0
-                    };
-                    let adjustability_right = if justification_ratio < 0.0 {
-                        glyph.shrinkability().1
-                    } else {
-                        glyph.stretchability().1
-                    };
-
-                    let justification_left = adjustability_left * justification_ratio;
-                    let mut justification_right =
-                        adjustability_right * justification_ratio;
-                    if glyph.is_justifiable() {
-                        justification_right +=
-                            Em::from_length(extra_justification, self.size)
-                    }
+                    let (justification_left, justification_right) = self.justification(
+                        glyph,
+                        justification_ratio,
+                        extra_justification,
+                    );
 
                     frame.size_mut().x += justification_left.at(self.size)
                         + justification_right.at(self.size);
@@ -311,11 +407,141 @@ impl<'a> ShapedText<'a> {
         frame
     }
 
+    /// The extra advance a glyph gets on its left and right side through
+    /// justification.
+    fn justification(
+        &self,
+        glyph: &ShapedGlyph,
+        justification_ratio: f64,
+        extra_justification: Abs,
+    ) -> (Em, Em) {
+        let adjustability_left = if justification_ratio < 0.0 {
+            glyph.shrinkability().0
+        } else {
+            glyph.stretchability().0
+        };
+        let adjustability_right = if justification_ratio < 0.0 {
+            glyph.shrinkability().1
+        } else {
+            glyph.stretchability().1
+        };
+
+        let justification_left = adjustability_left * justification_ratio;
+        let mut justification_right = adjustability_right * justification_ratio;
+        if glyph.is_justifiable() {
+            justification_right += Em::from_length(extra_justification, self.size)
+        }
+
+        (justification_left, justification_right)
+    }
+
+    /// Build a group of upright or combined glyphs in vertical text and
+    /// return the advance it takes up in the line.
+    ///
+    /// The line is rotated clockwise by the page afterwards, so the glyph cells
+    /// are rotated counter-clockwise here to end up upright. The cells are
+    /// centered on the line's baseline.
+    fn build_upright(
+        &self,
+        frame: &mut Frame,
+        group: &[ShapedGlyph],
+        offset: Abs,
+        baseline: Abs,
+        justification_ratio: f64,
+        extra_justification: Abs,
+    ) -> Abs {
+        let lang = TextElem::lang_in(self.styles);
+        let fill = TextElem::fill_in(self.styles);
+        let font = &group[0].font;
+        let ascender = font.metrics().ascender.at(self.size);
+
+        // Create a text item for the given glyphs with their horizontal
+        // advances.
+        let item = |glyphs: &[ShapedGlyph]| {
+            let start = glyphs[0].range.start;
+            let end = glyphs.iter().map(|g| g.range.end).max().unwrap_or(start);
+            TextItem {
+                font: font.clone(),
+                size: self.size,
+                lang,
+                fill: fill.clone(),
+                text: self.text[start - self.base..end - self.base].into(),
+                glyphs: glyphs
+                    .iter()
+                    .map(|glyph| Glyph {
+                        id: glyph.glyph_id,
+                        x_advance: font.advance(glyph.glyph_id).unwrap_or_default(),
+                        x_offset: Em::zero(),
+                        range: (glyph.range.start - start).saturating_as()
+                            ..(glyph.range.end - start).saturating_as(),
+                        span: glyph.span,
+                    })
+                    .collect(),
+            }
+        };
+
+        let rotate = Transform::rotate(Angle::deg(-90.0));
+        let mut advance = Abs::zero();
+
+        if group[0].orientation == GlyphOrientation::Combined {
+            // Squeeze the run into a single cell.
+            let mut justification = Em::zero();
+            for glyph in group {
+                let (left, right) =
+                    self.justification(glyph, justification_ratio, extra_justification);
+                justification += left + right;
+            }
+
+            let item = item(group);
+            let width = item.width();
+            let cell = self.size;
+            let scale = if width > cell { cell / width } else { 1.0 };
+
+            let mut unit = Frame::new(Size::new(width, cell));
+            unit.push(Point::with_y(ascender), FrameItem::Text(item));
+            unit.transform(Transform::scale(Ratio::new(scale), Ratio::one()));
+
+            let mut sub = Frame::new(Size::splat(cell));
+            sub.push_frame(Point::with_x((cell - width * scale) / 2.0), unit);
+            sub.transform(rotate);
+
+            let extent: Em =
+                group.iter().map(|g| g.x_advance).sum::<Em>() + justification;
+            frame.size_mut().x += justification.at(self.size);
+            frame.push_frame(Point::new(offset, baseline + cell / 2.0), sub);
+            advance += extent.at(self.size);
+        } else {
+            for glyph in group {
+                let (left, right) =
+                    self.justification(glyph, justification_ratio, extra_justification);
+                frame.size_mut().x += (left + right).at(self.size);
+
+                let item = item(std::slice::from_ref(glyph));
+                let width = item.width();
+                let mut sub = Frame::new(Size::new(width, glyph.x_advance.at(self.size)));
+                sub.push(Point::with_y(ascender), FrameItem::Text(item));
+                sub.transform(rotate);
+
+                let x = offset + advance + (glyph.x_offset + left).at(self.size);
+                frame.push_frame(Point::new(x, baseline + width / 2.0), sub);
+                advance += (glyph.x_advance + left + right).at(self.size);
+            }
+        }
+
+        advance
+    }
+
     /// Measure the top and bottom extent of this text.
     fn measure(&self, vt: &Vt) -> (Abs, Abs) {
         let mut top = Abs::zero();
         let mut bottom = Abs::zero();
 
+        // In vertical text, the line's baseline is the center of the
+        // character cells.
+        if TextElem::vertical_in(self.styles) {
+            return (self.size / 2.0, self.size / 2.0);
+        }
+
         let top_edge = TextElem::top_edge_in(self.styles);
         let bottom_edge = TextElem::bottom_edge_in(self.styles);
 
@@ -336,7 +562,7 @@ impl<'a> ShapedText<'a> {
                     .select(family.as_str(), self.variant)
                     .and_then(|id| world.font(id))
                 {
-                    expand(&font);
+                    expand(&instance(&font, self.styles, self.variant));
                     break;
                 }
             }
@@ -430,6 +656,7 @@ impl<'a> ShapedText<'a> {
                 .book()
                 .select(family.as_str(), self.variant)
                 .and_then(|id| world.font(id))?;
+            let font = instance(&font, self.styles, self.variant);
             let ttf = font.ttf();
             let glyph_id = ttf.glyph_index('-')?;
             let x_advance = font.to_em(ttf.glyph_hor_advance(glyph_id)?);
@@ -438,18 +665,25 @@ impl<'a> ShapedText<'a> {
                 .last()
                 .map(|g| g.range.end..g.range.end)
                 .unwrap_or_default();
+            let y_offset = if TextElem::vertical_in(self.styles) {
+                rotated_offset(&font)
+            } else {
+                Em::zero()
+            };
             self.width += x_advance.at(self.size);
             self.glyphs.to_mut().push(ShapedGlyph {
                 font,
                 glyph_id: glyph_id.0,
                 x_advance,
                 x_offset: Em::zero(),
-                y_offset: Em::zero(),
+                y_offset,
                 adjustability: Adjustability::default(),
                 range,
                 safe_to_break: true,
                 c: '-',
                 span: (Span::detached(), 0),
+                orientation: GlyphOrientation::Rotated,
+                autospace: (Em::zero(), Em::zero()),
             });
             Some(())
         });
@@ -561,7 +795,11 @@ pub fn shape<'a>(
     };
 
     if !text.is_empty() {
-        shape_segment(&mut ctx, base, text, families(styles));
+        if TextElem::vertical_in(styles) {
+            shape_vertical(&mut ctx, base, text);
+        } else {
+            shape_segment(&mut ctx, base, text, families(styles));
+        }
     }
 
     track_and_space(&mut ctx);
@@ -586,6 +824,116 @@ pub fn shape<'a>(
     }
 }
 
+/// Shape vertical text in runs of upright, rotated and combined characters.
+fn shape_vertical(ctx: &mut ShapingContext, base: usize, text: &str) {
+    let orientation = TextElem::orientation_in(ctx.styles);
+    let combine = TextElem::combine_upright_in(ctx.styles).map_or(0, NonZeroUsize::get);
+
+    // Split the text into runs of equal orientation.
+    let mut runs: Vec<(Range<usize>, GlyphOrientation)> = vec![];
+    let mut cursor = 0;
+    while let Some(c) = text[cursor..].chars().next() {
+        let mut end = cursor + c.len_utf8();
+        let kind = match orientation {
+            TextOrientation::Upright => GlyphOrientation::Upright,
+            TextOrientation::Sideways => GlyphOrientation::Rotated,
+            TextOrientation::Mixed if c.is_ascii_digit() => {
+                let digits =
+                    text[cursor..].bytes().take_while(u8::is_ascii_digit).count();
+                let before = text[..cursor].chars().next_back();
+                if digits <= combine && !before.map_or(false, |c| c.is_ascii_digit()) {
+                    end = cursor + digits;
+                    GlyphOrientation::Combined
+                } else {
+                    GlyphOrientation::Rotated
+                }
+            }
+            TextOrientation::Mixed if is_upright(c) => GlyphOrientation::Upright,
+            TextOrientation::Mixed => GlyphOrientation::Rotated,
+        };
+
+        match runs.last_mut() {
+            Some((range, last))
+                if *last == kind && kind != GlyphOrientation::Combined =>
+            {
+                range.end = end
+            }
+            _ => runs.push((cursor..end, kind)),
+        }
+
+        cursor = end;
+    }
+
+    for (range, kind) in runs {
+        let start = ctx.glyphs.len();
+        let tags = ctx.tags.len();
+        if kind == GlyphOrientation::Upright {
+            // Use the vertical alternates of glyphs like brackets.
+            for feature in [b"vert", b"vrt2"] {
+                ctx.tags.push(Feature::new(Tag::from_bytes(feature), 1, ..));
+            }
+        }
+
+        shape_segment(
+            ctx,
+            base + range.start,
+            &text[range.clone()],
+            families(ctx.styles),
+        );
+        ctx.tags.truncate(tags);
+
+        let glyphs = &mut ctx.glyphs[start..];
+        let count = glyphs.len();
+        for glyph in glyphs {
+            glyph.orientation = kind;
+            match kind {
+                GlyphOrientation::Rotated => {
+                    glyph.y_offset += rotated_offset(&glyph.font);
+                }
+                GlyphOrientation::Upright => {
+                    glyph.x_advance = glyph
+                        .font
+                        .ttf()
+                        .glyph_ver_advance(ttf_parser::GlyphId(glyph.glyph_id))
+                        .map_or(Em::one(), |units| glyph.font.to_em(units));
+                    glyph.x_offset = Em::zero();
+                    glyph.y_offset = Em::zero();
+                }
+                GlyphOrientation::Combined => {
+                    glyph.x_advance = Em::one() / count as f64;
+                    glyph.x_offset = Em::zero();
+                    glyph.y_offset = Em::zero();
+                }
+            }
+        }
+    }
+}
+
+/// The vertical offset that centers rotated glyphs of a font on the baseline
+/// of vertical text.
+fn rotated_offset(font: &Font) -> Em {
+    let metrics = font.metrics();
+    -(metrics.ascender + metrics.descender) / 2.0
+}
+
+/// Whether a character stands upright in vertical text, following the
+/// Unicode `Vertical_Orientation` property for common characters.
+fn is_upright(c: char) -> bool {
+    use Script::*;
+    matches!(c.script(), Han | Hiragana | Katakana | Hangul | Bopomofo | Yi)
+        || matches!(
+            c as u32,
+            0x2E80..=0x2FFF
+                | 0x3000..=0x30FF
+                | 0x3190..=0x33FF
+                | 0xFE10..=0xFE1F
+                | 0xFE30..=0xFE4F
+                | 0xFF01..=0xFF60
+                | 0xFFE0..=0xFFE7
+                | 0x1F000..=0x1FAFF
+        )
+}
+
 /// Shape text with font fallback using the `families` iterator.
 fn shape_segment(
     ctx: &mut ShapingContext,
@@ -625,6 +973,7 @@ fn shape_segment(
     };
 
     ctx.used.push(font.clone());
+    let font = instance(&font, ctx.styles, ctx.variant);
 
     // Fill the buffer with our text.
     let mut buffer = UnicodeBuffer::new();
@@ -670,6 +1019,8 @@ fn shape_segment(
                 safe_to_break: !info.unsafe_to_break(),
                 c: text[cluster..].chars().next().unwrap(),
                 span: ctx.spans.span_at(start),
+                orientation: GlyphOrientation::Rotated,
+                autospace: (Em::zero(), Em::zero()),
             });
         } else {
             // First, search for the end of the tofu sequence.
@@ -720,6 +1071,7 @@ fn shape_segment(
 
 /// Shape the text with tofus from the given font.
 fn shape_tofus(ctx: &mut ShapingContext, base: usize, text: &str, font: Font) {
+    let font = instance(&font, ctx.styles, ctx.variant);
     let x_advance = font.advance(0).unwrap_or_default();
     let add_glyph = |(cluster, c): (usize, char)| {
         let start = base + cluster;
@@ -735,6 +1087,8 @@ fn shape_tofus(ctx: &mut ShapingContext, base: usize, text: &str, font: Font) {
             safe_to_break: true,
             c,
             span: ctx.spans.span_at(start),
+            orientation: GlyphOrientation::Rotated,
+            autospace: (Em::zero(), Em::zero()),
         });
     };
     if ctx.dir.is_positive() {
@@ -786,16 +1140,19 @@ fn calculate_adjustability(ctx: &mut ShapingContext, lang: Lang, region: Option<
         glyph.adjustability = glyph.base_adjustability(gb_style);
     }
 
+    apply_justification_limits(ctx);
+
     let mut glyphs = ctx.glyphs.iter_mut().peekable();
     while let Some(glyph) = glyphs.next() {
-        // Only GB style needs further adjustment.
+        // Only GB style needs further adjustment. This includes Japanese, while
+        // the centered marks of Taiwan and Hong Kong keep their full width.
         if glyph.is_cjk_punctuation() && !gb_style {
             continue;
         }
 
         // Now we apply consecutive punctuation adjustment, specified in Chinese Layout
         // Requirements, section 3.1.6.1 Punctuation Adjustment Space, and Japanese Layout
-        // Requirements, section 3.1 Line Composition Rules for Punctuation Marks
+        // Requirements, section 3.1 Line Composition Rules for Punctuation Marks.
         let Some(next) = glyphs.peek_mut() else { continue };
         let width = glyph.x_advance;
         let delta = width / 2.0;
@@ -808,6 +1165,45 @@ fn calculate_adjustability(ctx: &mut ShapingContext, lang: Lang, region: Option<
             next.shrink_left(delta - left_delta);
         }
     }
+
+    if ctx.dir.is_positive() && TextElem::cjk_latin_spacing_in(ctx.styles).is_auto() {
+        for i in 1..ctx.glyphs.len() {
+            let (prev, next) = ctx.glyphs.split_at_mut(i);
+            add_cjk_latin_spacing(&mut prev[i - 1], &mut next[0]);
+        }
+    }
+}
+
+/// Adjust the stretchability and shrinkability of spaces and the tracking
+/// between characters to the paragraph's justification limits.
+fn apply_justification_limits(ctx: &mut ShapingContext) {
+    let limits = ParElem::justification_limits_in(ctx.styles);
+    let styles = ctx.styles;
+    let size = ctx.size;
+    let em = |abs: Abs| Em::from_length(abs, size);
+    let spacing_min = limits.spacing_min().resolve(styles).map(em);
+    let spacing_max = limits.spacing_max().resolve(styles).map(em);
+    let tracking_min = em(limits.tracking_min().resolve(styles)).min(Em::zero());
+    let tracking_max = em(limits.tracking_max().resolve(styles)).max(Em::zero());
+
+    let mut glyphs = ctx.glyphs.iter_mut().peekable();
+    while let Some(glyph) = glyphs.next() {
+        if glyph.is_space() {
+            let width = glyph.x_advance;
+            let min = spacing_min.relative_to(width);
+            let max = spacing_max.relative_to(width);
+            glyph.adjustability.stretchability.1 = (max - width).max(Em::zero());
+            glyph.adjustability.shrinkability.1 = (width - min).max(Em::zero());
+        } else if !glyph.is_cjk_script()
+            && !glyph.is_cjk_punctuation()
+            && glyphs
+                .peek()
+                .map_or(false, |next| glyph.range.start != next.range.start)
+        {
+            glyph.adjustability.stretchability.1 += tracking_max;
+            glyph.adjustability.shrinkability.1 -= tracking_min;
+        }
+    }
 }
 
 /// Difference between non-breaking and normal space.
@@ -841,6 +1237,41 @@ pub fn variant(styles: StyleChain) -> FontVariant {
     variant
 }
 
+/// Apply the variation axis values requested by the styles to a font.
+///
+/// The `wght`, `wdth`, `ital`, `slnt` and `opsz` axes are derived from the
+/// variant and font size. Explicit `variations` take precedence over them.
+/// Fonts without variation axes are returned unchanged.
+pub fn instance(font: &Font, styles: StyleChain, variant: FontVariant) -> Font {
+    if font.info().axes.is_empty() {
+        return font.clone();
+    }
+
+    let (ital, slnt) = match variant.style {
+        FontStyle::Normal => (0.0, 0.0),
+        FontStyle::Italic => (1.0, 0.0),
+        FontStyle::Oblique => (0.0, -14.0),
+    };
+
+    let mut coords = vec![
+        (Tag::from_bytes(b"wght"), variant.weight.to_number() as f32),
+        (Tag::from_bytes(b"wdth"), variant.stretch.to_ratio().get() as f32 * 100.0),
+        (Tag::from_bytes(b"ital"), ital),
+        (Tag::from_bytes(b"slnt"), slnt),
+        (Tag::from_bytes(b"opsz"), TextElem::size_in(styles).to_pt() as f32),
+    ];
+
+    // Fonts without an italic axis might still have a slant axis.
+    if variant.style == FontStyle::Italic
+        && font.info().axis(Tag::from_bytes(b"ital")).is_none()
+    {
+        coords[3].1 = -14.0;
+    }
+
+    coords.extend(TextElem::variations_in(styles).0);
+    font.with_variations(&coords)
+}
+
 /// Resolve a prioritized iterator over the font families.
 pub fn families(styles: StyleChain) -> impl Iterator<Item = FontFamily> + Clone {
     const FALLBACKS: &[&str] = &[
@@ -4,6 +4,7 @@ use std::path::Path;
 use typst::image::{Image, ImageFormat, RasterFormat, VectorFormat};
 use typst::util::Bytes;
 
+use crate::compute::Readable;
 use crate::meta::{Figurable, LocalName};
 use crate::prelude::*;
 use crate::text::families;
@@ -32,6 +33,10 @@ use crate::text::families;
 /// Display: Image
 /// Category: visualize
 #[element(Layout, LocalName, Figurable)]
+#[scope(
+    scope.define("decode", image_decode_func());
+    scope
+)]
 pub struct ImageElem {
     /// Path to an image file.
     #[required]
@@ -50,6 +55,10 @@ pub struct ImageElem {
     #[parse(data)]
     pub data: Bytes,
 
+    /// The image's format. Detected automatically by default, first from the
+    /// file extension and then from the data itself.
+    pub format: Smart<ImageFormat>,
+
     /// The width of the image.
     pub width: Smart<Rel<Length>>,
 
@@ -64,6 +73,61 @@ pub struct ImageElem {
     pub fit: ImageFit,
 }
 
+/// Decode a raster or vector graphic from bytes or a string.
+///
+/// ## Example { #example }
+/// ```example
+/// #let original = read("diagram.svg")
+/// #let changed = original.replace(
+///   "#2B80FF", // blue
+///   green.hex(),
+/// )
+///
+/// #image.decode(original)
+/// #image.decode(changed)
+/// ```
+///
+/// Display: Decode Image
+/// Category: visualize
+#[func]
+pub fn image_decode(
+    /// The data to decode as an image. Can be a string for SVGs.
+    data: Readable,
+    /// The image's format. Detected automatically by default.
+    #[named]
+    format: Option<Smart<ImageFormat>>,
+    /// The width of the image.
+    #[named]
+    width: Option<Smart<Rel<Length>>>,
+    /// The height of the image.
+    #[named]
+    height: Option<Smart<Rel<Length>>>,
+    /// A text describing the image.
+    #[named]
+    alt: Option<Option<EcoString>>,
+    /// How the image should adjust itself to a given area.
+    #[named]
+    fit: Option<ImageFit>,
+) -> Content {
+    let mut elem = ImageElem::new(EcoString::new(), data.into());
+    if let Some(format) = format {
+        elem.push_format(format);
+    }
+    if let Some(width) = width {
+        elem.push_width(width);
+    }
+    if let Some(height) = height {
+        elem.push_height(height);
+    }
+    if let Some(alt) = alt {
+        elem.push_alt(alt);
+    }
+    if let Some(fit) = fit {
+        elem.push_fit(fit);
+    }
+    elem.pack()
+}
+
 impl Layout for ImageElem {
     #[tracing::instrument(name = "ImageElem::layout", skip_all)]
     fn layout(
@@ -72,22 +136,16 @@ impl Layout for ImageElem {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        let ext = Path::new(self.path().as_str())
-            .extension()
-            .and_then(OsStr::to_str)
-            .unwrap_or_default()
-            .to_lowercase();
-
-        let format = match ext.as_str() {
-            "png" => ImageFormat::Raster(RasterFormat::Png),
-            "jpg" | "jpeg" => ImageFormat::Raster(RasterFormat::Jpg),
-            "gif" => ImageFormat::Raster(RasterFormat::Gif),
-            "svg" | "svgz" => ImageFormat::Vector(VectorFormat::Svg),
-            _ => bail!(self.span(), "unknown image format"),
+        let data = self.data();
+        let format = match self.format(styles) {
+            Smart::Custom(format) => format,
+            Smart::Auto => determine_format(self.path().as_str(), &data)
+                .ok_or("unknown image format")
+                .at(self.span())?,
         };
 
         let image = Image::with_fonts(
-            self.data(),
+            data,
             format,
             vt.world,
             families(styles).next().as_ref().map(|f| f.as_str()),
@@ -153,6 +211,24 @@ impl Layout for ImageElem {
     }
 }
 
+/// Determine the format of an image from its path's extension or, failing
+/// that, from its data.
+fn determine_format(path: &str, data: &[u8]) -> Option<ImageFormat> {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match ext.as_str() {
+        "png" => Some(ImageFormat::Raster(RasterFormat::Png)),
+        "jpg" | "jpeg" => Some(ImageFormat::Raster(RasterFormat::Jpg)),
+        "gif" => Some(ImageFormat::Raster(RasterFormat::Gif)),
+        "svg" | "svgz" => Some(ImageFormat::Vector(VectorFormat::Svg)),
+        _ => ImageFormat::detect(data),
+    }
+}
+
 impl LocalName for ImageElem {
     fn local_name(&self, lang: Lang, _: Option<Region>) -> &'static str {
         match lang {
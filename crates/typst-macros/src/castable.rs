@@ -107,7 +107,7 @@ pub fn cast(stream: TokenStream) -> Result<TokenStream> {
 
     let ty = input.name.as_ref().map(|name| {
         quote! {
-            impl #eval::Type for #ty {
+            impl #eval::NativeType for #ty {
                 const TYPE_NAME: &'static str = #name;
             }
         }
@@ -83,6 +83,10 @@ pub struct SourceError {
     /// Additonal hints to the user, indicating how this error could be avoided
     /// or worked around.
     pub hints: Vec<EcoString>,
+    /// Whether the error aborts compilation even when it occurs within a call
+    /// to `catch`. This is the case for explicit panics and exceeded resource
+    /// limits.
+    pub fatal: bool,
 }
 
 impl SourceError {
@@ -93,6 +97,7 @@ impl SourceError {
             trace: vec![],
             message: message.into(),
             hints: vec![],
+            fatal: false,
         }
     }
 
@@ -101,6 +106,12 @@ impl SourceError {
         self.hints.extend(hints);
         self
     }
+
+    /// Marks the error as fatal so that it cannot be caught.
+    pub fn into_fatal(mut self) -> Self {
+        self.fatal = true;
+        self
+    }
 }
 
 /// A part of an error's [trace](SourceError::trace).
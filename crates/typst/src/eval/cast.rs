@@ -292,6 +292,7 @@ impl<T> Variadics for Vec<T> {
 }
 
 /// An uninhabitable type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Never {}
 
 impl Reflect for Never {
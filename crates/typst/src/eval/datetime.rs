@@ -1,11 +1,15 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
 
 use ecow::{eco_format, EcoString, EcoVec};
-use time::error::{Format, InvalidFormatDescription};
-use time::{format_description, PrimitiveDateTime};
+use time::error::{Format, InvalidFormatDescription, Parse, TryFromParsed};
+use time::format_description::FormatItem;
+use time::{format_description, OffsetDateTime, PrimitiveDateTime, UtcOffset};
 
+use super::Duration;
+use crate::diag::StrResult;
 use crate::eval::cast;
 use crate::util::pretty_array_like;
 
@@ -79,6 +83,25 @@ impl Datetime {
         }
     }
 
+    /// Return the ordinal (day of the year, starting at 1) of the datetime, if
+    /// existing.
+    pub fn ordinal(&self) -> Option<u16> {
+        match self {
+            Datetime::Date(date) => Some(date.ordinal()),
+            Datetime::Time(_) => None,
+            Datetime::Datetime(datetime) => Some(datetime.ordinal()),
+        }
+    }
+
+    /// Return the ISO 8601 week number of the datetime, if existing.
+    pub fn iso_week(&self) -> Option<u8> {
+        match self {
+            Datetime::Date(date) => Some(date.iso_week()),
+            Datetime::Time(_) => None,
+            Datetime::Datetime(datetime) => Some(datetime.iso_week()),
+        }
+    }
+
     /// Return the hour of the datetime, if existing.
     pub fn hour(&self) -> Option<u8> {
         match self {
@@ -134,6 +157,150 @@ impl Datetime {
         let time = time::Time::from_hms(hour, minute, second).ok()?;
         Some(Datetime::Datetime(PrimitiveDateTime::new(date, time)))
     }
+
+    /// Parse a datetime from an ISO 8601 string.
+    ///
+    /// Supports dates (`2023-05-01`), times (`10:30:00` or `10:30`) and
+    /// combinations of both (`2023-05-01T10:30:00`), where full datetimes may
+    /// carry a UTC offset (`Z` or `+02:00`). If `offset` is given, the result
+    /// is converted to that offset (in hours from UTC), assuming UTC for
+    /// datetimes without an offset. Otherwise, the time is kept as written.
+    pub fn from_iso(text: &str, offset: Option<i64>) -> StrResult<Self> {
+        static FORMATS: &[&str] = &[
+            "[year]-[month]-[day]T[hour]:[minute]:[second]\
+             [offset_hour sign:mandatory]:[offset_minute]",
+            "[year]-[month]-[day]T[hour]:[minute]:[second]",
+            "[year]-[month]-[day]T[hour]:[minute]",
+            "[year]-[month]-[day] [hour]:[minute]:[second]",
+            "[year]-[month]-[day]",
+            "[hour]:[minute]:[second]",
+            "[hour]:[minute]",
+        ];
+
+        // The time crate's format descriptions have no syntax for `Z`.
+        let normalized = match text.strip_suffix('Z') {
+            Some(rest) if rest.contains('T') => eco_format!("{rest}+00:00"),
+            _ => text.into(),
+        };
+
+        for pattern in FORMATS {
+            let format = format_description::parse(pattern)
+                .map_err(format_time_invalid_format_description_error)?;
+            if let Ok(datetime) = Self::parse_with(&normalized, &format, offset) {
+                return Ok(datetime);
+            }
+        }
+
+        Err(eco_format!("invalid ISO 8601 datetime: {text}"))
+    }
+
+    /// Parse a datetime from a string in the given format.
+    ///
+    /// The format uses the same syntax as [`display`](Self::display). If the
+    /// format contains a UTC offset, the `offset` parameter behaves like for
+    /// [`from_iso`](Self::from_iso).
+    pub fn parse(text: &str, pattern: &str, offset: Option<i64>) -> StrResult<Self> {
+        let format = format_description::parse(pattern)
+            .map_err(format_time_invalid_format_description_error)?;
+        Self::parse_with(text, &format, offset)
+    }
+
+    /// Parse a datetime with a format description, picking the most precise
+    /// kind of datetime the format has components for.
+    fn parse_with(
+        text: &str,
+        format: &[FormatItem],
+        offset: Option<i64>,
+    ) -> StrResult<Self> {
+        let insufficient = |err: &Parse| {
+            matches!(err, Parse::TryFromParsed(TryFromParsed::InsufficientInformation))
+        };
+
+        let convert = |local: PrimitiveDateTime, from: UtcOffset| match offset {
+            Some(hours) => convert_offset(local, from, hours)
+                .map(Datetime::Datetime)
+                .ok_or_else(|| "datetime is out of range".into()),
+            None => Ok(Datetime::Datetime(local)),
+        };
+
+        match OffsetDateTime::parse(text, format) {
+            Ok(datetime) => {
+                let local = PrimitiveDateTime::new(datetime.date(), datetime.time());
+                return convert(local, datetime.offset());
+            }
+            Err(err) if !insufficient(&err) => return Err(format_time_parse_error(err)),
+            Err(_) => {}
+        }
+
+        match PrimitiveDateTime::parse(text, format) {
+            Ok(datetime) => return convert(datetime, UtcOffset::UTC),
+            Err(err) if !insufficient(&err) => return Err(format_time_parse_error(err)),
+            Err(_) => {}
+        }
+
+        match time::Date::parse(text, format) {
+            Ok(date) => return Ok(Datetime::Date(date)),
+            Err(err) if !insufficient(&err) => return Err(format_time_parse_error(err)),
+            Err(_) => {}
+        }
+
+        time::Time::parse(text, format)
+            .map(Datetime::Time)
+            .map_err(format_time_parse_error)
+    }
+
+    /// Add a duration to the datetime.
+    ///
+    /// For dates, only the whole days of the duration are considered. Times
+    /// wrap around at midnight. Returns `None` if the result is out of range.
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        let duration = time::Duration::from(duration);
+        Some(match self {
+            Datetime::Date(date) => Datetime::Date(date.checked_add(duration)?),
+            Datetime::Time(time) => Datetime::Time(time + duration),
+            Datetime::Datetime(datetime) => {
+                Datetime::Datetime(datetime.checked_add(duration)?)
+            }
+        })
+    }
+
+    /// Subtract a duration from the datetime.
+    ///
+    /// Behaves like [`checked_add`](Self::checked_add) with the negated
+    /// duration.
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        self.checked_add(duration.checked_neg()?)
+    }
+
+    /// The duration from `other` to `self`, if both are of the same kind.
+    pub fn duration_since(self, other: Self) -> Option<Duration> {
+        Some(Duration::from(match (self, other) {
+            (Datetime::Date(a), Datetime::Date(b)) => a - b,
+            (Datetime::Time(a), Datetime::Time(b)) => a - b,
+            (Datetime::Datetime(a), Datetime::Datetime(b)) => a - b,
+            _ => return None,
+        }))
+    }
+
+    /// The kind of datetime, for use in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Datetime::Date(_) => "date",
+            Datetime::Time(_) => "time",
+            Datetime::Datetime(_) => "datetime",
+        }
+    }
+}
+
+impl PartialOrd for Datetime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Datetime::Date(a), Datetime::Date(b)) => a.partial_cmp(b),
+            (Datetime::Time(a), Datetime::Time(b)) => a.partial_cmp(b),
+            (Datetime::Datetime(a), Datetime::Datetime(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
 }
 
 impl Debug for Datetime {
@@ -165,6 +332,30 @@ fn format_time_format_error(error: Format) -> EcoString {
     }
 }
 
+/// Convert a datetime from one UTC offset to another one, given in hours.
+fn convert_offset(
+    datetime: PrimitiveDateTime,
+    from: UtcOffset,
+    hours: i64,
+) -> Option<PrimitiveDateTime> {
+    let to = time::Duration::seconds(hours.checked_mul(60 * 60)?);
+    let from = time::Duration::seconds(from.whole_seconds().into());
+    datetime.checked_sub(from)?.checked_add(to)
+}
+
+/// Format the `Parse` error of the time crate in an appropriate way.
+fn format_time_parse_error(error: Parse) -> EcoString {
+    match error {
+        Parse::TryFromParsed(TryFromParsed::InsufficientInformation) => {
+            "format does not describe a date or time".into()
+        }
+        Parse::TryFromParsed(TryFromParsed::ComponentRange(range)) => {
+            eco_format!("{} is out of range", range.name())
+        }
+        error => eco_format!("failed to parse datetime ({error})"),
+    }
+}
+
 /// Format the `InvalidFormatDescription` error of the time crate in an
 /// appropriate way.
 fn format_time_invalid_format_description_error(
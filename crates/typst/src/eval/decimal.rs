@@ -0,0 +1,208 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::Neg;
+
+use ecow::eco_format;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Error, RoundingStrategy};
+
+use super::Cast;
+use crate::diag::StrResult;
+
+/// A fixed-point decimal number with exact base-10 arithmetic.
+///
+/// Unlike floats, decimals represent numbers like `0.1` exactly, so that
+/// `{decimal("0.1") + decimal("0.2") == decimal("0.3")}` holds. Decimals
+/// have up to 28 digits after the decimal point and keep the number of
+/// decimal places they were created with.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Decimal(rust_decimal::Decimal);
+
+impl Decimal {
+    /// The decimal zero.
+    pub const ZERO: Self = Self(rust_decimal::Decimal::ZERO);
+
+    /// Parse a decimal from its base-10 representation without any loss of
+    /// precision.
+    pub fn parse(text: &str) -> StrResult<Self> {
+        rust_decimal::Decimal::from_str_exact(text)
+            .map(Self)
+            .map_err(|err| match err {
+                Error::Underflow => eco_format!("decimal has too many digits: {text}"),
+                Error::ErrorString(msg) if msg.contains("overflow") => {
+                    eco_format!("decimal is too large: {text}")
+                }
+                _ => eco_format!("invalid decimal: {text}"),
+            })
+    }
+
+    /// Convert a float to the closest decimal, if it is finite and in range.
+    pub fn from_f64(v: f64) -> Option<Self> {
+        rust_decimal::Decimal::from_f64(v).map(Self)
+    }
+
+    /// Convert this decimal to the closest float.
+    pub fn to_f64(self) -> f64 {
+        self.0.to_f64().unwrap_or_default()
+    }
+
+    /// Convert this decimal to an integer, truncating its fractional part.
+    pub fn to_i64(self) -> Option<i64> {
+        self.0.trunc().to_i64()
+    }
+
+    /// Whether the decimal is zero.
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Add two decimals, returning `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Subtract two decimals, returning `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Multiply two decimals, returning `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        self.0.checked_mul(other.0).map(Self)
+    }
+
+    /// Divide two decimals, returning `None` on overflow or division by zero.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.0.checked_div(other.0).map(Self)
+    }
+
+    /// Round the decimal to the given number of decimal places.
+    ///
+    /// The result has exactly `digits` decimal places. Negative `digits`
+    /// round to the left of the decimal point. Returns `None` on overflow.
+    pub fn round(self, digits: i64, mode: RoundingMode) -> Option<Self> {
+        let strategy = mode.strategy();
+        if digits >= 0 {
+            let digits = u32::try_from(digits).ok()?.min(28);
+            let mut rounded = self.0.round_dp_with_strategy(digits, strategy);
+            rounded.rescale(digits);
+            Some(Self(rounded))
+        } else {
+            let exp = u32::try_from(-digits).ok()?;
+            if exp > 28 {
+                return self.round_beyond_max(exp, mode);
+            }
+
+            let factor = 10_i128.pow(exp);
+            let factor =
+                rust_decimal::Decimal::try_from_i128_with_scale(factor, 0).ok()?;
+            let shifted = self.0.checked_div(factor)?;
+            shifted
+                .round_dp_with_strategy(0, strategy)
+                .checked_mul(factor)
+                .map(Self)
+        }
+    }
+
+    /// Round to more than 28 places before the decimal point.
+    ///
+    /// Every decimal is smaller than 10^29 in magnitude, so the result is
+    /// either zero or, if the value is rounded away from zero, too large.
+    fn round_beyond_max(self, exp: u32, mode: RoundingMode) -> Option<Self> {
+        if self.0.is_zero() {
+            return Some(Self::ZERO);
+        }
+
+        let half = rust_decimal::Decimal::from_i128_with_scale(5 * 10_i128.pow(28), 0);
+        let magnitude = self.0.abs();
+        let away = match mode {
+            RoundingMode::HalfUp => exp == 29 && magnitude >= half,
+            RoundingMode::HalfDown | RoundingMode::HalfEven => {
+                exp == 29 && magnitude > half
+            }
+            RoundingMode::Up => true,
+            RoundingMode::Down => false,
+            RoundingMode::Ceiling => self.0.is_sign_positive(),
+            RoundingMode::Floor => self.0.is_sign_negative(),
+        };
+
+        (!away).then_some(Self::ZERO)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(v: i64) -> Self {
+        Self(v.into())
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Debug for Decimal {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "decimal(\"{}\")", self.0)
+    }
+}
+
+/// How to round a number to a given precision.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum RoundingMode {
+    /// Round to the nearest value; halfway cases are rounded away from zero.
+    #[default]
+    HalfUp,
+    /// Round to the nearest value; halfway cases are rounded towards zero.
+    HalfDown,
+    /// Round to the nearest value; halfway cases are rounded to the even
+    /// neighbour (banker's rounding).
+    HalfEven,
+    /// Round away from zero.
+    Up,
+    /// Round towards zero.
+    Down,
+    /// Round towards positive infinity.
+    Ceiling,
+    /// Round towards negative infinity.
+    Floor,
+}
+
+impl RoundingMode {
+    /// Round a float to an integral value with this mode.
+    pub fn round_f64(self, v: f64) -> f64 {
+        let halfway = v.fract().abs() == 0.5;
+        match self {
+            Self::HalfUp => v.round(),
+            Self::HalfDown if halfway => v.trunc(),
+            Self::HalfDown => v.round(),
+            Self::HalfEven if halfway => 2.0 * (v / 2.0).round(),
+            Self::HalfEven => v.round(),
+            Self::Up => v.signum() * v.abs().ceil(),
+            Self::Down => v.trunc(),
+            Self::Ceiling => v.ceil(),
+            Self::Floor => v.floor(),
+        }
+    }
+
+    /// The matching rounding strategy for decimals.
+    fn strategy(self) -> RoundingStrategy {
+        match self {
+            Self::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            Self::HalfDown => RoundingStrategy::MidpointTowardZero,
+            Self::HalfEven => RoundingStrategy::MidpointNearestEven,
+            Self::Up => RoundingStrategy::AwayFromZero,
+            Self::Down => RoundingStrategy::ToZero,
+            Self::Ceiling => RoundingStrategy::ToPositiveInfinity,
+            Self::Floor => RoundingStrategy::ToNegativeInfinity,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::{self, Debug, Formatter};
+
+use ecow::{eco_format, EcoVec};
+
+use crate::eval::cast;
+use crate::util::pretty_array_like;
+
+/// A span of time, like `3 days` or `2 hours and 30 minutes`.
+///
+/// Durations can be added to and subtracted from each other and from
+/// datetimes. Subtracting two datetimes yields a duration.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Duration(time::Duration);
+
+impl Duration {
+    /// The duration of zero length.
+    pub const ZERO: Self = Self(time::Duration::ZERO);
+
+    /// Create a duration from its components, returning `None` if the total
+    /// does not fit into the supported range.
+    pub fn new(
+        weeks: i64,
+        days: i64,
+        hours: i64,
+        minutes: i64,
+        seconds: i64,
+    ) -> Option<Self> {
+        let total = weeks
+            .checked_mul(7 * 24 * 60 * 60)?
+            .checked_add(days.checked_mul(24 * 60 * 60)?)?
+            .checked_add(hours.checked_mul(60 * 60)?)?
+            .checked_add(minutes.checked_mul(60)?)?
+            .checked_add(seconds)?;
+        Some(Self(time::Duration::seconds(total)))
+    }
+
+    /// Create a duration from a possibly fractional number of seconds.
+    pub fn from_seconds_f64(seconds: f64) -> Option<Self> {
+        (seconds.is_finite() && seconds.abs() < i64::MAX as f64)
+            .then(|| Self(time::Duration::seconds_f64(seconds)))
+    }
+
+    /// The duration expressed in seconds.
+    pub fn seconds(self) -> f64 {
+        self.0.as_seconds_f64()
+    }
+
+    /// The duration expressed in minutes.
+    pub fn minutes(self) -> f64 {
+        self.seconds() / 60.0
+    }
+
+    /// The duration expressed in hours.
+    pub fn hours(self) -> f64 {
+        self.seconds() / (60.0 * 60.0)
+    }
+
+    /// The duration expressed in days.
+    pub fn days(self) -> f64 {
+        self.seconds() / (24.0 * 60.0 * 60.0)
+    }
+
+    /// The duration expressed in weeks.
+    pub fn weeks(self) -> f64 {
+        self.seconds() / (7.0 * 24.0 * 60.0 * 60.0)
+    }
+
+    /// Whether the duration is zero.
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Add two durations, returning `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Subtract two durations, returning `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    /// Negate the duration, returning `None` on overflow.
+    pub fn checked_neg(self) -> Option<Self> {
+        Self::ZERO.checked_sub(self)
+    }
+
+    /// Scale the duration by a factor, returning `None` on overflow.
+    pub fn checked_mul(self, factor: f64) -> Option<Self> {
+        Self::from_seconds_f64(self.seconds() * factor)
+    }
+}
+
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+impl Debug for Duration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut rest = self.0;
+        let weeks = rest.whole_weeks();
+        rest -= time::Duration::weeks(weeks);
+        let days = rest.whole_days();
+        rest -= time::Duration::days(days);
+        let hours = rest.whole_hours();
+        rest -= time::Duration::hours(hours);
+        let minutes = rest.whole_minutes();
+        rest -= time::Duration::minutes(minutes);
+        let seconds = rest.as_seconds_f64();
+
+        let mut pieces = [
+            ("weeks", weeks as f64),
+            ("days", days as f64),
+            ("hours", hours as f64),
+            ("minutes", minutes as f64),
+            ("seconds", seconds),
+        ]
+        .into_iter()
+        .filter(|&(_, v)| v != 0.0)
+        .map(|(name, v)| eco_format!("{name}: {v}"))
+        .collect::<EcoVec<_>>();
+
+        if pieces.is_empty() {
+            pieces.push("seconds: 0".into());
+        }
+
+        write!(f, "duration{}", &pretty_array_like(&pieces, false))
+    }
+}
+
+cast! {
+    type Duration: "duration",
+}
@@ -3,18 +3,18 @@ use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use comemo::{Prehashed, Tracked, TrackedMut};
-use ecow::eco_format;
+use ecow::{eco_format, EcoString};
 use once_cell::sync::Lazy;
 
 use super::{
-    cast, Args, CastInfo, Eval, FlowEvent, IntoValue, Route, Scope, Scopes, Tracer,
-    Value, Vm,
+    cast, methods, Args, CastInfo, Eval, FlowEvent, IntoValue, NativeMethod, Plugin,
+    Route, Scope, Scopes, Tracer, Type, Value, Vm,
 };
-use crate::diag::{bail, SourceResult, StrResult};
+use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::file::FileId;
 use crate::model::{DelayedErrors, ElemFunc, Introspector, Locator, Vt};
 use crate::syntax::ast::{self, AstNode, Expr, Ident};
-use crate::syntax::{Span, SyntaxNode};
+use crate::syntax::{Span, Spanned, SyntaxNode};
 use crate::World;
 
 /// An evaluatable function.
@@ -38,6 +38,10 @@ enum Repr {
     Closure(Arc<Prehashed<Closure>>),
     /// A nested function with pre-applied arguments.
     With(Arc<(Func, Args)>),
+    /// A function exported by a WebAssembly plugin.
+    Plugin(Arc<(Plugin, EcoString)>),
+    /// A built-in method of a type, taking the value as its first argument.
+    Method(Type, &'static NativeMethod),
 }
 
 impl Func {
@@ -45,9 +49,11 @@ impl Func {
     pub fn name(&self) -> Option<&str> {
         match &self.repr {
             Repr::Native(native) => Some(native.info.name),
-            Repr::Elem(func) => Some(func.info().name),
+            Repr::Elem(func) => Some(func.name()),
             Repr::Closure(closure) => closure.name.as_deref(),
             Repr::With(arc) => arc.0.name(),
+            Repr::Plugin(arc) => Some(&arc.1),
+            Repr::Method(_, method) => Some(method.name),
         }
     }
 
@@ -55,9 +61,11 @@ impl Func {
     pub fn info(&self) -> Option<&FuncInfo> {
         match &self.repr {
             Repr::Native(native) => Some(&native.info),
-            Repr::Elem(func) => Some(func.info()),
+            Repr::Elem(func) => func.info(),
             Repr::Closure(_) => None,
             Repr::With(arc) => arc.0.info(),
+            Repr::Plugin(_) => None,
+            Repr::Method(..) => None,
         }
     }
 
@@ -115,6 +123,27 @@ impl Func {
                 args.items = arc.1.items.iter().cloned().chain(args.items).collect();
                 arc.0.call_vm(vm, args)
             }
+            Repr::Plugin(arc) => {
+                let span = args.span;
+                let bytes = args.all()?;
+                args.finish()?;
+                let (plugin, name) = &**arc;
+                let fuel = vm.limits.plugin_fuel;
+                Ok(Value::Bytes(plugin.call(name, bytes, fuel, span)?))
+            }
+            Repr::Method(ty, method) => {
+                let span = args.span;
+                let this = args.expect::<Spanned<Value>>("self")?;
+                if this.v.ty() != *ty {
+                    bail!(
+                        this.span,
+                        "expected {}, found {}",
+                        ty.name(),
+                        this.v.type_name()
+                    );
+                }
+                methods::call_native(vm, method, this.v, args, span)
+            }
         }
     }
 
@@ -146,10 +175,29 @@ impl Func {
         Self { repr: Repr::With(Arc::new((self, args))), span }
     }
 
+    /// Create a function that calls the plugin function with the given name.
+    pub fn plugin(plugin: Plugin, name: EcoString) -> Self {
+        Repr::Plugin(Arc::new((plugin, name))).into()
+    }
+
+    /// Create a function that calls a built-in method of a type on its first
+    /// argument.
+    pub(super) fn method(ty: Type, method: &'static NativeMethod) -> Self {
+        Repr::Method(ty, method).into()
+    }
+
+    /// The type and definition of the method, if this is a built-in method.
+    pub fn as_method(&self) -> Option<(Type, &'static NativeMethod)> {
+        match self.repr {
+            Repr::Method(ty, method) => Some((ty, method)),
+            _ => None,
+        }
+    }
+
     /// Extract the element function, if it is one.
     pub fn element(&self) -> Option<ElemFunc> {
-        match self.repr {
-            Repr::Elem(func) => Some(func),
+        match &self.repr {
+            Repr::Elem(func) => Some(func.clone()),
             _ => None,
         }
     }
@@ -164,17 +212,23 @@ impl Func {
                     field
                 )
             }),
-            Repr::Elem(func) => func.info().scope.get(field).ok_or_else(|| {
-                eco_format!(
-                    "function `{}` does not contain field `{}`",
-                    func.name(),
-                    field
-                )
-            }),
+            Repr::Elem(func) => {
+                func.info().and_then(|info| info.scope.get(field)).ok_or_else(|| {
+                    eco_format!(
+                        "function `{}` does not contain field `{}`",
+                        func.name(),
+                        field
+                    )
+                })
+            }
             Repr::Closure(_) => {
                 Err(eco_format!("cannot access fields on user-defined functions"))
             }
             Repr::With(arc) => arc.0.get(field),
+            Repr::Plugin(_) => {
+                Err(eco_format!("cannot access fields on plugin functions"))
+            }
+            Repr::Method(..) => Err(eco_format!("cannot access fields on methods")),
         }
     }
 }
@@ -333,7 +387,7 @@ impl Closure {
         delayed: TrackedMut<DelayedErrors>,
         tracer: TrackedMut<Tracer>,
         depth: usize,
-        mut args: Args,
+        args: Args,
     ) -> SourceResult<Value> {
         let closure = match &this.repr {
             Repr::Closure(closure) => closure,
@@ -359,20 +413,27 @@ impl Closure {
         let mut vm = Vm::new(vt, route, closure.location, scopes);
         vm.depth = depth;
 
+        let result = closure.run(this, &mut vm, args);
+        vm.flush_steps(closure.body.span())?;
+        result
+    }
+
+    /// Bind the arguments and evaluate the closure's body.
+    fn run(&self, this: &Func, vm: &mut Vm, mut args: Args) -> SourceResult<Value> {
         // Provide the closure itself for recursive calls.
-        if let Some(name) = &closure.name {
+        if let Some(name) = &self.name {
             vm.define(name.clone(), Value::Func(this.clone()));
         }
 
         // Parse the arguments according to the parameter list.
         let num_pos_params =
-            closure.params.iter().filter(|p| matches!(p, Param::Pos(_))).count();
+            self.params.iter().filter(|p| matches!(p, Param::Pos(_))).count();
         let num_pos_args = args.to_pos().len();
         let sink_size = num_pos_args.checked_sub(num_pos_params);
 
         let mut sink = None;
         let mut sink_pos_values = None;
-        for p in &closure.params {
+        for p in &self.params {
             match p {
                 Param::Pos(pattern) => match pattern {
                     ast::Pattern::Normal(ast::Expr::Ident(ident)) => {
@@ -380,10 +441,7 @@ impl Closure {
                     }
                     ast::Pattern::Normal(_) => unreachable!(),
                     _ => {
-                        pattern.define(
-                            &mut vm,
-                            args.expect::<Value>("pattern parameter")?,
-                        )?;
+                        pattern.define(vm, args.expect::<Value>("pattern parameter")?)?;
                     }
                 },
                 Param::Sink(ident) => {
@@ -412,8 +470,8 @@ impl Closure {
         args.finish()?;
 
         // Handle control flow.
-        let result = closure.body.eval(&mut vm);
-        match vm.flow {
+        let result = self.body.eval(vm);
+        match vm.flow.take() {
             Some(FlowEvent::Return(_, Some(explicit))) => return Ok(explicit),
             Some(FlowEvent::Return(_, None)) => {}
             Some(flow) => bail!(flow.forbidden()),
@@ -542,9 +600,12 @@ impl<'a> CapturesVisitor<'a> {
             // path is evaluated.
             Some(ast::Expr::Import(expr)) => {
                 self.visit(expr.source().as_untyped());
+                if let Some(new_name) = expr.new_name() {
+                    self.bind(new_name);
+                }
                 if let Some(ast::Imports::Items(items)) = expr.imports() {
                     for item in items {
-                        self.bind(item);
+                        self.bind(item.bound_name());
                     }
                 }
             }
@@ -635,6 +696,8 @@ mod tests {
         // Import.
         test("#import z: x, y", &["z"]);
         test("#import x + y: x, y, z", &["x", "y"]);
+        test("#import z as w; #(w + x)", &["x", "z"]);
+        test("#import z: x as a, y.b; #(a + b + x + y)", &["x", "y", "z"]);
 
         // Blocks.
         test("#{ let x = 1; { let y = 2; y }; x + y }", &["y"]);
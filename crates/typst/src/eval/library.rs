@@ -6,12 +6,11 @@ use comemo::Tracked;
 use ecow::EcoString;
 use std::sync::OnceLock;
 
-use super::{Args, Dynamic, Module, NativeFunc, Value, Vm};
+use super::{Module, NativeFunc, NativeMethod, Type};
 use crate::diag::SourceResult;
 use crate::doc::Document;
 use crate::geom::{Abs, Dir};
 use crate::model::{Content, ElemFunc, Introspector, Label, StyleChain, Styles, Vt};
-use crate::syntax::Span;
 use crate::util::hash128;
 
 /// Definition of Typst's standard library.
@@ -110,14 +109,10 @@ pub struct LangItems {
     pub math_frac: fn(num: Content, denom: Content) -> Content,
     /// A root in math: `√x`, `∛x` or `∜x`.
     pub math_root: fn(index: Option<Content>, radicand: Content) -> Content,
-    /// Dispatch a method on a library value.
-    pub library_method: fn(
-        vm: &mut Vm,
-        dynamic: &Dynamic,
-        method: &str,
-        args: Args,
-        span: Span,
-    ) -> SourceResult<Value>,
+    /// The methods of a type defined by the library.
+    pub library_methods: fn(ty: Type) -> &'static [NativeMethod],
+    /// The constructor of a type, if it has one.
+    pub type_constructor: fn(ty: Type) -> Option<&'static NativeFunc>,
 }
 
 impl Debug for LangItems {
@@ -160,7 +155,8 @@ impl Hash for LangItems {
         self.math_accent.hash(state);
         self.math_frac.hash(state);
         self.math_root.hash(state);
-        (self.library_method as usize).hash(state);
+        (self.library_methods as usize).hash(state);
+        (self.type_constructor as usize).hash(state);
     }
 }
 
@@ -1,280 +1,132 @@
 //! Methods on values.
 
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+
 use ecow::{eco_format, EcoString};
 
-use super::{Args, IntoValue, Str, Value, Vm};
+use super::{Args, Array, Content, Dict, Func, IntoValue, Str, Type, Value, Vm};
 use crate::diag::{At, Hint, SourceResult};
-use crate::eval::{bail, Datetime};
-use crate::geom::{Align, Axes, Color, Dir, Em, GenAlign};
+use crate::eval::{bail, Datetime, Duration};
+use crate::geom::{Abs, Align, Angle, Axes, Color, Dir, Em, GenAlign, Length};
 use crate::model::{Location, Selector};
 use crate::syntax::Span;
+use crate::util::Bytes;
 
-/// Call a method on a value.
-pub fn call(
-    vm: &mut Vm,
-    value: Value,
-    method: &str,
-    mut args: Args,
-    span: Span,
-) -> SourceResult<Value> {
-    let name = value.type_name();
-    let missing = || Err(missing_method(name, method)).at(span);
-
-    let output = match value {
-        Value::Color(color) => match method {
-            "lighten" => color.lighten(args.expect("amount")?).into_value(),
-            "darken" => color.darken(args.expect("amount")?).into_value(),
-            "negate" => color.negate().into_value(),
-            "kind" => match color {
-                Color::Luma(_) => vm.items.luma_func.into_value(),
-                Color::Rgba(_) => vm.items.rgb_func.into_value(),
-                Color::Cmyk(_) => vm.items.cmyk_func.into_value(),
-            },
-            "hex" => color.to_rgba().to_hex().into_value(),
-            "rgba" => color.to_rgba().to_array().into_value(),
-            "cmyk" => match color {
-                Color::Luma(luma) => luma.to_cmyk().to_array().into_value(),
-                Color::Rgba(_) => {
-                    bail!(span, "cannot obtain cmyk values from rgba color")
-                }
-                Color::Cmyk(cmyk) => cmyk.to_array().into_value(),
-            },
-            "luma" => match color {
-                Color::Luma(luma) => luma.0.into_value(),
-                Color::Rgba(_) => {
-                    bail!(span, "cannot obtain the luma value of rgba color")
-                }
-                Color::Cmyk(_) => {
-                    bail!(span, "cannot obtain the luma value of cmyk color")
-                }
-            },
-            _ => return missing(),
-        },
-
-        Value::Str(string) => match method {
-            "len" => string.len().into_value(),
-            "first" => string.first().at(span)?.into_value(),
-            "last" => string.last().at(span)?.into_value(),
-            "at" => {
-                let index = args.expect("index")?;
-                let default = args.named::<EcoString>("default")?;
-                string.at(index, default.as_deref()).at(span)?.into_value()
-            }
-            "slice" => {
-                let start = args.expect("start")?;
-                let mut end = args.eat()?;
-                if end.is_none() {
-                    end = args.named("count")?.map(|c: i64| start + c);
-                }
-                string.slice(start, end).at(span)?.into_value()
-            }
-            "clusters" => string.clusters().into_value(),
-            "codepoints" => string.codepoints().into_value(),
-            "contains" => string.contains(args.expect("pattern")?).into_value(),
-            "starts-with" => string.starts_with(args.expect("pattern")?).into_value(),
-            "ends-with" => string.ends_with(args.expect("pattern")?).into_value(),
-            "find" => string.find(args.expect("pattern")?).into_value(),
-            "position" => string.position(args.expect("pattern")?).into_value(),
-            "match" => string.match_(args.expect("pattern")?).into_value(),
-            "matches" => string.matches(args.expect("pattern")?).into_value(),
-            "replace" => {
-                let pattern = args.expect("pattern")?;
-                let with = args.expect("string or function")?;
-                let count = args.named("count")?;
-                string.replace(vm, pattern, with, count)?.into_value()
-            }
-            "trim" => {
-                let pattern = args.eat()?;
-                let at = args.named("at")?;
-                let repeat = args.named("repeat")?.unwrap_or(true);
-                string.trim(pattern, at, repeat).into_value()
-            }
-            "split" => string.split(args.eat()?).into_value(),
-            _ => return missing(),
-        },
-
-        Value::Content(content) => match method {
-            "func" => content.func().into_value(),
-            "has" => content.has(&args.expect::<EcoString>("field")?).into_value(),
-            "at" => content
-                .at(&args.expect::<EcoString>("field")?, args.named("default")?)
-                .at(span)?,
-            "fields" => content.dict().into_value(),
-            "location" => content
-                .location()
-                .ok_or("this method can only be called on content returned by query(..)")
-                .at(span)?
-                .into_value(),
-            _ => return missing(),
-        },
-
-        Value::Array(array) => match method {
-            "len" => array.len().into_value(),
-            "first" => array.first().at(span)?.clone(),
-            "last" => array.last().at(span)?.clone(),
-            "at" => array
-                .at(args.expect("index")?, args.named("default")?.as_ref())
-                .at(span)?
-                .clone(),
-            "slice" => {
-                let start = args.expect("start")?;
-                let mut end = args.eat()?;
-                if end.is_none() {
-                    end = args.named("count")?.map(|c: i64| start + c);
-                }
-                array.slice(start, end).at(span)?.into_value()
-            }
-            "contains" => array.contains(&args.expect("value")?).into_value(),
-            "find" => array.find(vm, args.expect("function")?)?.into_value(),
-            "position" => array.position(vm, args.expect("function")?)?.into_value(),
-            "filter" => array.filter(vm, args.expect("function")?)?.into_value(),
-            "map" => array.map(vm, args.expect("function")?)?.into_value(),
-            "fold" => {
-                array.fold(vm, args.expect("initial value")?, args.expect("function")?)?
-            }
-            "sum" => array.sum(args.named("default")?, span)?,
-            "product" => array.product(args.named("default")?, span)?,
-            "any" => array.any(vm, args.expect("function")?)?.into_value(),
-            "all" => array.all(vm, args.expect("function")?)?.into_value(),
-            "flatten" => array.flatten().into_value(),
-            "rev" => array.rev().into_value(),
-            "split" => array.split(args.expect("separator")?).into_value(),
-            "join" => {
-                let sep = args.eat()?;
-                let last = args.named("last")?;
-                array.join(sep, last).at(span)?
-            }
-            "sorted" => array.sorted(vm, span, args.named("key")?)?.into_value(),
-            "zip" => array.zip(args.expect("other")?).into_value(),
-            "enumerate" => array.enumerate().into_value(),
-            _ => return missing(),
-        },
+/// Define the built-in methods of a type.
+///
+/// The header names the virtual machine, the value the method is called on
+/// (together with the Rust type it is cast to), the arguments and the span.
+/// Methods that take arguments are marked with `(..)`. Mutating methods follow
+/// after a semicolon and a pattern that binds the value in place.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __native_methods {
+    (
+        |$vm:ident, $this:ident: $ty:ty, $args:ident, $span:ident|
+        $($name:literal $(($dots:tt))? => $body:expr),* $(,)?
+        $(; mut $pat:pat =>
+            $($mut_name:literal $(($mut_dots:tt))? => $mut_body:expr),* $(,)?)?
+    ) => {
+        &[
+            $($crate::eval::NativeMethod {
+                name: $name,
+                takes_args: $crate::eval::native_methods!(@args $($dots)?),
+                func: $crate::eval::MethodFunc::Value(|
+                    #[allow(unused_variables)] $vm,
+                    value,
+                    #[allow(unused_variables)] $args,
+                    $span,
+                | {
+                    let $this: $ty =
+                        $crate::diag::At::at($crate::eval::Value::cast(value), $span)?;
+                    Ok($body)
+                }),
+            },)*
+            $($($crate::eval::NativeMethod {
+                name: $mut_name,
+                takes_args: $crate::eval::native_methods!(@args $($mut_dots)?),
+                func: $crate::eval::MethodFunc::Mut(|
+                    value,
+                    #[allow(unused_variables)] $args,
+                    #[allow(unused_variables)] $span,
+                | {
+                    let $pat = value else { unreachable!() };
+                    Ok($mut_body)
+                }),
+            },)*)?
+        ]
+    };
+    (@args) => { false };
+    (@args ..) => { true };
+}
 
-        Value::Dict(dict) => match method {
-            "len" => dict.len().into_value(),
-            "at" => dict
-                .at(&args.expect::<Str>("key")?, args.named("default")?.as_ref())
-                .at(span)?
-                .clone(),
-            "keys" => dict.keys().into_value(),
-            "values" => dict.values().into_value(),
-            "pairs" => dict.pairs().into_value(),
-            _ => return missing(),
-        },
+#[doc(inline)]
+pub use crate::__native_methods as native_methods;
 
-        Value::Func(func) => match method {
-            "with" => func.with(args.take()).into_value(),
-            "where" => {
-                let fields = args.to_named();
-                args.items.retain(|arg| arg.name.is_none());
-                func.element()
-                    .ok_or("`where()` can only be called on element functions")
-                    .at(span)?
-                    .where_(fields)
-                    .into_value()
-            }
-            _ => return missing(),
-        },
+/// A built-in method of a type.
+#[derive(Debug, Copy, Clone)]
+pub struct NativeMethod {
+    /// The method's name.
+    pub name: &'static str,
+    /// Whether the method takes arguments.
+    pub takes_args: bool,
+    /// The method's implementation.
+    pub func: MethodFunc,
+}
 
-        Value::Length(length) => match method {
-            unit @ ("pt" | "cm" | "mm" | "inches") => {
-                if length.em != Em::zero() {
-                    return Err(eco_format!("cannot convert a length with non-zero em units ({length:?}) to {unit}"))
-                        .hint(eco_format!("use 'length.abs.{unit}()' instead to ignore its em component"))
-                        .at(span);
-                }
-                match unit {
-                    "pt" => length.abs.to_pt().into_value(),
-                    "cm" => length.abs.to_cm().into_value(),
-                    "mm" => length.abs.to_mm().into_value(),
-                    "inches" => length.abs.to_inches().into_value(),
-                    _ => unreachable!(),
-                }
-            }
-            _ => return missing(),
-        },
+/// The implementation of a built-in method.
+#[derive(Debug, Copy, Clone)]
+pub enum MethodFunc {
+    /// A method that takes the value it is called on by value.
+    Value(fn(&mut Vm, Value, &mut Args, Span) -> SourceResult<Value>),
+    /// A method that modifies the value it is called on in place.
+    Mut(fn(&mut Value, &mut Args, Span) -> SourceResult<Value>),
+}
 
-        Value::Angle(angle) => match method {
-            "deg" => angle.to_deg().into_value(),
-            "rad" => angle.to_rad().into_value(),
-            _ => return missing(),
-        },
+impl PartialEq for NativeMethod {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
 
-        Value::Args(args) => match method {
-            "pos" => args.to_pos().into_value(),
-            "named" => args.to_named().into_value(),
-            _ => return missing(),
-        },
+impl Eq for NativeMethod {}
 
-        Value::Dyn(dynamic) => {
-            if let Some(location) = dynamic.downcast::<Location>() {
-                match method {
-                    "page" => vm.vt.introspector.page(*location).into_value(),
-                    "position" => vm.vt.introspector.position(*location).into_value(),
-                    "page-numbering" => vm.vt.introspector.page_numbering(*location),
-                    _ => return missing(),
-                }
-            } else if let Some(selector) = dynamic.downcast::<Selector>() {
-                match method {
-                    "or" => selector.clone().or(args.all::<Selector>()?).into_value(),
-                    "and" => selector.clone().and(args.all::<Selector>()?).into_value(),
-                    "before" => {
-                        let location = args.expect::<Selector>("selector")?;
-                        let inclusive =
-                            args.named_or_find::<bool>("inclusive")?.unwrap_or(true);
-                        selector.clone().before(location, inclusive).into_value()
-                    }
-                    "after" => {
-                        let location = args.expect::<Selector>("selector")?;
-                        let inclusive =
-                            args.named_or_find::<bool>("inclusive")?.unwrap_or(true);
-                        selector.clone().after(location, inclusive).into_value()
-                    }
-                    _ => return missing(),
-                }
-            } else if let Some(&datetime) = dynamic.downcast::<Datetime>() {
-                match method {
-                    "display" => {
-                        datetime.display(args.eat()?).at(args.span)?.into_value()
-                    }
-                    "year" => datetime.year().into_value(),
-                    "month" => datetime.month().into_value(),
-                    "weekday" => datetime.weekday().into_value(),
-                    "day" => datetime.day().into_value(),
-                    "hour" => datetime.hour().into_value(),
-                    "minute" => datetime.minute().into_value(),
-                    "second" => datetime.second().into_value(),
-                    _ => return missing(),
-                }
-            } else if let Some(direction) = dynamic.downcast::<Dir>() {
-                match method {
-                    "axis" => direction.axis().description().into_value(),
-                    "start" => {
-                        GenAlign::from(Align::from(direction.start())).into_value()
-                    }
-                    "end" => GenAlign::from(Align::from(direction.end())).into_value(),
-                    "inv" => direction.inv().into_value(),
-                    _ => return missing(),
-                }
-            } else if let Some(align) = dynamic.downcast::<GenAlign>() {
-                match method {
-                    "axis" => align.axis().description().into_value(),
-                    "inv" => align.inv().into_value(),
-                    _ => return missing(),
-                }
-            } else if let Some(align2d) = dynamic.downcast::<Axes<GenAlign>>() {
-                match method {
-                    "inv" => align2d.map(GenAlign::inv).into_value(),
-                    _ => return missing(),
-                }
-            } else {
-                return (vm.items.library_method)(vm, &dynamic, method, args, span);
-            }
-        }
+impl Hash for NativeMethod {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
 
-        _ => return missing(),
+/// Call a method on a value.
+///
+/// The method is looked up in the scope of the value's type.
+pub fn call(
+    vm: &mut Vm,
+    value: Value,
+    method: &str,
+    args: Args,
+    span: Span,
+) -> SourceResult<Value> {
+    let ty = value.ty();
+    let Some(method) = ty.method(method) else {
+        bail!(span, "{}", missing_method(ty.name(), method));
     };
+    call_native(vm, method, value, args, span)
+}
 
+/// Call a built-in, non-mutating method on a value of the method's type.
+pub(super) fn call_native(
+    vm: &mut Vm,
+    method: &NativeMethod,
+    value: Value,
+    mut args: Args,
+    span: Span,
+) -> SourceResult<Value> {
+    let MethodFunc::Value(func) = method.func else {
+        bail!(span, "method `{}` can only be called on a variable", method.name);
+    };
+    let output = func(vm, value, &mut args, span)?;
     args.finish()?;
     Ok(output)
 }
@@ -286,32 +138,17 @@ pub fn call_mut(
     mut args: Args,
     span: Span,
 ) -> SourceResult<Value> {
-    let name = value.type_name();
-    let missing = || Err(missing_method(name, method)).at(span);
-    let mut output = Value::None;
-
-    match value {
-        Value::Array(array) => match method {
-            "push" => array.push(args.expect("value")?),
-            "pop" => output = array.pop().at(span)?,
-            "insert" => {
-                array.insert(args.expect("index")?, args.expect("value")?).at(span)?
-            }
-            "remove" => output = array.remove(args.expect("index")?).at(span)?,
-            _ => return missing(),
-        },
+    let ty = value.ty();
+    let func = ty.methods().iter().find_map(|m| match m.func {
+        MethodFunc::Mut(func) if m.name == method => Some(func),
+        _ => None,
+    });
 
-        Value::Dict(dict) => match method {
-            "insert" => dict.insert(args.expect::<Str>("key")?, args.expect("value")?),
-            "remove" => {
-                output = dict.remove(&args.expect::<EcoString>("key")?).at(span)?
-            }
-            _ => return missing(),
-        },
-
-        _ => return missing(),
-    }
+    let Some(func) = func else {
+        bail!(span, "{}", missing_method(ty.name(), method));
+    };
 
+    let output = func(value, &mut args, span)?;
     args.finish()?;
     Ok(output)
 }
@@ -360,99 +197,308 @@ fn missing_method(type_name: &str, method: &str) -> String {
     format!("type {type_name} has no method `{method}`")
 }
 
-/// List the available methods for a type and whether they take arguments.
-pub fn methods_on(type_name: &str) -> &[(&'static str, bool)] {
-    match type_name {
-        "color" => &[
-            ("lighten", true),
-            ("darken", true),
-            ("negate", false),
-            ("kind", false),
-            ("hex", false),
-            ("rgba", false),
-            ("cmyk", false),
-            ("luma", false),
-        ],
-        "string" => &[
-            ("len", false),
-            ("at", true),
-            ("clusters", false),
-            ("codepoints", false),
-            ("contains", true),
-            ("ends-with", true),
-            ("find", true),
-            ("first", false),
-            ("last", false),
-            ("match", true),
-            ("matches", true),
-            ("position", true),
-            ("replace", true),
-            ("slice", true),
-            ("split", true),
-            ("starts-with", true),
-            ("trim", true),
-        ],
-        "content" => &[
-            ("func", false),
-            ("has", true),
-            ("at", true),
-            ("fields", false),
-            ("location", false),
-        ],
-        "array" => &[
-            ("all", true),
-            ("any", true),
-            ("at", true),
-            ("contains", true),
-            ("filter", true),
-            ("find", true),
-            ("first", false),
-            ("flatten", false),
-            ("fold", true),
-            ("insert", true),
-            ("split", true),
-            ("join", true),
-            ("last", false),
-            ("len", false),
-            ("map", true),
-            ("pop", false),
-            ("position", true),
-            ("push", true),
-            ("remove", true),
-            ("rev", false),
-            ("slice", true),
-            ("sorted", false),
-            ("enumerate", false),
-            ("zip", true),
-        ],
-        "dictionary" => &[
-            ("at", true),
-            ("insert", true),
-            ("keys", false),
-            ("len", false),
-            ("pairs", false),
-            ("remove", true),
-            ("values", false),
-        ],
-        "function" => &[("where", true), ("with", true)],
-        "length" => &[("pt", false), ("cm", false), ("mm", false), ("inches", false)],
-        "angle" => &[("deg", false), ("rad", false)],
-        "arguments" => &[("named", false), ("pos", false)],
-        "location" => &[("page", false), ("position", false), ("page-numbering", false)],
-        "selector" => &[("or", true), ("and", true), ("before", true), ("after", true)],
-        "direction" => {
-            &[("axis", false), ("start", false), ("end", false), ("inv", false)]
-        }
-        "alignment" => &[("axis", false), ("inv", false)],
-        "2d alignment" => &[("inv", false)],
-        "counter" => &[
-            ("display", true),
-            ("at", true),
-            ("final", true),
-            ("step", true),
-            ("update", true),
-        ],
-        "state" => &[("display", true), ("at", true), ("final", true), ("update", true)],
-        _ => &[],
+/// The built-in methods of a type.
+pub(super) fn methods(ty: Type) -> &'static [NativeMethod] {
+    [
+        (Type::of::<Color>(), COLOR),
+        (Type::of::<Str>(), STR),
+        (Type::of::<Bytes>(), BYTES),
+        (Type::of::<Content>(), CONTENT),
+        (Type::of::<Array>(), ARRAY),
+        (Type::of::<Dict>(), DICT),
+        (Type::of::<Func>(), FUNC),
+        (Type::of::<Length>(), LENGTH),
+        (Type::of::<Angle>(), ANGLE),
+        (Type::of::<Args>(), ARGS),
+        (Type::of::<Location>(), LOCATION),
+        (Type::of::<Selector>(), SELECTOR),
+        (Type::of::<Datetime>(), DATETIME),
+        (Type::of::<Duration>(), DURATION),
+        (Type::of::<Dir>(), DIR),
+        (Type::of::<GenAlign>(), ALIGN),
+        (Type::of::<Axes<GenAlign>>(), ALIGN_2D),
+    ]
+    .into_iter()
+    .find(|&(other, _)| other == ty)
+    .map_or_else(|| (item!(library_methods))(ty), |(_, methods)| methods)
+}
+
+const COLOR: &[NativeMethod] = native_methods! {
+    |vm, color: Color, args, span|
+    "lighten"(..) => color.lighten(args.expect("amount")?).into_value(),
+    "darken"(..) => color.darken(args.expect("amount")?).into_value(),
+    "negate" => color.negate().into_value(),
+    "kind" => match color {
+        Color::Luma(_) => vm.items.luma_func.into_value(),
+        Color::Rgba(_) => vm.items.rgb_func.into_value(),
+        Color::Cmyk(_) => vm.items.cmyk_func.into_value(),
+    },
+    "hex" => color.to_rgba().to_hex().into_value(),
+    "rgba" => color.to_rgba().to_array().into_value(),
+    "cmyk" => match color {
+        Color::Luma(luma) => luma.to_cmyk().to_array().into_value(),
+        Color::Rgba(_) => bail!(span, "cannot obtain cmyk values from rgba color"),
+        Color::Cmyk(cmyk) => cmyk.to_array().into_value(),
+    },
+    "luma" => match color {
+        Color::Luma(luma) => luma.0.into_value(),
+        Color::Rgba(_) => bail!(span, "cannot obtain the luma value of rgba color"),
+        Color::Cmyk(_) => bail!(span, "cannot obtain the luma value of cmyk color"),
+    },
+};
+
+const STR: &[NativeMethod] = native_methods! {
+    |vm, string: Str, args, span|
+    "len" => string.len().into_value(),
+    "first" => string.first().at(span)?.into_value(),
+    "last" => string.last().at(span)?.into_value(),
+    "at"(..) => {
+        let index = args.expect("index")?;
+        let default = args.named::<EcoString>("default")?;
+        string.at(index, default.as_deref()).at(span)?.into_value()
+    },
+    "slice"(..) => {
+        let (start, end) = slice_bounds(args)?;
+        string.slice(start, end).at(span)?.into_value()
+    },
+    "clusters" => string.clusters().into_value(),
+    "codepoints" => string.codepoints().into_value(),
+    "contains"(..) => string.contains(args.expect("pattern")?).into_value(),
+    "starts-with"(..) => string.starts_with(args.expect("pattern")?).into_value(),
+    "ends-with"(..) => string.ends_with(args.expect("pattern")?).into_value(),
+    "find"(..) => string.find(args.expect("pattern")?).into_value(),
+    "position"(..) => string.position(args.expect("pattern")?).into_value(),
+    "match"(..) => string.match_(args.expect("pattern")?).into_value(),
+    "matches"(..) => string.matches(args.expect("pattern")?).into_value(),
+    "replace"(..) => {
+        let pattern = args.expect("pattern")?;
+        let with = args.expect("string or function")?;
+        let count = args.named("count")?;
+        string.replace(vm, pattern, with, count)?.into_value()
+    },
+    "trim"(..) => {
+        let pattern = args.eat()?;
+        let at = args.named("at")?;
+        let repeat = args.named("repeat")?.unwrap_or(true);
+        string.trim(pattern, at, repeat).into_value()
+    },
+    "split"(..) => string.split(args.eat()?).into_value(),
+};
+
+const BYTES: &[NativeMethod] = native_methods! {
+    |vm, bytes: Bytes, args, span|
+    "len" => bytes.len().into_value(),
+    "at"(..) => bytes.at(args.expect("index")?, args.named("default")?).at(span)?,
+    "slice"(..) => {
+        let (start, end) = slice_bounds(args)?;
+        bytes.slice(start, end).at(span)?.into_value()
+    },
+};
+
+const CONTENT: &[NativeMethod] = native_methods! {
+    |vm, content: Content, args, span|
+    "func" => content.func().into_value(),
+    "has"(..) => content.has(&args.expect::<EcoString>("field")?).into_value(),
+    "at"(..) => content
+        .at(&args.expect::<EcoString>("field")?, args.named("default")?)
+        .at(span)?,
+    "fields" => content.dict().into_value(),
+    "location" => content
+        .location()
+        .ok_or("this method can only be called on content returned by query(..)")
+        .at(span)?
+        .into_value(),
+};
+
+const ARRAY: &[NativeMethod] = native_methods! {
+    |vm, array: Array, args, span|
+    "len" => array.len().into_value(),
+    "first" => array.first().at(span)?.clone(),
+    "last" => array.last().at(span)?.clone(),
+    "at"(..) => array
+        .at(args.expect("index")?, args.named("default")?.as_ref())
+        .at(span)?
+        .clone(),
+    "slice"(..) => {
+        let (start, end) = slice_bounds(args)?;
+        array.slice(start, end).at(span)?.into_value()
+    },
+    "contains"(..) => array.contains(&args.expect("value")?).into_value(),
+    "find"(..) => array.find(vm, args.expect("function")?)?.into_value(),
+    "position"(..) => array.position(vm, args.expect("function")?)?.into_value(),
+    "filter"(..) => array.filter(vm, args.expect("function")?)?.into_value(),
+    "map"(..) => array.map(vm, args.expect("function")?)?.into_value(),
+    "fold"(..) => {
+        array.fold(vm, args.expect("initial value")?, args.expect("function")?)?
+    },
+    "sum"(..) => array.sum(args.named("default")?, span)?,
+    "product"(..) => array.product(args.named("default")?, span)?,
+    "any"(..) => array.any(vm, args.expect("function")?)?.into_value(),
+    "all"(..) => array.all(vm, args.expect("function")?)?.into_value(),
+    "flatten" => array.flatten().into_value(),
+    "rev" => array.rev().into_value(),
+    "split"(..) => array.split(args.expect("separator")?).into_value(),
+    "join"(..) => {
+        let sep = args.eat()?;
+        let last = args.named("last")?;
+        array.join(sep, last).at(span)?
+    },
+    "sorted"(..) => array.sorted(vm, span, args.named("key")?)?.into_value(),
+    "zip"(..) => array.zip(args.expect("other")?).into_value(),
+    "enumerate" => array.enumerate().into_value();
+    mut Value::Array(array) =>
+    "push"(..) => {
+        array.push(args.expect("value")?);
+        Value::None
+    },
+    "pop" => array.pop().at(span)?,
+    "insert"(..) => {
+        array.insert(args.expect("index")?, args.expect("value")?).at(span)?;
+        Value::None
+    },
+    "remove"(..) => array.remove(args.expect("index")?).at(span)?,
+};
+
+const DICT: &[NativeMethod] = native_methods! {
+    |vm, dict: Dict, args, span|
+    "len" => dict.len().into_value(),
+    "at"(..) => dict
+        .at(&args.expect::<Str>("key")?, args.named("default")?.as_ref())
+        .at(span)?
+        .clone(),
+    "keys" => dict.keys().into_value(),
+    "values" => dict.values().into_value(),
+    "pairs" => dict.pairs().into_value();
+    mut Value::Dict(dict) =>
+    "insert"(..) => {
+        dict.insert(args.expect::<Str>("key")?, args.expect("value")?);
+        Value::None
+    },
+    "remove"(..) => dict.remove(&args.expect::<EcoString>("key")?).at(span)?,
+};
+
+const FUNC: &[NativeMethod] = native_methods! {
+    |vm, func: Func, args, span|
+    "with"(..) => func.with(args.take()).into_value(),
+    "where"(..) => {
+        let fields = args.to_named();
+        args.items.retain(|arg| arg.name.is_none());
+        func.element()
+            .ok_or("`where()` can only be called on element functions")
+            .at(span)?
+            .where_(fields)
+            .into_value()
+    },
+};
+
+const LENGTH: &[NativeMethod] = native_methods! {
+    |vm, length: Length, args, span|
+    "pt" => absolute(length, "pt", span)?.to_pt().into_value(),
+    "cm" => absolute(length, "cm", span)?.to_cm().into_value(),
+    "mm" => absolute(length, "mm", span)?.to_mm().into_value(),
+    "inches" => absolute(length, "inches", span)?.to_inches().into_value(),
+};
+
+const ANGLE: &[NativeMethod] = native_methods! {
+    |vm, angle: Angle, args, span|
+    "deg" => angle.to_deg().into_value(),
+    "rad" => angle.to_rad().into_value(),
+};
+
+const ARGS: &[NativeMethod] = native_methods! {
+    |vm, arguments: Args, args, span|
+    "pos" => arguments.to_pos().into_value(),
+    "named" => arguments.to_named().into_value(),
+};
+
+const LOCATION: &[NativeMethod] = native_methods! {
+    |vm, location: Location, args, span|
+    "page" => vm.vt.introspector.page(location).into_value(),
+    "position" => vm.vt.introspector.position(location).into_value(),
+    "page-numbering" => vm.vt.introspector.page_numbering(location),
+};
+
+const SELECTOR: &[NativeMethod] = native_methods! {
+    |vm, selector: Selector, args, span|
+    "or"(..) => selector.or(args.all::<Selector>()?).into_value(),
+    "and"(..) => selector.and(args.all::<Selector>()?).into_value(),
+    "before"(..) => {
+        let location = args.expect::<Selector>("selector")?;
+        let inclusive = args.named_or_find::<bool>("inclusive")?.unwrap_or(true);
+        selector.before(location, inclusive).into_value()
+    },
+    "after"(..) => {
+        let location = args.expect::<Selector>("selector")?;
+        let inclusive = args.named_or_find::<bool>("inclusive")?.unwrap_or(true);
+        selector.after(location, inclusive).into_value()
+    },
+    "page"(..) => {
+        let page = args.expect::<NonZeroUsize>("page")?;
+        selector.on_page(page).into_value()
+    },
+};
+
+const DATETIME: &[NativeMethod] = native_methods! {
+    |vm, datetime: Datetime, args, span|
+    "display"(..) => datetime.display(args.eat()?).at(args.span)?.into_value(),
+    "year" => datetime.year().into_value(),
+    "month" => datetime.month().into_value(),
+    "weekday" => datetime.weekday().into_value(),
+    "day" => datetime.day().into_value(),
+    "ordinal" => datetime.ordinal().into_value(),
+    "iso-week" => datetime.iso_week().into_value(),
+    "hour" => datetime.hour().into_value(),
+    "minute" => datetime.minute().into_value(),
+    "second" => datetime.second().into_value(),
+};
+
+const DURATION: &[NativeMethod] = native_methods! {
+    |vm, duration: Duration, args, span|
+    "seconds" => duration.seconds().into_value(),
+    "minutes" => duration.minutes().into_value(),
+    "hours" => duration.hours().into_value(),
+    "days" => duration.days().into_value(),
+    "weeks" => duration.weeks().into_value(),
+};
+
+const DIR: &[NativeMethod] = native_methods! {
+    |vm, direction: Dir, args, span|
+    "axis" => direction.axis().description().into_value(),
+    "start" => GenAlign::from(Align::from(direction.start())).into_value(),
+    "end" => GenAlign::from(Align::from(direction.end())).into_value(),
+    "inv" => direction.inv().into_value(),
+};
+
+const ALIGN: &[NativeMethod] = native_methods! {
+    |vm, align: GenAlign, args, span|
+    "axis" => align.axis().description().into_value(),
+    "inv" => align.inv().into_value(),
+};
+
+const ALIGN_2D: &[NativeMethod] = native_methods! {
+    |vm, align: Axes<GenAlign>, args, span|
+    "inv" => align.map(GenAlign::inv).into_value(),
+};
+
+/// Parse the bounds of a `slice` call: A start index and either an end index
+/// or a `count`.
+fn slice_bounds(args: &mut Args) -> SourceResult<(i64, Option<i64>)> {
+    let start = args.expect("start")?;
+    let mut end = args.eat()?;
+    if end.is_none() {
+        end = args.named("count")?.map(|c: i64| start + c);
+    }
+    Ok((start, end))
+}
+
+/// The absolute part of a length, failing if it has a non-zero em component.
+fn absolute(length: Length, unit: &str, span: Span) -> SourceResult<Abs> {
+    if length.em != Em::zero() {
+        return Err(eco_format!(
+            "cannot convert a length with non-zero em units ({length:?}) to {unit}"
+        ))
+        .hint(eco_format!("use 'length.abs.{unit}()' instead to ignore its em component"))
+        .at(span);
     }
+    Ok(length.abs)
 }
@@ -15,6 +15,8 @@ mod value;
 mod args;
 mod auto;
 mod datetime;
+mod decimal;
+mod duration;
 mod fields;
 mod func;
 mod int;
@@ -22,8 +24,10 @@ mod methods;
 mod module;
 mod none;
 pub mod ops;
+mod plugin;
 mod scope;
 mod symbol;
+mod ty;
 
 #[doc(hidden)]
 pub use {
@@ -43,21 +47,26 @@ pub use self::cast::{
     cast, Cast, CastInfo, FromValue, IntoResult, IntoValue, Never, Reflect, Variadics,
 };
 pub use self::datetime::Datetime;
+pub use self::decimal::{Decimal, RoundingMode};
 pub use self::dict::{dict, Dict};
+pub use self::duration::Duration;
 pub use self::fields::fields_on;
 pub use self::func::{Func, FuncInfo, NativeFunc, Param, ParamInfo};
 pub use self::library::{set_lang_items, LangItems, Library};
-pub use self::methods::methods_on;
+pub use self::methods::{native_methods, MethodFunc, NativeMethod};
 pub use self::module::Module;
 pub use self::none::NoneValue;
+pub use self::plugin::Plugin;
 pub use self::scope::{Scope, Scopes};
 pub use self::str::{format_str, Regex, Str};
 pub use self::symbol::Symbol;
-pub use self::value::{Dynamic, Type, Value};
+pub use self::ty::Type;
+pub use self::value::{Dynamic, NativeType, Value};
 
 use std::collections::HashSet;
 use std::mem;
 use std::path::Path;
+use std::time::Instant;
 
 use comemo::{Track, Tracked, TrackedMut, Validate};
 use ecow::{EcoString, EcoVec};
@@ -74,10 +83,11 @@ use crate::model::{
 };
 use crate::syntax::ast::{self, AstNode};
 use crate::syntax::{parse_code, Source, Span, Spanned, SyntaxKind, SyntaxNode};
-use crate::World;
+use crate::{Limits, World};
 
-const MAX_ITERATIONS: usize = 10_000;
-const MAX_CALL_DEPTH: usize = 64;
+/// How many evaluation steps a VM takes before counting them towards the
+/// compilation's budget.
+const STEP_BATCH: usize = 64;
 
 /// Evaluate a source file and return the resulting module.
 #[comemo::memoize]
@@ -121,6 +131,7 @@ pub fn eval(
 
     // Evaluate the module.
     let result = root.eval(&mut vm);
+    vm.flush_steps(root.span())?;
 
     // Handle control flow.
     if let Some(flow) = vm.flow {
@@ -199,6 +210,11 @@ pub struct Vm<'a> {
     scopes: Scopes<'a>,
     /// The current call depth.
     depth: usize,
+    /// The resource limits of the compilation.
+    limits: Limits,
+    /// The number of evaluation steps that were not yet counted towards the
+    /// compilation's step budget.
+    steps: usize,
     /// A span that is currently traced.
     traced: Option<Span>,
 }
@@ -213,6 +229,7 @@ impl<'a> Vm<'a> {
     ) -> Self {
         let traced = vt.tracer.span(location);
         let items = vt.world.library().items.clone();
+        let limits = vt.world.limits();
         Self {
             vt,
             items,
@@ -221,6 +238,8 @@ impl<'a> Vm<'a> {
             flow: None,
             scopes,
             depth: 0,
+            limits,
+            steps: 0,
             traced,
         }
     }
@@ -235,6 +254,33 @@ impl<'a> Vm<'a> {
         self.location
     }
 
+    /// Count an evaluation step.
+    ///
+    /// Steps are counted towards the compilation's budget in batches to keep
+    /// the tracer's constraints small.
+    fn step(&mut self, span: Span) -> SourceResult<()> {
+        self.steps += 1;
+        if self.steps >= STEP_BATCH {
+            self.flush_steps(span)?;
+        }
+        Ok(())
+    }
+
+    /// Count the pending evaluation steps towards the compilation's budget,
+    /// failing if it or the deadline is exceeded.
+    fn flush_steps(&mut self, span: Span) -> SourceResult<()> {
+        self.vt.tracer.step(mem::take(&mut self.steps));
+        if self.vt.tracer.exceeds(self.limits.steps) {
+            bail!(
+                error!(span, "maximum number of evaluation steps exceeded").into_fatal()
+            );
+        }
+        if self.vt.tracer.past_deadline() {
+            bail!(error!(span, "compilation deadline exceeded").into_fatal());
+        }
+        Ok(())
+    }
+
     /// Define a variable in the current scope.
     #[tracing::instrument(skip_all)]
     pub fn define(&mut self, var: ast::Ident, value: impl IntoValue) {
@@ -319,11 +365,15 @@ impl<'a> Route<'a> {
     }
 }
 
-/// Traces which values existed for an expression at a span.
+/// Traces which values existed for an expression at a span and accounts for
+/// the resources used by a compilation.
 #[derive(Default, Clone)]
 pub struct Tracer {
     span: Option<Span>,
     values: Vec<Value>,
+    warnings: Vec<SourceError>,
+    steps: usize,
+    deadline: Option<Instant>,
 }
 
 impl Tracer {
@@ -332,13 +382,26 @@ impl Tracer {
 
     /// Create a new tracer, possibly with a span under inspection.
     pub fn new(span: Option<Span>) -> Self {
-        Self { span, values: vec![] }
+        Self { span, ..Self::default() }
+    }
+
+    /// Abort the compilation once the given point in time has passed.
+    ///
+    /// The deadline is only checked, but not part of the cache key of memoized
+    /// work. Thus, results from earlier compilations can be reused.
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        Self { deadline: Some(deadline), ..self }
     }
 
     /// Get the traced values.
     pub fn finish(self) -> Vec<Value> {
         self.values
     }
+
+    /// The warnings that were emitted during compilation.
+    pub fn warnings(&self) -> &[SourceError] {
+        &self.warnings
+    }
 }
 
 #[comemo::track]
@@ -358,6 +421,26 @@ impl Tracer {
             self.values.push(v);
         }
     }
+
+    /// Emit a warning that does not abort compilation.
+    pub fn warn(&mut self, warning: SourceError) {
+        self.warnings.push(warning);
+    }
+
+    /// Count evaluation steps towards the compilation's budget.
+    fn step(&mut self, steps: usize) {
+        self.steps = self.steps.saturating_add(steps);
+    }
+
+    /// Whether the compilation took more than `limit` evaluation steps.
+    fn exceeds(&self, limit: usize) -> bool {
+        self.steps > limit
+    }
+
+    /// Whether the compilation's deadline has passed.
+    pub fn past_deadline(&self) -> bool {
+        self.deadline.map_or(false, |deadline| Instant::now() >= deadline)
+    }
 }
 
 /// Evaluate an expression.
@@ -434,6 +517,8 @@ impl Eval for ast::Expr {
     #[tracing::instrument(name = "Expr::eval", skip_all)]
     fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let span = self.span();
+        vm.step(span)?;
+
         let forbidden = |name| {
             error!(span, "{} is only allowed directly in code and content blocks", name)
         };
@@ -1053,6 +1138,25 @@ impl ast::Binary {
         }
 
         let rhs = self.rhs().eval(vm)?;
+
+        // Types used to be represented by their names.
+        let deprecated = match self.op() {
+            ast::BinOp::Add | ast::BinOp::Eq | ast::BinOp::Neq => matches!(
+                (&lhs, &rhs),
+                (Value::Type(_), Value::Str(_)) | (Value::Str(_), Value::Type(_))
+            ),
+            ast::BinOp::In | ast::BinOp::NotIn => {
+                matches!((&lhs, &rhs), (Value::Str(_), Value::Type(_)))
+            }
+            _ => false,
+        };
+
+        if deprecated {
+            let hint = "compare with the type itself or convert it with `str`";
+            let warning = error!(self.span(), "using a type as a string is deprecated");
+            vm.vt.tracer.warn(warning.with_hints([hint.into()]));
+        }
+
         op(lhs, rhs).at(self.span())
     }
 
@@ -1099,8 +1203,8 @@ impl Eval for ast::FuncCall {
     #[tracing::instrument(name = "FuncCall::eval", skip_all)]
     fn eval(&self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let span = self.span();
-        if vm.depth >= MAX_CALL_DEPTH {
-            bail!(span, "maximum function call depth exceeded");
+        if vm.depth >= vm.limits.call_depth {
+            bail!(error!(span, "maximum function call depth exceeded").into_fatal());
         }
 
         let callee = self.callee();
@@ -1123,10 +1227,12 @@ impl Eval for ast::FuncCall {
                 // Prioritize a function's own methods (with, where) over its
                 // fields. This is fine as we define each field of a function,
                 // if it has any.
-                // ('methods_on' will be empty for Symbol and Module - their
-                // method calls always refer to their fields.)
-                if !matches!(target, Value::Symbol(_) | Value::Module(_) | Value::Func(_))
-                    || methods_on(target.type_name()).iter().any(|(m, _)| m == &field)
+                // (Symbol, Module and Type have no methods - their method calls
+                // always refer to their fields.)
+                if !matches!(
+                    target,
+                    Value::Symbol(_) | Value::Module(_) | Value::Func(_) | Value::Type(_)
+                ) || target.ty().methods().iter().any(|m| m.name == field.as_str())
                 {
                     return methods::call_mut(target, &field, args, span).trace(
                         vm.world(),
@@ -1139,8 +1245,10 @@ impl Eval for ast::FuncCall {
                 let target = target.eval(vm)?;
                 let args = args.eval(vm)?;
 
-                if !matches!(target, Value::Symbol(_) | Value::Module(_) | Value::Func(_))
-                    || methods_on(target.type_name()).iter().any(|(m, _)| m == &field)
+                if !matches!(
+                    target,
+                    Value::Symbol(_) | Value::Module(_) | Value::Func(_) | Value::Type(_)
+                ) || target.ty().methods().iter().any(|m| m.name == field.as_str())
                 {
                     return methods::call(vm, target, &field, args, span).trace(
                         vm.world(),
@@ -1157,7 +1265,7 @@ impl Eval for ast::FuncCall {
         // Handle math special cases for non-functions:
         // Combining accent symbols apply themselves while everything else
         // simply displays the arguments verbatim.
-        if in_math && !matches!(callee, Value::Func(_)) {
+        if in_math && !matches!(callee, Value::Func(_) | Value::Type(_)) {
             if let Value::Symbol(sym) = &callee {
                 let c = sym.get();
                 if let Some(accent) = Symbol::combining_accent(c) {
@@ -1550,8 +1658,8 @@ impl Eval for ast::WhileLoop {
                 && !can_diverge(body.as_untyped())
             {
                 bail!(condition.span(), "condition is always true");
-            } else if i >= MAX_ITERATIONS {
-                bail!(self.span(), "loop seems to be infinite");
+            } else if i >= vm.limits.loop_iterations {
+                bail!(error!(self.span(), "loop seems to be infinite").into_fatal());
             }
 
             let value = body.eval(vm)?;
@@ -1668,30 +1776,36 @@ impl Eval for ast::ForLoop {
 }
 
 /// Applies imports from `import` to the current scope.
-fn apply_imports<V: IntoValue>(
+fn apply_imports<V: IntoValue + Clone>(
     imports: Option<ast::Imports>,
+    new_name: Option<ast::Ident>,
     vm: &mut Vm,
     source_value: V,
     name: impl Fn(&V) -> EcoString,
     scope: impl Fn(&V) -> &Scope,
 ) -> SourceResult<()> {
-    match imports {
-        None => {
-            vm.scopes.top.define(name(&source_value), source_value);
+    match new_name {
+        Some(new_name) => vm.define(new_name, source_value.clone()),
+        None if imports.is_none() => {
+            vm.scopes.top.define(name(&source_value), source_value.clone())
         }
+        None => {}
+    }
+
+    match imports {
+        None => {}
         Some(ast::Imports::Wildcard) => {
             for (var, value) in scope(&source_value).iter() {
                 vm.scopes.top.define(var.clone(), value.clone());
             }
         }
-        Some(ast::Imports::Items(idents)) => {
+        Some(ast::Imports::Items(items)) => {
             let mut errors = vec![];
             let scope = scope(&source_value);
-            for ident in idents {
-                if let Some(value) = scope.get(&ident) {
-                    vm.define(ident, value.clone());
-                } else {
-                    errors.push(error!(ident.span(), "unresolved import"));
+            for item in items {
+                match import_item(scope, &item.path()) {
+                    Ok(value) => vm.define(item.bound_name(), value),
+                    Err(error) => errors.push(error),
                 }
             }
             if !errors.is_empty() {
@@ -1703,6 +1817,30 @@ fn apply_imports<V: IntoValue>(
     Ok(())
 }
 
+/// Resolve the value an import item refers to, descending into nested modules
+/// for a path like `a.b.c`.
+fn import_item(scope: &Scope, path: &ast::ImportItemPath) -> Result<Value, SourceError> {
+    let mut resolved: Option<(Value, Span)> = None;
+    for ident in path.segments() {
+        let next = match &resolved {
+            None => scope.get(&ident).cloned(),
+            Some((Value::Module(module), _)) => module.scope().get(&ident).cloned(),
+            Some((Value::Func(func), _)) => {
+                func.info().and_then(|info| info.scope.get(&ident)).cloned()
+            }
+            Some((Value::Type(ty), _)) => ty.scope().get(&ident).cloned(),
+            Some((v, span)) => {
+                return Err(error!(*span, "expected module, found {}", v.type_name()));
+            }
+        };
+        let value = next.ok_or_else(|| error!(ident.span(), "unresolved import"))?;
+        resolved = Some((value, ident.span()));
+    }
+    resolved
+        .map(|(value, _)| value)
+        .ok_or_else(|| error!(path.span(), "unresolved import"))
+}
+
 impl Eval for ast::ModuleImport {
     type Output = Value;
 
@@ -1716,6 +1854,7 @@ impl Eval for ast::ModuleImport {
             }
             apply_imports(
                 self.imports(),
+                self.new_name(),
                 vm,
                 func,
                 |func| func.info().unwrap().name.into(),
@@ -1725,6 +1864,7 @@ impl Eval for ast::ModuleImport {
             let module = import(vm, source, span, true)?;
             apply_imports(
                 self.imports(),
+                self.new_name(),
                 vm,
                 module,
                 |module| module.name().clone(),
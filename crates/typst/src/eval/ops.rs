@@ -5,7 +5,7 @@ use std::fmt::Debug;
 
 use ecow::eco_format;
 
-use super::{format_str, Regex, Value};
+use super::{format_str, Datetime, Duration, Regex, Value};
 use crate::diag::{bail, StrResult};
 use crate::geom::{Axes, Axis, GenAlign, Length, Numeric, PartialStroke, Rel, Smart};
 use Value::*;
@@ -42,6 +42,7 @@ pub fn pos(value: Value) -> StrResult<Value> {
     Ok(match value {
         Int(v) => Int(v),
         Float(v) => Float(v),
+        Decimal(v) => Decimal(v),
         Length(v) => Length(v),
         Angle(v) => Angle(v),
         Ratio(v) => Ratio(v),
@@ -56,11 +57,18 @@ pub fn neg(value: Value) -> StrResult<Value> {
     Ok(match value {
         Int(v) => Int(v.checked_neg().ok_or("value is too large")?),
         Float(v) => Float(-v),
+        Decimal(v) => Decimal(-v),
         Length(v) => Length(-v),
         Angle(v) => Angle(-v),
         Ratio(v) => Ratio(-v),
         Relative(v) => Relative(-v),
         Fraction(v) => Fraction(-v),
+        Dyn(d) => match d.downcast::<Duration>() {
+            Some(&duration) => {
+                Value::dynamic(duration.checked_neg().ok_or("value is too large")?)
+            }
+            None => mismatch!("cannot apply '-' to {}", d),
+        },
         v => mismatch!("cannot apply '-' to {}", v),
     })
 }
@@ -76,6 +84,12 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Float(a), Int(b)) => Float(a + b as f64),
         (Float(a), Float(b)) => Float(a + b),
 
+        (Decimal(a), Decimal(b)) => {
+            Decimal(a.checked_add(b).ok_or("value is too large")?)
+        }
+        (Decimal(a), Int(b)) => add(Decimal(a), Decimal(b.into()))?,
+        (Int(a), Decimal(b)) => add(Decimal(a.into()), Decimal(b))?,
+
         (Angle(a), Angle(b)) => Angle(a + b),
 
         (Length(a), Length(b)) => Length(a + b),
@@ -96,6 +110,8 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Str(a), Str(b)) => Str(a + b),
         (Str(a), Symbol(b)) => Str(format_str!("{a}{b}")),
         (Symbol(a), Str(b)) => Str(format_str!("{a}{b}")),
+        (Str(a), Type(b)) => Str(a + b.name().into()),
+        (Type(a), Str(b)) => Str(super::Str::from(a.name()) + b),
         (Content(a), Content(b)) => Content(a + b),
         (Content(a), Symbol(b)) => Content(a + item!(text)(b.get().into())),
         (Content(a), Str(b)) => Content(a + item!(text)(b.into())),
@@ -114,6 +130,22 @@ pub fn add(lhs: Value, rhs: Value) -> StrResult<Value> {
         }
 
         (Dyn(a), Dyn(b)) => {
+            // Durations can be added to each other and to datetimes.
+            if let Some(&duration) = b.downcast::<Duration>() {
+                if let Some(&other) = a.downcast::<Duration>() {
+                    let sum = other.checked_add(duration);
+                    return Ok(Value::dynamic(sum.ok_or("value is too large")?));
+                } else if let Some(&datetime) = a.downcast::<Datetime>() {
+                    let sum = datetime.checked_add(duration);
+                    return Ok(Value::dynamic(sum.ok_or("datetime is out of range")?));
+                }
+            } else if let (Some(&duration), Some(&datetime)) =
+                (a.downcast::<Duration>(), b.downcast::<Datetime>())
+            {
+                let sum = datetime.checked_add(duration);
+                return Ok(Value::dynamic(sum.ok_or("datetime is out of range")?));
+            }
+
             // 1D alignments can be summed into 2D alignments.
             if let (Some(&a), Some(&b)) =
                 (a.downcast::<GenAlign>(), b.downcast::<GenAlign>())
@@ -143,6 +175,12 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Float(a), Int(b)) => Float(a - b as f64),
         (Float(a), Float(b)) => Float(a - b),
 
+        (Decimal(a), Decimal(b)) => {
+            Decimal(a.checked_sub(b).ok_or("value is too large")?)
+        }
+        (Decimal(a), Int(b)) => sub(Decimal(a), Decimal(b.into()))?,
+        (Int(a), Decimal(b)) => sub(Decimal(a.into()), Decimal(b))?,
+
         (Angle(a), Angle(b)) => Angle(a - b),
 
         (Length(a), Length(b)) => Length(a - b),
@@ -159,6 +197,31 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
 
         (Fraction(a), Fraction(b)) => Fraction(a - b),
 
+        (Dyn(a), Dyn(b)) => {
+            // Durations can be subtracted from each other and from datetimes
+            // and subtracting two datetimes yields a duration.
+            if let Some(&duration) = b.downcast::<Duration>() {
+                if let Some(&other) = a.downcast::<Duration>() {
+                    let diff = other.checked_sub(duration);
+                    return Ok(Value::dynamic(diff.ok_or("value is too large")?));
+                } else if let Some(&datetime) = a.downcast::<Datetime>() {
+                    let diff = datetime.checked_sub(duration);
+                    return Ok(Value::dynamic(diff.ok_or("datetime is out of range")?));
+                }
+            } else if let (Some(&a), Some(&b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Datetime>())
+            {
+                return match a.duration_since(b) {
+                    Some(duration) => Ok(Value::dynamic(duration)),
+                    None => {
+                        Err(eco_format!("cannot subtract {} from {}", b.kind(), a.kind()))
+                    }
+                };
+            }
+
+            mismatch!("cannot subtract {1} from {0}", a, b);
+        }
+
         (a, b) => mismatch!("cannot subtract {1} from {0}", a, b),
     })
 }
@@ -171,6 +234,12 @@ pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Float(a), Int(b)) => Float(a * b as f64),
         (Float(a), Float(b)) => Float(a * b),
 
+        (Decimal(a), Decimal(b)) => {
+            Decimal(a.checked_mul(b).ok_or("value is too large")?)
+        }
+        (Decimal(a), Int(b)) => mul(Decimal(a), Decimal(b.into()))?,
+        (Int(a), Decimal(b)) => mul(Decimal(a.into()), Decimal(b))?,
+
         (Length(a), Int(b)) => Length(a * b as f64),
         (Length(a), Float(b)) => Length(a * b),
         (Length(a), Ratio(b)) => Length(a * b.get()),
@@ -212,6 +281,21 @@ pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Content(a), b @ Int(_)) => Content(a.repeat(b.cast()?)),
         (a @ Int(_), Content(b)) => Content(b.repeat(a.cast()?)),
 
+        (Dyn(a), b @ (Int(_) | Float(_))) => match a.downcast::<Duration>() {
+            Some(&duration) => {
+                let product = duration.checked_mul(b.cast()?);
+                Value::dynamic(product.ok_or("value is too large")?)
+            }
+            None => mismatch!("cannot multiply {} with {}", a, b),
+        },
+        (a @ (Int(_) | Float(_)), Dyn(b)) => match b.downcast::<Duration>() {
+            Some(&duration) => {
+                let product = duration.checked_mul(a.cast()?);
+                Value::dynamic(product.ok_or("value is too large")?)
+            }
+            None => mismatch!("cannot multiply {} with {}", a, b),
+        },
+
         (a, b) => mismatch!("cannot multiply {} with {}", a, b),
     })
 }
@@ -228,6 +312,12 @@ pub fn div(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Float(a), Int(b)) => Float(a / b as f64),
         (Float(a), Float(b)) => Float(a / b),
 
+        (Decimal(a), Decimal(b)) => {
+            Decimal(a.checked_div(b).ok_or("value is too large")?)
+        }
+        (Decimal(a), Int(b)) => div(Decimal(a), Decimal(b.into()))?,
+        (Int(a), Decimal(b)) => div(Decimal(a.into()), Decimal(b))?,
+
         (Length(a), Int(b)) => Length(a / b as f64),
         (Length(a), Float(b)) => Length(a / b),
         (Length(a), Length(b)) => Float(try_div_length(a, b)?),
@@ -252,6 +342,19 @@ pub fn div(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Fraction(a), Float(b)) => Fraction(a / b),
         (Fraction(a), Fraction(b)) => Float(a / b),
 
+        (Dyn(a), b @ (Int(_) | Float(_))) => match a.downcast::<Duration>() {
+            Some(&duration) => {
+                let seconds = duration.seconds() / b.cast::<f64>()?;
+                let quotient = Duration::from_seconds_f64(seconds);
+                Value::dynamic(quotient.ok_or("value is too large")?)
+            }
+            None => mismatch!("cannot divide {} by {}", a, b),
+        },
+        (Dyn(a), Dyn(b)) => match (a.downcast::<Duration>(), b.downcast::<Duration>()) {
+            (Some(a), Some(b)) => Float(a.seconds() / b.seconds()),
+            _ => mismatch!("cannot divide {} by {}", a, b),
+        },
+
         (a, b) => mismatch!("cannot divide {} by {}", a, b),
     })
 }
@@ -261,11 +364,13 @@ fn is_zero(v: &Value) -> bool {
     match *v {
         Int(v) => v == 0,
         Float(v) => v == 0.0,
+        Decimal(v) => v.is_zero(),
         Length(v) => v.is_zero(),
         Angle(v) => v.is_zero(),
         Ratio(v) => v.is_zero(),
         Relative(v) => v.is_zero(),
         Fraction(v) => v.is_zero(),
+        Dyn(ref v) => v.downcast::<Duration>().map_or(false, |d| d.is_zero()),
         _ => false,
     }
 }
@@ -339,6 +444,7 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Bool(a), Bool(b)) => a == b,
         (Int(a), Int(b)) => a == b,
         (Float(a), Float(b)) => a == b,
+        (Decimal(a), Decimal(b)) => a == b,
         (Length(a), Length(b)) => a == b,
         (Angle(a), Angle(b)) => a == b,
         (Ratio(a), Ratio(b)) => a == b,
@@ -347,6 +453,7 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Color(a), Color(b)) => a == b,
         (Symbol(a), Symbol(b)) => a == b,
         (Str(a), Str(b)) => a == b,
+        (Bytes(a), Bytes(b)) => a == b,
         (Label(a), Label(b)) => a == b,
         (Content(a), Content(b)) => a == b,
         (Array(a), Array(b)) => a == b,
@@ -354,16 +461,22 @@ pub fn equal(lhs: &Value, rhs: &Value) -> bool {
         (Func(a), Func(b)) => a == b,
         (Args(a), Args(b)) => a == b,
         (Module(a), Module(b)) => a == b,
+        (Type(a), Type(b)) => a == b,
         (Dyn(a), Dyn(b)) => a == b,
 
         // Some technically different things should compare equal.
         (&Int(a), &Float(b)) => a as f64 == b,
         (&Float(a), &Int(b)) => a == b as f64,
+        (&Int(a), &Decimal(b)) => super::Decimal::from(a) == b,
+        (&Decimal(a), &Int(b)) => a == super::Decimal::from(b),
         (&Length(a), &Relative(b)) => a == b.abs && b.rel.is_zero(),
         (&Ratio(a), &Relative(b)) => a == b.rel && b.abs.is_zero(),
         (&Relative(a), &Length(b)) => a.abs == b && a.rel.is_zero(),
         (&Relative(a), &Ratio(b)) => a.rel == b && a.abs.is_zero(),
 
+        // Types used to be represented by their names.
+        (Type(a), Str(b)) | (Str(b), Type(a)) => a.name() == b.as_str(),
+
         _ => false,
     }
 }
@@ -374,6 +487,7 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
         (Bool(a), Bool(b)) => a.cmp(b),
         (Int(a), Int(b)) => a.cmp(b),
         (Float(a), Float(b)) => try_cmp_values(a, b)?,
+        (Decimal(a), Decimal(b)) => a.cmp(b),
         (Length(a), Length(b)) => try_cmp_values(a, b)?,
         (Angle(a), Angle(b)) => a.cmp(b),
         (Ratio(a), Ratio(b)) => a.cmp(b),
@@ -384,11 +498,27 @@ pub fn compare(lhs: &Value, rhs: &Value) -> StrResult<Ordering> {
         // Some technically different things should be comparable.
         (Int(a), Float(b)) => try_cmp_values(&(*a as f64), b)?,
         (Float(a), Int(b)) => try_cmp_values(a, &(*b as f64))?,
+        (&Int(a), Decimal(b)) => super::Decimal::from(a).cmp(b),
+        (Decimal(a), &Int(b)) => a.cmp(&b.into()),
         (Length(a), Relative(b)) if b.rel.is_zero() => try_cmp_values(a, &b.abs)?,
         (Ratio(a), Relative(b)) if b.abs.is_zero() => a.cmp(&b.rel),
         (Relative(a), Length(b)) if a.rel.is_zero() => try_cmp_values(&a.abs, b)?,
         (Relative(a), Ratio(b)) if a.abs.is_zero() => a.rel.cmp(b),
 
+        (Dyn(a), Dyn(b)) => {
+            if let (Some(a), Some(b)) =
+                (a.downcast::<Datetime>(), b.downcast::<Datetime>())
+            {
+                try_cmp_values(a, b)?
+            } else if let (Some(a), Some(b)) =
+                (a.downcast::<Duration>(), b.downcast::<Duration>())
+            {
+                a.cmp(b)
+            } else {
+                mismatch!("cannot compare {} and {}", lhs, rhs);
+            }
+        }
+
         _ => mismatch!("cannot compare {} and {}", lhs, rhs),
     })
 }
@@ -424,6 +554,7 @@ pub fn contains(lhs: &Value, rhs: &Value) -> Option<bool> {
         (Dyn(a), Str(b)) => a.downcast::<Regex>().map(|regex| regex.is_match(b)),
         (Str(a), Dict(b)) => Some(b.contains(a)),
         (a, Array(b)) => Some(b.contains(a)),
+        (Str(a), Type(b)) => Some(b.name().contains(a.as_str())),
         _ => Option::None,
     }
 }
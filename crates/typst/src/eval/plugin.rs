@@ -0,0 +1,257 @@
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use ecow::{eco_format, EcoString};
+use wasmi::core::{Trap, TrapCode};
+use wasmi::{AsContext, AsContextMut, Caller, Engine, Linker, Module, StoreLimits};
+
+use super::{Func, Scope};
+use crate::diag::{bail, error, At, SourceResult, StrResult};
+use crate::syntax::Span;
+use crate::util::Bytes;
+
+/// The maximum size of a plugin's linear memory in bytes.
+const MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+/// The name of the module from which plugins import host functions.
+const HOST_MODULE: &str = "typst_env";
+
+/// A sandboxed WebAssembly plugin.
+///
+/// Plugins communicate with Typst through byte buffers: An exported function
+/// receives the lengths of its arguments as `i32` parameters and can copy the
+/// arguments into its memory by calling the imported
+/// `wasm_minimal_protocol_write_args_to_buffer` function. It then reports its
+/// output through `wasm_minimal_protocol_send_result_to_host` and returns `0`
+/// on success or `1` if the output is an error message.
+///
+/// Each call runs in a fresh instance of the module, so plugin functions are
+/// pure and their results can safely be cached.
+#[derive(Clone)]
+pub struct Plugin(Arc<Repr>);
+
+/// The internal representation of a plugin.
+struct Repr {
+    /// The raw WebAssembly bytes the plugin was created from.
+    bytes: Bytes,
+    /// The engine the module was compiled with.
+    engine: Engine,
+    /// The compiled module.
+    module: Module,
+    /// The exported functions and their number of parameters.
+    functions: Vec<(EcoString, usize)>,
+}
+
+/// The data owned by the store of a single plugin call.
+struct StoreData {
+    /// The concatenated arguments of the call.
+    args: Vec<u8>,
+    /// The output written by the plugin.
+    output: Vec<u8>,
+    /// Limits for the growable resources of the instance.
+    limits: StoreLimits,
+}
+
+impl Plugin {
+    /// Compile and validate a plugin from WebAssembly bytes.
+    pub fn new(bytes: Bytes) -> StrResult<Self> {
+        let mut config = wasmi::Config::default();
+        config.consume_fuel(true);
+
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, bytes.as_slice())
+            .map_err(|err| eco_format!("failed to load WebAssembly module ({err})"))?;
+
+        for import in module.imports() {
+            if import.module() != HOST_MODULE || !is_host_function(import.name()) {
+                bail!(
+                    "plugin imports unsupported function `{}::{}`",
+                    import.module(),
+                    import.name()
+                );
+            }
+        }
+
+        let mut functions = vec![];
+        for export in module.exports() {
+            let wasmi::ExternType::Func(ty) = export.ty() else { continue };
+            if ty.params().iter().all(|&ty| ty == wasmi::core::ValueType::I32)
+                && ty.results() == [wasmi::core::ValueType::I32]
+            {
+                functions.push((export.name().into(), ty.params().len()));
+            }
+        }
+
+        Ok(Self(Arc::new(Repr { bytes, engine, module, functions })))
+    }
+
+    /// The names of the functions the plugin exports.
+    pub fn functions(&self) -> impl Iterator<Item = &EcoString> {
+        self.0.functions.iter().map(|(name, _)| name)
+    }
+
+    /// A scope with a function for each function the plugin exports.
+    pub fn scope(&self) -> Scope {
+        let mut scope = Scope::new();
+        for name in self.functions() {
+            scope.define(name.clone(), Func::plugin(self.clone(), name.clone()));
+        }
+        scope
+    }
+
+    /// Call the exported function with the given name, allowing it to consume
+    /// at most the given amount of fuel.
+    pub fn call(
+        &self,
+        name: &str,
+        args: Vec<Bytes>,
+        fuel: u64,
+        span: Span,
+    ) -> SourceResult<Bytes> {
+        let Some(output) = call(self, name, &args, fuel).at(span)? else {
+            // Running out of fuel exceeds a resource limit, so the error must
+            // not be catchable from within the document.
+            let message = eco_format!("plugin function `{name}` ran out of fuel");
+            bail!(error!(span, "{message}").into_fatal());
+        };
+        Ok(output)
+    }
+}
+
+/// Call a plugin function in a fresh instance of the plugin's module.
+///
+/// Returns `None` if the function ran out of fuel.
+#[comemo::memoize]
+fn call(
+    plugin: &Plugin,
+    name: &str,
+    args: &[Bytes],
+    fuel: u64,
+) -> StrResult<Option<Bytes>> {
+    let repr = &plugin.0;
+    let Some(&(_, count)) = repr.functions.iter().find(|(func, _)| func == name) else {
+        bail!("plugin does not contain a function called `{name}`");
+    };
+
+    if count != args.len() {
+        bail!(
+            "plugin function `{name}` takes {count} argument{}, but {} were given",
+            if count == 1 { "" } else { "s" },
+            args.len(),
+        );
+    }
+
+    let data = StoreData {
+        args: args.iter().flat_map(|arg| arg.iter().copied()).collect(),
+        output: vec![],
+        limits: wasmi::StoreLimitsBuilder::new().memory_size(MEMORY_LIMIT).build(),
+    };
+
+    let mut store = wasmi::Store::new(&repr.engine, data);
+    store.limiter(|data| &mut data.limits);
+    store.add_fuel(fuel).map_err(|err| eco_format!("{err}"))?;
+
+    let mut linker = Linker::new(&repr.engine);
+    linker
+        .func_wrap(HOST_MODULE, "wasm_minimal_protocol_write_args_to_buffer", write_args)
+        .and_then(|linker| {
+            linker.func_wrap(
+                HOST_MODULE,
+                "wasm_minimal_protocol_send_result_to_host",
+                send_result,
+            )
+        })
+        .map_err(|err| eco_format!("{err}"))?;
+
+    let instance = linker
+        .instantiate(&mut store, &repr.module)
+        .and_then(|pre| pre.start(&mut store))
+        .map_err(|err| eco_format!("failed to instantiate plugin ({err})"))?;
+
+    let func = instance.get_func(&store, name).ok_or_else(|| {
+        eco_format!("plugin does not contain a function called `{name}`")
+    })?;
+
+    let lengths = args
+        .iter()
+        .map(|arg| i32::try_from(arg.len()).map(wasmi::Value::I32))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "plugin argument is too large")?;
+
+    let mut code = [wasmi::Value::I32(-1)];
+    if let Err(err) = func.call(&mut store, &lengths, &mut code) {
+        if matches!(
+            &err,
+            wasmi::Error::Trap(trap) if matches!(trap.trap_code(), Some(TrapCode::OutOfFuel))
+        ) {
+            return Ok(None);
+        }
+        bail!("plugin function `{name}` panicked: {err}");
+    }
+
+    let output = std::mem::take(&mut store.data_mut().output);
+    match code[0].i32() {
+        Some(0) => Ok(Some(output.into())),
+        Some(1) => match std::str::from_utf8(&output) {
+            Ok(message) => bail!("plugin function `{name}` errored with: {message}"),
+            Err(_) => bail!("plugin function `{name}` errored with an invalid message"),
+        },
+        _ => bail!("plugin function `{name}` did not respect the protocol"),
+    }
+}
+
+/// Whether the name refers to a function provided by the host.
+fn is_host_function(name: &str) -> bool {
+    matches!(
+        name,
+        "wasm_minimal_protocol_write_args_to_buffer"
+            | "wasm_minimal_protocol_send_result_to_host"
+    )
+}
+
+/// Copy the arguments of the current call into the plugin's memory.
+fn write_args(mut caller: Caller<StoreData>, ptr: u32) -> Result<(), Trap> {
+    let memory = memory(&caller)?;
+    let args = std::mem::take(&mut caller.data_mut().args);
+    let result = memory.write(&mut caller, ptr as usize, &args);
+    caller.data_mut().args = args;
+    result.map_err(|_| Trap::new("plugin wrote arguments out of bounds"))
+}
+
+/// Copy the output of the current call out of the plugin's memory.
+fn send_result(mut caller: Caller<StoreData>, ptr: u32, len: u32) -> Result<(), Trap> {
+    let memory = memory(&caller)?;
+    let mut output = vec![0; len as usize];
+    memory
+        .read(caller.as_context(), ptr as usize, &mut output)
+        .map_err(|_| Trap::new("plugin sent result out of bounds"))?;
+    caller.as_context_mut().data_mut().output = output;
+    Ok(())
+}
+
+/// Find the exported memory of the calling plugin.
+fn memory(caller: &Caller<StoreData>) -> Result<wasmi::Memory, Trap> {
+    caller
+        .get_export("memory")
+        .and_then(|export| export.into_memory())
+        .ok_or_else(|| Trap::new("plugin does not export its memory"))
+}
+
+impl Debug for Plugin {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("plugin(..)")
+    }
+}
+
+impl PartialEq for Plugin {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.bytes == other.0.bytes
+    }
+}
+
+impl Hash for Plugin {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.bytes.hash(state);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Mutex;
+
+use ecow::eco_format;
+use once_cell::sync::Lazy;
+
+use super::{
+    methods, Func, FuncInfo, MethodFunc, NativeMethod, NativeType, Scope, Value,
+};
+use crate::diag::StrResult;
+
+/// A type of values.
+///
+/// Types are first-class values: They can be compared, called to construct
+/// a value of the type (if the type has a constructor), and hold the type's
+/// methods and associated functions in their scope. This makes it possible to
+/// write `{type(1) == int}`, `{int("3")}`, or `{str.len("hi")}`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Type(&'static str);
+
+impl Type {
+    /// The type of the native Rust type `T`.
+    pub fn of<T: NativeType>() -> Self {
+        Self(T::TYPE_NAME)
+    }
+
+    /// The type with the given name.
+    pub(super) fn named(name: &'static str) -> Self {
+        Self(name)
+    }
+
+    /// The name of the type.
+    pub fn name(self) -> &'static str {
+        self.0
+    }
+
+    /// The constructor function of the type.
+    pub fn constructor(self) -> StrResult<Func> {
+        (item!(type_constructor))(self)
+            .map(Func::from)
+            .ok_or_else(|| eco_format!("type {} does not have a constructor", self.0))
+    }
+
+    /// Details about the type's constructor, if it has one.
+    pub fn constructor_info(self) -> Option<&'static FuncInfo> {
+        (item!(type_constructor))(self).map(|native| &*native.info)
+    }
+
+    /// The scope of the type.
+    ///
+    /// Contains the definitions in the scope of the type's constructor and a
+    /// function for each non-mutating method of the type.
+    pub fn scope(self) -> &'static Scope {
+        /// Caches the scopes so that they are only built once per type.
+        static SCOPES: Lazy<Mutex<HashMap<&'static str, &'static Scope>>> =
+            Lazy::new(Default::default);
+
+        let mut scopes = SCOPES.lock().unwrap();
+        *scopes.entry(self.0).or_insert_with(|| {
+            let mut scope = self
+                .constructor_info()
+                .map(|info| info.scope.clone())
+                .unwrap_or_default();
+
+            for method in self.methods() {
+                if let MethodFunc::Value(_) = method.func {
+                    scope.define(method.name, Func::method(self, method));
+                }
+            }
+
+            Box::leak(Box::new(scope))
+        })
+    }
+
+    /// The built-in methods of the type, including the mutating ones.
+    pub fn methods(self) -> &'static [NativeMethod] {
+        methods::methods(self)
+    }
+
+    /// The non-mutating method of the type with the given name, if any.
+    ///
+    /// The method is looked up in the type's scope, so that a definition in
+    /// the constructor's scope takes precedence over a method of the same name.
+    pub fn method(self, name: &str) -> Option<&'static NativeMethod> {
+        match self.scope().get(name) {
+            Some(Value::Func(func)) => match func.as_method() {
+                Some((ty, method)) if ty == self => Some(method),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Get a field from this type's scope, if possible.
+    pub fn field(self, field: &str) -> StrResult<&'static Value> {
+        self.scope().get(field).ok_or_else(|| {
+            eco_format!("type {} does not contain field `{}`", self.0, field)
+        })
+    }
+}
+
+impl Debug for Type {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.0)
+    }
+}
@@ -8,8 +8,8 @@ use ecow::eco_format;
 use siphasher::sip128::{Hasher128, SipHasher13};
 
 use super::{
-    cast, fields, format_str, ops, Args, Array, CastInfo, Content, Dict, FromValue, Func,
-    IntoValue, Module, Reflect, Str, Symbol,
+    cast, fields, format_str, ops, Args, Array, CastInfo, Content, Decimal, Dict,
+    FromValue, Func, IntoValue, Module, Reflect, Str, Symbol, Type,
 };
 use crate::diag::StrResult;
 use crate::geom::{Abs, Angle, Color, Em, Fr, Length, Ratio, Rel};
@@ -31,6 +31,8 @@ pub enum Value {
     Int(i64),
     /// A floating-point number: `1.2`, `10e-4`.
     Float(f64),
+    /// A decimal number with exact base-10 arithmetic: `decimal("1.20")`.
+    Decimal(Decimal),
     /// A length: `12pt`, `3cm`, `1.5em`, `1em - 2pt`.
     Length(Length),
     /// An angle: `1.5rad`, `90deg`.
@@ -65,6 +67,8 @@ pub enum Value {
     Args(Args),
     /// A module.
     Module(Module),
+    /// A type: `int`, `str`.
+    Type(Type),
     /// A dynamic value.
     Dyn(Dynamic),
 }
@@ -73,7 +77,7 @@ impl Value {
     /// Create a new dynamic value.
     pub fn dynamic<T>(any: T) -> Self
     where
-        T: Type + Debug + PartialEq + Hash + Sync + Send + 'static,
+        T: NativeType + Debug + PartialEq + Hash + Sync + Send + 'static,
     {
         Self::Dyn(Dynamic::new(any))
     }
@@ -98,6 +102,7 @@ impl Value {
             Self::Bool(_) => bool::TYPE_NAME,
             Self::Int(_) => i64::TYPE_NAME,
             Self::Float(_) => f64::TYPE_NAME,
+            Self::Decimal(_) => Decimal::TYPE_NAME,
             Self::Length(_) => Length::TYPE_NAME,
             Self::Angle(_) => Angle::TYPE_NAME,
             Self::Ratio(_) => Ratio::TYPE_NAME,
@@ -115,10 +120,16 @@ impl Value {
             Self::Func(_) => Func::TYPE_NAME,
             Self::Args(_) => Args::TYPE_NAME,
             Self::Module(_) => Module::TYPE_NAME,
+            Self::Type(_) => Type::TYPE_NAME,
             Self::Dyn(v) => v.type_name(),
         }
     }
 
+    /// The type of the stored value.
+    pub fn ty(&self) -> Type {
+        Type::named(self.type_name())
+    }
+
     /// Try to cast the value into a specific type.
     pub fn cast<T: FromValue>(self) -> StrResult<T> {
         T::from_value(self)
@@ -132,6 +143,7 @@ impl Value {
             Self::Content(content) => content.at(field, None),
             Self::Module(module) => module.get(field).cloned(),
             Self::Func(func) => func.get(field).cloned(),
+            Self::Type(ty) => ty.field(field).cloned(),
             _ => fields::field(self, field),
         }
     }
@@ -156,11 +168,13 @@ impl Value {
             Self::None => Content::empty(),
             Self::Int(v) => item!(text)(eco_format!("{}", v)),
             Self::Float(v) => item!(text)(eco_format!("{}", v)),
+            Self::Decimal(v) => item!(text)(eco_format!("{}", v)),
             Self::Str(v) => item!(text)(v.into()),
             Self::Symbol(v) => item!(text)(v.get().into()),
             Self::Content(v) => v,
             Self::Func(_) => Content::empty(),
             Self::Module(module) => module.content(),
+            Self::Type(ty) => item!(text)(ty.name().into()),
             _ => item!(raw)(self.repr().into(), Some("typc".into()), false),
         }
     }
@@ -169,6 +183,7 @@ impl Value {
     pub fn docs(&self) -> Option<&'static str> {
         match self {
             Self::Func(func) => func.info().map(|info| info.docs),
+            Self::Type(ty) => ty.constructor_info().map(|info| info.docs),
             _ => None,
         }
     }
@@ -182,6 +197,7 @@ impl Debug for Value {
             Self::Bool(v) => Debug::fmt(v, f),
             Self::Int(v) => Debug::fmt(v, f),
             Self::Float(v) => Debug::fmt(v, f),
+            Self::Decimal(v) => Debug::fmt(v, f),
             Self::Length(v) => Debug::fmt(v, f),
             Self::Angle(v) => Debug::fmt(v, f),
             Self::Ratio(v) => Debug::fmt(v, f),
@@ -199,6 +215,7 @@ impl Debug for Value {
             Self::Func(v) => Debug::fmt(v, f),
             Self::Args(v) => Debug::fmt(v, f),
             Self::Module(v) => Debug::fmt(v, f),
+            Self::Type(v) => Debug::fmt(v, f),
             Self::Dyn(v) => Debug::fmt(v, f),
         }
     }
@@ -225,6 +242,7 @@ impl Hash for Value {
             Self::Bool(v) => v.hash(state),
             Self::Int(v) => v.hash(state),
             Self::Float(v) => v.to_bits().hash(state),
+            Self::Decimal(v) => v.hash(state),
             Self::Length(v) => v.hash(state),
             Self::Angle(v) => v.hash(state),
             Self::Ratio(v) => v.hash(state),
@@ -242,6 +260,7 @@ impl Hash for Value {
             Self::Func(v) => v.hash(state),
             Self::Args(v) => v.hash(state),
             Self::Module(v) => v.hash(state),
+            Self::Type(v) => v.hash(state),
             Self::Dyn(v) => v.hash(state),
         }
     }
@@ -256,18 +275,18 @@ impl Dynamic {
     /// Create a new instance from any value that satisfies the required bounds.
     pub fn new<T>(any: T) -> Self
     where
-        T: Type + Debug + PartialEq + Hash + Sync + Send + 'static,
+        T: NativeType + Debug + PartialEq + Hash + Sync + Send + 'static,
     {
         Self(Arc::new(any))
     }
 
     /// Whether the wrapped type is `T`.
-    pub fn is<T: Type + 'static>(&self) -> bool {
+    pub fn is<T: NativeType + 'static>(&self) -> bool {
         (*self.0).as_any().is::<T>()
     }
 
     /// Try to downcast to a reference to a specific type.
-    pub fn downcast<T: Type + 'static>(&self) -> Option<&T> {
+    pub fn downcast<T: NativeType + 'static>(&self) -> Option<&T> {
         (*self.0).as_any().downcast_ref()
     }
 
@@ -303,7 +322,7 @@ trait Bounds: Debug + Sync + Send + 'static {
 
 impl<T> Bounds for T
 where
-    T: Type + Debug + PartialEq + Hash + Sync + Send + 'static,
+    T: NativeType + Debug + PartialEq + Hash + Sync + Send + 'static,
 {
     fn as_any(&self) -> &dyn Any {
         self
@@ -336,7 +355,7 @@ impl Hash for dyn Bounds {
 }
 
 /// The type of a value.
-pub trait Type {
+pub trait NativeType {
     /// The name of the type.
     const TYPE_NAME: &'static str;
 }
@@ -347,7 +366,7 @@ macro_rules! primitive {
         $ty:ty: $name:literal, $variant:ident
         $(, $other:ident$(($binding:ident))? => $out:expr)*
     ) => {
-        impl Type for $ty {
+        impl NativeType for $ty {
             const TYPE_NAME: &'static str = $name;
         }
 
@@ -390,6 +409,7 @@ macro_rules! primitive {
 primitive! { bool: "boolean", Bool }
 primitive! { i64: "integer", Int }
 primitive! { f64: "float", Float, Int(v) => v as f64 }
+primitive! { Decimal: "decimal", Decimal }
 primitive! { Length: "length", Length }
 primitive! { Angle: "angle", Angle }
 primitive! { Ratio: "ratio", Ratio }
@@ -417,9 +437,10 @@ primitive! { Content: "content",
 primitive! { Styles: "styles", Styles }
 primitive! { Array: "array", Array }
 primitive! { Dict: "dictionary", Dict }
-primitive! { Func: "function", Func }
+primitive! { Func: "function", Func, Type(ty) => ty.constructor()? }
 primitive! { Args: "arguments", Args }
 primitive! { Module: "module", Module }
+primitive! { Type: "type", Type }
 
 #[cfg(test)]
 mod tests {
@@ -1,9 +1,10 @@
 use std::collections::BTreeMap;
+use std::fmt::Write;
 
 use ecow::{eco_format, EcoString};
 use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, UnicodeCmap};
 use pdf_writer::{Filter, Finish, Name, Rect, Str};
-use ttf_parser::{name_id, GlyphId, Tag};
+use ttf_parser::{name_id, GlyphId, OutlineBuilder, Tag};
 use unicode_general_category::GeneralCategory;
 
 use super::{deflate, EmExt, PdfContext, RefExt};
@@ -32,10 +33,15 @@ pub fn write_fonts(ctx: &mut PdfContext) {
         let metrics = font.metrics();
         let ttf = font.ttf();
 
-        let postscript_name = font
+        let mut postscript_name = font
             .find_name(name_id::POST_SCRIPT_NAME)
             .unwrap_or_else(|| "unknown".to_string());
 
+        // Variable font instances get a distinct name per set of axis values.
+        for (tag, value) in font.variations() {
+            write!(postscript_name, "-{tag}{value}").ok();
+        }
+
         let base_font = eco_format!("ABCDEF+{}", postscript_name);
         let base_font = Name(base_font.as_bytes());
 
@@ -48,14 +54,16 @@ pub fn write_fonts(ctx: &mut PdfContext) {
             .to_unicode(cmap_ref);
 
         // Check for the presence of CFF outlines to select the correct
-        // CID-Font subtype.
+        // CID-Font subtype. Instances of variable fonts are always embedded
+        // with TrueType outlines because PDF doesn't support variations.
+        let instanced = !font.variations().is_empty();
         let subtype = match ttf
             .raw_face()
             .table(Tag::from_bytes(b"CFF "))
             .or(ttf.raw_face().table(Tag::from_bytes(b"CFF2")))
         {
-            Some(_) => CidFontType::Type0,
-            None => CidFontType::Type2,
+            Some(_) if !instanced => CidFontType::Type0,
+            _ => CidFontType::Type2,
         };
 
         // Write the CID font referencing the font descriptor.
@@ -140,7 +148,11 @@ pub fn write_fonts(ctx: &mut PdfContext) {
 
         // Subset and write the font's bytes.
         let glyphs: Vec<_> = glyph_set.keys().copied().collect();
-        let data = subset_font(font, &glyphs);
+        let data = if instanced {
+            instance_font(font, &glyphs)
+        } else {
+            subset_font(font, &glyphs)
+        };
         let mut stream = ctx.writer.stream(data_ref, &data);
         stream.filter(Filter::FlateDecode);
 
@@ -162,6 +174,270 @@ fn subset_font(font: &Font, glyphs: &[u16]) -> Bytes {
     deflate(data).into()
 }
 
+/// Create a TrueType font containing the outlines and advances of a variable
+/// font instance for the given glyphs.
+///
+/// Glyph ids stay the same as in the original font, but glyphs that are not in
+/// `glyphs` (which must be sorted) are left empty.
+///
+/// The result is only meant for embedding into the PDF, which references
+/// glyphs by id and describes the font in its own dictionaries. Therefore, it
+/// only contains the tables needed to render glyphs and lacks `cmap`, `OS/2`
+/// and `name`. Variable CFF2 outlines are converted to TrueType outlines by
+/// approximating each cubic curve with four quadratic ones, which may deviate
+/// slightly from the original shape.
+#[comemo::memoize]
+fn instance_font(font: &Font, glyphs: &[u16]) -> Bytes {
+    let ttf = font.ttf();
+    let num_glyphs = ttf.number_of_glyphs();
+
+    let mut glyf = vec![];
+    let mut loca = vec![];
+    let mut hmtx = vec![];
+    let mut max_points = 0;
+    let mut max_contours = 0;
+
+    for id in 0..num_glyphs {
+        loca.extend((glyf.len() as u32).to_be_bytes());
+
+        let mut lsb = 0;
+        if glyphs.binary_search(&id).is_ok() {
+            let mut builder = GlyfBuilder::default();
+            if ttf.outline_glyph(GlyphId(id), &mut builder).is_some() {
+                let (points, contours, x_min) = builder.write(&mut glyf);
+                max_points = max_points.max(points);
+                max_contours = max_contours.max(contours);
+                lsb = x_min;
+            }
+        }
+
+        let advance = ttf.glyph_hor_advance(GlyphId(id)).unwrap_or(0);
+        hmtx.extend(advance.to_be_bytes());
+        hmtx.extend(lsb.to_be_bytes());
+    }
+
+    loca.extend((glyf.len() as u32).to_be_bytes());
+
+    // Reuse the original header tables, but switch to long loca offsets and
+    // one horizontal metric per glyph.
+    let raw = ttf.raw_face();
+    let mut head = raw.table(Tag::from_bytes(b"head")).unwrap_or_default().to_vec();
+    head.resize(54, 0);
+    head[8..12].copy_from_slice(&[0; 4]);
+    head[50..52].copy_from_slice(&1u16.to_be_bytes());
+
+    let mut hhea = raw.table(Tag::from_bytes(b"hhea")).unwrap_or_default().to_vec();
+    hhea.resize(36, 0);
+    hhea[34..36].copy_from_slice(&num_glyphs.to_be_bytes());
+
+    let mut maxp = vec![];
+    maxp.extend(0x00010000u32.to_be_bytes());
+    maxp.extend(num_glyphs.to_be_bytes());
+    maxp.extend(max_points.to_be_bytes());
+    maxp.extend(max_contours.to_be_bytes());
+    maxp.extend(0u16.to_be_bytes()); // maxCompositePoints
+    maxp.extend(0u16.to_be_bytes()); // maxCompositeContours
+    maxp.extend(2u16.to_be_bytes()); // maxZones
+    maxp.resize(32, 0);
+
+    let mut post = vec![];
+    post.extend(0x00030000u32.to_be_bytes());
+    post.resize(32, 0);
+
+    let data = write_sfnt(vec![
+        (*b"glyf", glyf),
+        (*b"head", head),
+        (*b"hhea", hhea),
+        (*b"hmtx", hmtx),
+        (*b"loca", loca),
+        (*b"maxp", maxp),
+        (*b"post", post),
+    ]);
+
+    deflate(&data).into()
+}
+
+/// Assemble an OpenType font file from tables sorted by tag.
+fn write_sfnt(tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    let count = tables.len() as u16;
+    let entry_selector = 15 - count.leading_zeros() as u16;
+    let search_range = 16 << entry_selector;
+
+    let mut data = vec![];
+    data.extend(0x00010000u32.to_be_bytes());
+    data.extend(count.to_be_bytes());
+    data.extend(search_range.to_be_bytes());
+    data.extend(entry_selector.to_be_bytes());
+    data.extend((count * 16 - search_range).to_be_bytes());
+
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = None;
+    for (tag, table) in &tables {
+        if tag == b"head" {
+            head_offset = Some(offset);
+        }
+
+        data.extend(tag);
+        data.extend(checksum(table).to_be_bytes());
+        data.extend((offset as u32).to_be_bytes());
+        data.extend((table.len() as u32).to_be_bytes());
+        offset += (table.len() + 3) / 4 * 4;
+    }
+
+    for (_, table) in &tables {
+        data.extend(table);
+        data.resize((data.len() + 3) / 4 * 4, 0);
+    }
+
+    // Fix up the whole-font checksum in the head table.
+    if let Some(offset) = head_offset {
+        let adjustment = 0xB1B0AFBAu32.wrapping_sub(checksum(&data));
+        data[offset + 8..offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+
+    data
+}
+
+/// Compute an OpenType table checksum.
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Builds a simple TrueType glyph from an outline.
+#[derive(Default)]
+struct GlyfBuilder {
+    /// The finished contours as lists of points and whether they are on the
+    /// curve.
+    contours: Vec<Vec<(i16, i16, bool)>>,
+    /// The contour that is currently being built.
+    current: Vec<(i16, i16, bool)>,
+    /// The last point in unrounded coordinates.
+    last: (f32, f32),
+}
+
+impl GlyfBuilder {
+    fn push(&mut self, x: f32, y: f32, on: bool) {
+        self.current.push((x.round() as i16, y.round() as i16, on));
+    }
+
+    /// Write the glyph and return its number of points, its number of
+    /// contours and its minimum x coordinate.
+    fn write(mut self, glyf: &mut Vec<u8>) -> (u16, u16, i16) {
+        self.close();
+
+        let points: Vec<_> = self.contours.iter().flatten().copied().collect();
+        if points.is_empty() {
+            return (0, 0, 0);
+        }
+
+        let x_min = points.iter().map(|p| p.0).min().unwrap_or(0);
+        let y_min = points.iter().map(|p| p.1).min().unwrap_or(0);
+        let x_max = points.iter().map(|p| p.0).max().unwrap_or(0);
+        let y_max = points.iter().map(|p| p.1).max().unwrap_or(0);
+
+        glyf.extend((self.contours.len() as i16).to_be_bytes());
+        for v in [x_min, y_min, x_max, y_max] {
+            glyf.extend(v.to_be_bytes());
+        }
+
+        let mut end = 0;
+        for contour in &self.contours {
+            end += contour.len() as u16;
+            glyf.extend((end - 1).to_be_bytes());
+        }
+
+        // No instructions.
+        glyf.extend(0u16.to_be_bytes());
+
+        // Flags, then x and y coordinates as 16-bit deltas.
+        glyf.extend(points.iter().map(|p| u8::from(p.2)));
+        let mut prev = 0i16;
+        for p in &points {
+            glyf.extend(p.0.wrapping_sub(prev).to_be_bytes());
+            prev = p.0;
+        }
+
+        let mut prev = 0i16;
+        for p in &points {
+            glyf.extend(p.1.wrapping_sub(prev).to_be_bytes());
+            prev = p.1;
+        }
+
+        glyf.resize((glyf.len() + 3) / 4 * 4, 0);
+        (points.len() as u16, self.contours.len() as u16, x_min)
+    }
+}
+
+impl OutlineBuilder for GlyfBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.push(x, y, true);
+        self.last = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push(x, y, true);
+        self.last = (x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.push(x1, y1, false);
+        self.push(x, y, true);
+        self.last = (x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // TrueType outlines only support quadratic curves, so we approximate
+        // the cubic curve with a few quadratic ones.
+        const PIECES: usize = 4;
+        let (x0, y0) = self.last;
+        let point = |t: f32, a: f32, b: f32, c: f32, d: f32| {
+            let s = 1.0 - t;
+            s * s * s * a + 3.0 * s * s * t * b + 3.0 * s * t * t * c + t * t * t * d
+        };
+        let deriv = |t: f32, a: f32, b: f32, c: f32, d: f32| {
+            let s = 1.0 - t;
+            3.0 * (s * s * (b - a) + 2.0 * s * t * (c - b) + t * t * (d - c))
+        };
+
+        for i in 0..PIECES {
+            let dt = 1.0 / PIECES as f32;
+            let (t0, t1) = (i as f32 * dt, (i + 1) as f32 * dt);
+            let (px0, py0) = (point(t0, x0, x1, x2, x), point(t0, y0, y1, y2, y));
+            let (px1, py1) = (point(t1, x0, x1, x2, x), point(t1, y0, y1, y2, y));
+
+            // The control points of the cubic piece.
+            let cx1 = px0 + deriv(t0, x0, x1, x2, x) * dt / 3.0;
+            let cy1 = py0 + deriv(t0, y0, y1, y2, y) * dt / 3.0;
+            let cx2 = px1 - deriv(t1, x0, x1, x2, x) * dt / 3.0;
+            let cy2 = py1 - deriv(t1, y0, y1, y2, y) * dt / 3.0;
+
+            // The best quadratic approximation of the piece.
+            let qx = (3.0 * (cx1 + cx2) - px0 - px1) / 4.0;
+            let qy = (3.0 * (cy1 + cy2) - py0 - py1) / 4.0;
+            self.push(qx, qy, false);
+            self.push(px1, py1, true);
+        }
+
+        self.last = (x, y);
+    }
+
+    fn close(&mut self) {
+        // Drop a final point that duplicates the start point.
+        if self.current.len() > 1 && self.current.first() == self.current.last() {
+            self.current.pop();
+        }
+
+        if !self.current.is_empty() {
+            self.contours.push(std::mem::take(&mut self.current));
+        }
+    }
+}
+
 /// Create a /ToUnicode CMap.
 fn create_cmap(
     ttf: &ttf_parser::Face,
@@ -202,3 +478,29 @@ fn create_cmap(
 
     cmap
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_font() {
+        let data = include_bytes!("../../../../../assets/fonts/VariableTest.ttf");
+        let font = Font::new(Bytes::from(data.as_slice()), 0)
+            .unwrap()
+            .with_variations(&[(Tag::from_bytes(b"wght"), 900.0)]);
+
+        let glyph = font.ttf().glyph_index('I').unwrap();
+        let compressed = instance_font(&font, &[0, glyph.0]);
+        let data = miniz_oxide::inflate::decompress_to_vec_zlib(&compressed).unwrap();
+        let ttf = ttf_parser::Face::parse(&data, 0).unwrap();
+
+        // The instance has the outline and advance at `wght` 900 baked in.
+        assert!(ttf.tables().fvar.is_none());
+        assert_eq!(ttf.glyph_hor_advance(glyph), Some(500));
+        assert_eq!(
+            ttf.glyph_bounding_box(glyph),
+            Some(ttf_parser::Rect { x_min: 100, y_min: 0, x_max: 400, y_max: 700 })
+        );
+    }
+}
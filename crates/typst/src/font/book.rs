@@ -1,11 +1,13 @@
 use std::cmp::Reverse;
 use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
 use ttf_parser::{name_id, PlatformId, Tag};
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::{Font, FontStretch, FontStyle, FontVariant, FontWeight};
+use crate::geom::Ratio;
 
 /// Metadata about a collection of fonts.
 #[derive(Default, Clone, Hash)]
@@ -121,6 +123,9 @@ impl FontBook {
     ///   normal.
     /// - The absolute distance to the target stretch.
     /// - The absolute distance to the target weight.
+    ///
+    /// For variable fonts, the style, stretch and weight distances take the
+    /// named instances and the ranges of the variation axes into account.
     fn find_best_variant(
         &self,
         like: Option<&FontInfo>,
@@ -143,9 +148,7 @@ impl FontBook {
                         current.family.len(),
                     )
                 }),
-                current.variant.style.distance(variant.style),
-                current.variant.stretch.distance(variant.stretch),
-                current.variant.weight.distance(variant.weight),
+                current.variant_distance(variant),
             );
 
             if best_key.map_or(true, |b| key < b) {
@@ -170,6 +173,66 @@ pub struct FontInfo {
     pub flags: FontFlags,
     /// The unicode coverage of the font.
     pub coverage: Coverage,
+    /// The variation axes of the font. Empty if this is not a variable font.
+    #[serde(default)]
+    pub axes: Vec<FontAxis>,
+    /// The named instances of a variable font, e.g. "Bold Condensed".
+    #[serde(default)]
+    pub instances: Vec<FontInstance>,
+}
+
+/// A variation axis of a variable font.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct FontAxis {
+    /// The axis' four-byte tag, e.g. `wght` or `opsz`.
+    pub tag: [u8; 4],
+    /// The minimum value the axis can take.
+    pub min: f32,
+    /// The value that the font's default instance has on this axis.
+    pub default: f32,
+    /// The maximum value the axis can take.
+    pub max: f32,
+}
+
+impl FontAxis {
+    /// The axis' tag.
+    pub fn tag(&self) -> Tag {
+        Tag::from_bytes(&self.tag)
+    }
+
+    /// Clamp a value into the axis' range.
+    pub fn clamp(&self, value: f32) -> f32 {
+        value.max(self.min).min(self.max)
+    }
+}
+
+impl Eq for FontAxis {}
+
+impl PartialEq for FontAxis {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+            && self.min.to_bits() == other.min.to_bits()
+            && self.default.to_bits() == other.default.to_bits()
+            && self.max.to_bits() == other.max.to_bits()
+    }
+}
+
+impl Hash for FontAxis {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+        self.min.to_bits().hash(state);
+        self.default.to_bits().hash(state);
+        self.max.to_bits().hash(state);
+    }
+}
+
+/// A named instance of a variable font.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct FontInstance {
+    /// The instance's subfamily name, e.g. "SemiBold Italic".
+    pub name: String,
+    /// The variant the instance's axis values correspond to.
+    pub variant: FontVariant,
 }
 
 bitflags::bitflags! {
@@ -277,13 +340,160 @@ impl FontInfo {
             }
         }
 
+        // Determine the variation axes and named instances of variable fonts.
+        let axes: Vec<_> = ttf
+            .variation_axes()
+            .into_iter()
+            .map(|axis| FontAxis {
+                tag: axis.tag.to_bytes(),
+                min: axis.min_value,
+                default: axis.def_value,
+                max: axis.max_value,
+            })
+            .collect();
+
+        let instances = named_instances(ttf, &axes, variant);
+
         Some(FontInfo {
             family,
             variant,
             flags,
             coverage: Coverage::from_vec(codepoints),
+            axes,
+            instances,
         })
     }
+
+    /// Find the variation axis with the given tag.
+    pub fn axis(&self, tag: Tag) -> Option<&FontAxis> {
+        self.axes.iter().find(|axis| axis.tag() == tag)
+    }
+
+    /// The distances in style, stretch and weight between the given variant
+    /// and the closest variant this font can provide.
+    ///
+    /// Besides the font's default variant, this considers the named instances
+    /// of variable fonts and, for the `wght`, `wdth`, `ital` and `slnt` axes,
+    /// every value within the axis' range.
+    pub fn variant_distance(&self, variant: FontVariant) -> (u16, Ratio, u16) {
+        let variants = || {
+            std::iter::once(self.variant)
+                .chain(self.instances.iter().map(|instance| instance.variant))
+        };
+
+        let mut style = variants()
+            .map(|v| v.style.distance(variant.style))
+            .min()
+            .unwrap_or_default();
+
+        if self
+            .axis(Tag::from_bytes(b"ital"))
+            .map_or(false, |axis| axis.max >= 1.0)
+        {
+            style = style.min(FontStyle::Italic.distance(variant.style));
+        }
+
+        if self
+            .axis(Tag::from_bytes(b"slnt"))
+            .map_or(false, |axis| axis.min < 0.0 || axis.max > 0.0)
+        {
+            style = style.min(FontStyle::Oblique.distance(variant.style));
+        }
+
+        let stretch = match self.axis(Tag::from_bytes(b"wdth")) {
+            Some(axis) => {
+                let target = variant.stretch.to_ratio().get() as f32 * 100.0;
+                Ratio::new(((target - axis.clamp(target)) / 100.0).abs() as f64)
+            }
+            None => variants()
+                .map(|v| v.stretch.distance(variant.stretch))
+                .min()
+                .unwrap_or_default(),
+        };
+
+        let weight = match self.axis(Tag::from_bytes(b"wght")) {
+            Some(axis) => {
+                let target = variant.weight.to_number() as f32;
+                (target - axis.clamp(target)).abs().round() as u16
+            }
+            None => variants()
+                .map(|v| v.weight.distance(variant.weight))
+                .min()
+                .unwrap_or_default(),
+        };
+
+        (style, stretch, weight)
+    }
+}
+
+/// Read the named instances from a variable font's `fvar` table.
+fn named_instances(
+    ttf: &ttf_parser::Face,
+    axes: &[FontAxis],
+    default: FontVariant,
+) -> Vec<FontInstance> {
+    let mut instances = vec![];
+    let Some(data) = ttf.raw_face().table(Tag::from_bytes(b"fvar")) else {
+        return instances;
+    };
+
+    let read_u16 =
+        |i: usize| data.get(i..i + 2).map(|b| u16::from_be_bytes([b[0], b[1]]));
+    let read_fixed = |i: usize| {
+        data.get(i..i + 4)
+            .map(|b| i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f32 / 65536.0)
+    };
+
+    // The fvar header stores the offset of the axis records, followed by the
+    // instance records, as well as the count and size of both.
+    let (Some(offset), Some(axis_count), Some(axis_size), Some(count), Some(size)) =
+        (read_u16(4), read_u16(8), read_u16(10), read_u16(12), read_u16(14))
+    else {
+        return instances;
+    };
+
+    if usize::from(axis_count) != axes.len() {
+        return instances;
+    }
+
+    let start = usize::from(offset) + usize::from(axis_count) * usize::from(axis_size);
+    for i in 0..usize::from(count) {
+        let record = start + i * usize::from(size);
+        let Some(name) = read_u16(record).and_then(|id| find_name(ttf, id)) else {
+            continue;
+        };
+
+        let coord = |tag: &[u8; 4]| {
+            let index = axes.iter().position(|axis| &axis.tag == tag)?;
+            read_fixed(record + 4 + 4 * index)
+        };
+
+        let lower = name.to_ascii_lowercase();
+        let style = if coord(b"ital").map_or(false, |v| v >= 0.5) {
+            FontStyle::Italic
+        } else if coord(b"slnt").map_or(false, |v| v != 0.0) {
+            FontStyle::Oblique
+        } else if lower.contains("italic") {
+            FontStyle::Italic
+        } else if lower.contains("oblique") || lower.contains("slanted") {
+            FontStyle::Oblique
+        } else {
+            default.style
+        };
+
+        let weight = coord(b"wght")
+            .map(|v| FontWeight::from_number(v.round() as u16))
+            .unwrap_or(default.weight);
+
+        let stretch = coord(b"wdth")
+            .map(|v| FontStretch::from_ratio(Ratio::new(v as f64 / 100.0)))
+            .unwrap_or(default.stretch);
+
+        let variant = FontVariant { style, weight, stretch };
+        instances.push(FontInstance { name, variant });
+    }
+
+    instances
 }
 
 /// Try to find and decode the name with the given id.
@@ -512,6 +722,39 @@ mod tests {
         assert_eq!(typographic_family("Font Ultra Bold"), "Font");
     }
 
+    #[test]
+    fn test_variable_font_info() {
+        let data = include_bytes!("../../../../assets/fonts/VariableTest.ttf");
+        let ttf = ttf_parser::Face::parse(data, 0).unwrap();
+        let info = FontInfo::from_ttf(&ttf).unwrap();
+        assert_eq!(info.family, "Variable Test");
+
+        let wght = info.axis(Tag::from_bytes(b"wght")).unwrap();
+        assert_eq!((wght.min, wght.default, wght.max), (100.0, 400.0, 900.0));
+
+        let instances: Vec<_> = info
+            .instances
+            .iter()
+            .map(|instance| (instance.name.as_str(), instance.variant.weight))
+            .collect();
+        assert_eq!(
+            instances,
+            [
+                ("Light", FontWeight::LIGHT),
+                ("Regular", FontWeight::REGULAR),
+                ("Bold", FontWeight::BOLD),
+                ("Black", FontWeight::BLACK),
+            ]
+        );
+
+        // Every weight within the axis' range is available. Larger weights
+        // are clamped to 900 and thus also match exactly.
+        let variant =
+            |weight| FontVariant::new(FontStyle::Normal, weight, FontStretch::NORMAL);
+        assert_eq!(info.variant_distance(variant(FontWeight::from_number(450))).2, 0);
+        assert_eq!(info.variant_distance(variant(FontWeight::from_number(950))).2, 0);
+    }
+
     #[test]
     fn test_coverage() {
         #[track_caller]
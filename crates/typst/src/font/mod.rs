@@ -3,14 +3,14 @@
 mod book;
 mod variant;
 
-pub use self::book::{Coverage, FontBook, FontFlags, FontInfo};
+pub use self::book::{Coverage, FontAxis, FontBook, FontFlags, FontInfo, FontInstance};
 pub use self::variant::{FontStretch, FontStyle, FontVariant, FontWeight};
 
 use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
-use ttf_parser::GlyphId;
+use ttf_parser::{GlyphId, Tag};
 
 use self::book::find_name;
 use crate::eval::Cast;
@@ -39,6 +39,9 @@ struct Repr {
     ttf: ttf_parser::Face<'static>,
     /// The underlying rustybuzz face.
     rusty: rustybuzz::Face<'static>,
+    /// The variation axis values of this instance, sorted by tag. Only
+    /// contains axes whose value differs from the axis' default.
+    variations: Vec<(Tag, f32)>,
 }
 
 impl Font {
@@ -58,7 +61,48 @@ impl Font {
         let metrics = FontMetrics::from_ttf(&ttf);
         let info = FontInfo::from_ttf(&ttf)?;
 
-        Some(Self(Arc::new(Repr { data, index, info, metrics, ttf, rusty })))
+        Some(Self(Arc::new(Repr {
+            data,
+            index,
+            info,
+            metrics,
+            ttf,
+            rusty,
+            variations: vec![],
+        })))
+    }
+
+    /// Create an instance of this font with the given variation axis values.
+    ///
+    /// Axes that the font doesn't have are ignored and values are clamped to
+    /// their axis' range. Axes that are not mentioned keep their default
+    /// value, so the result is equal to the default instance if nothing
+    /// differs from it.
+    pub fn with_variations(&self, variations: &[(Tag, f32)]) -> Self {
+        let mut coords: Vec<(Tag, f32)> = vec![];
+        for &(tag, value) in variations {
+            let Some(axis) = self.info().axis(tag) else { continue };
+            let value = axis.clamp(value);
+            coords.retain(|&(t, _)| t != tag);
+            if value != axis.default {
+                coords.push((tag, value));
+            }
+        }
+
+        coords.sort_by_key(|&(tag, _)| tag);
+        if same_variations(&coords, &self.0.variations) {
+            return self.clone();
+        }
+
+        let bits: Vec<_> =
+            coords.iter().map(|&(tag, value)| (tag, value.to_bits())).collect();
+        instantiate(self.data(), self.index(), &bits).unwrap_or_else(|| self.clone())
+    }
+
+    /// The variation axis values of this instance that differ from the
+    /// defaults, sorted by tag. Empty for the default instance.
+    pub fn variations(&self) -> &[(Tag, f32)] {
+        &self.0.variations
     }
 
     /// Parse all fonts in the given data.
@@ -125,10 +169,36 @@ impl Font {
     }
 }
 
+/// Parse a font and apply variation axis values to it.
+#[comemo::memoize]
+fn instantiate(data: &Bytes, index: u32, coords: &[(Tag, u32)]) -> Option<Font> {
+    let font = Font::new(data.clone(), index)?;
+    let mut repr = Arc::try_unwrap(font.0).ok()?;
+    for &(tag, bits) in coords {
+        let value = f32::from_bits(bits);
+        repr.ttf.set_variation(tag, value)?;
+        repr.variations.push((tag, value));
+    }
+
+    let variations: Vec<_> = repr
+        .variations
+        .iter()
+        .map(|&(tag, value)| rustybuzz::Variation { tag, value })
+        .collect();
+
+    repr.rusty.set_variations(&variations);
+    repr.metrics = FontMetrics::from_ttf(&repr.ttf);
+    Some(Font(Arc::new(repr)))
+}
+
 impl Hash for Font {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.0.data.hash(state);
         self.0.index.hash(state);
+        for &(tag, value) in &self.0.variations {
+            tag.hash(state);
+            value.to_bits().hash(state);
+        }
     }
 }
 
@@ -142,10 +212,20 @@ impl Eq for Font {}
 
 impl PartialEq for Font {
     fn eq(&self, other: &Self) -> bool {
-        self.0.data == other.0.data && self.0.index == other.0.index
+        self.0.data == other.0.data
+            && self.0.index == other.0.index
+            && same_variations(&self.0.variations, &other.0.variations)
     }
 }
 
+/// Whether two lists of variation axis values are bitwise equal.
+fn same_variations(a: &[(Tag, f32)], b: &[(Tag, f32)]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(x, y)| x.0 == y.0 && x.1.to_bits() == y.1.to_bits())
+}
+
 /// Metrics of a font.
 #[derive(Debug, Copy, Clone)]
 pub struct FontMetrics {
@@ -5,9 +5,11 @@ use if_chain::if_chain;
 use unscanny::Scanner;
 
 use super::analyze::analyze_labels;
-use super::{analyze_expr, analyze_import, plain_docs_sentence, summarize_font_family};
+use super::{
+    analyze_expr, analyze_import, func_info, plain_docs_sentence, summarize_font_family,
+};
 use crate::doc::Frame;
-use crate::eval::{fields_on, format_str, methods_on, CastInfo, Library, Scope, Value};
+use crate::eval::{fields_on, format_str, CastInfo, Library, Scope, Value};
 use crate::syntax::{
     ast, is_id_continue, is_id_start, is_ident, LinkedNode, Source, SyntaxKind,
 };
@@ -347,14 +349,15 @@ fn complete_field_accesses(ctx: &mut CompletionContext) -> bool {
 
 /// Add completions for all fields on a value.
 fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
-    for &(method, args) in methods_on(value.type_name()) {
+    for method in value.ty().methods() {
+        let name = method.name;
         ctx.completions.push(Completion {
             kind: CompletionKind::Func,
-            label: method.into(),
-            apply: Some(if args {
-                eco_format!("{method}(${{}})")
+            label: name.into(),
+            apply: Some(if method.takes_args {
+                eco_format!("{name}(${{}})")
             } else {
-                eco_format!("{method}()${{}}")
+                eco_format!("{name}()${{}}")
             }),
             detail: None,
         })
@@ -410,6 +413,11 @@ fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
                 }
             }
         }
+        Value::Type(ty) => {
+            for (name, value) in ty.scope().iter() {
+                ctx.value_completion(Some(name.clone()), value, true, None);
+            }
+        }
         _ => {}
     }
 }
@@ -443,16 +451,19 @@ fn complete_imports(ctx: &mut CompletionContext) -> bool {
         if let Some(value) = analyze_expr(ctx.world, &source).into_iter().next();
         then {
             ctx.from = ctx.cursor;
-            import_item_completions(ctx, &items, &value);
+            import_item_completions(ctx, &items, &[], &value);
             return true;
         }
     }
 
-    // Behind a half-started identifier in an import list:
+    // Behind a half-started identifier or a dot in an import list:
     // "#import "path.typ": thi|",
+    // "#import "path.typ": sub.|".
     if_chain! {
-        if ctx.leaf.kind() == SyntaxKind::Ident;
-        if let Some(parent) = ctx.leaf.parent();
+        if matches!(ctx.leaf.kind(), SyntaxKind::Ident | SyntaxKind::Dot);
+        if let Some(path) = ctx.leaf.parent();
+        if path.kind() == SyntaxKind::ImportItemPath;
+        if let Some(parent) = path.parent();
         if parent.kind() == SyntaxKind::ImportItems;
         if let Some(grand) = parent.parent();
         if let Some(ast::Expr::Import(import)) = grand.cast();
@@ -460,8 +471,17 @@ fn complete_imports(ctx: &mut CompletionContext) -> bool {
         if let Some(source) = grand.children().find(|child| child.is::<ast::Expr>());
         if let Some(value) = analyze_expr(ctx.world, &source).into_iter().next();
         then {
-            ctx.from = ctx.leaf.offset();
-            import_item_completions(ctx, &items, &value);
+            let prefix: Vec<_> = path
+                .children()
+                .take_while(|child| child.offset() < ctx.leaf.offset())
+                .filter_map(|child| child.cast::<ast::Ident>())
+                .map(ast::Ident::take)
+                .collect();
+            ctx.from = match ctx.leaf.kind() {
+                SyntaxKind::Ident => ctx.leaf.offset(),
+                _ => ctx.cursor,
+            };
+            import_item_completions(ctx, &items, &prefix, &value);
             return true;
         }
     }
@@ -469,13 +489,15 @@ fn complete_imports(ctx: &mut CompletionContext) -> bool {
     false
 }
 
-/// Add completions for all exports of a module.
+/// Add completions for all exports of a module, or of a nested module if a
+/// path `prefix` is given.
 fn import_item_completions(
     ctx: &mut CompletionContext,
-    existing: &[ast::Ident],
+    existing: &[ast::ImportItem],
+    prefix: &[EcoString],
     value: &Value,
 ) {
-    let module = match value {
+    let mut module = match value {
         Value::Str(path) => match analyze_import(ctx.world, ctx.source, path) {
             Some(module) => module,
             None => return,
@@ -484,12 +506,25 @@ fn import_item_completions(
         _ => return,
     };
 
+    for segment in prefix {
+        match module.scope().get(segment) {
+            Some(Value::Module(nested)) => module = nested.clone(),
+            _ => return,
+        }
+    }
+
     if existing.is_empty() {
         ctx.snippet_completion("*", "*", "Import everything.");
     }
 
     for (name, value) in module.scope().iter() {
-        if existing.iter().all(|ident| ident.as_str() != name) {
+        let imported = existing.iter().any(|item| {
+            item.path()
+                .segments()
+                .map(ast::Ident::take)
+                .eq(prefix.iter().chain([name]).cloned())
+        });
+        if !imported {
             ctx.value_completion(Some(name.clone()), value, false, None);
         }
     }
@@ -670,12 +705,7 @@ fn param_completions(
     set: bool,
     exclude: &[ast::Ident],
 ) {
-    let info = if_chain! {
-        if let Some(Value::Func(func)) = ctx.global.get(callee);
-        if let Some(info) = func.info();
-        then { info }
-        else { return; }
-    };
+    let Some(info) = ctx.global.get(callee).and_then(func_info) else { return };
 
     for param in &info.params {
         if exclude.iter().any(|ident| ident.as_str() == param.name) {
@@ -712,8 +742,7 @@ fn named_param_value_completions(
     name: &str,
 ) {
     let param = if_chain! {
-        if let Some(Value::Func(func)) = ctx.global.get(callee);
-        if let Some(info) = func.info();
+        if let Some(info) = ctx.global.get(callee).and_then(func_info);
         if let Some(param) = info.param(name);
         if param.named;
         then { param }
@@ -769,7 +798,7 @@ fn complete_code(ctx: &mut CompletionContext) -> bool {
 #[rustfmt::skip]
 fn code_completions(ctx: &mut CompletionContext, hashtag: bool) {
     ctx.scope_completions(true, |value| !hashtag || {
-        matches!(value, Value::Symbol(_) | Value::Func(_) | Value::Module(_))
+        matches!(value, Value::Symbol(_) | Value::Func(_) | Value::Type(_) | Value::Module(_))
     });
 
     ctx.snippet_completion(
@@ -1061,7 +1090,9 @@ impl<'a> CompletionContext<'a> {
 
         let detail = docs.map(Into::into).or_else(|| match value {
             Value::Symbol(_) => None,
-            Value::Func(func) => func.info().map(|info| plain_docs_sentence(info.docs)),
+            Value::Func(_) | Value::Type(_) => {
+                func_info(value).map(|info| plain_docs_sentence(info.docs))
+            }
             v => {
                 let repr = v.repr();
                 (repr.as_str() != label).then(|| repr.into())
@@ -1069,7 +1100,7 @@ impl<'a> CompletionContext<'a> {
         });
 
         let mut apply = None;
-        if parens && matches!(value, Value::Func(_)) {
+        if parens && matches!(value, Value::Func(_) | Value::Type(_)) {
             apply = Some(eco_format!("{label}(${{}})"));
         } else if at {
             apply = Some(eco_format!("at(\"{label}\")"));
@@ -1081,7 +1112,7 @@ impl<'a> CompletionContext<'a> {
 
         self.completions.push(Completion {
             kind: match value {
-                Value::Func(_) => CompletionKind::Func,
+                Value::Func(_) | Value::Type(_) => CompletionKind::Func,
                 Value::Symbol(s) => CompletionKind::Symbol(s.get()),
                 _ => CompletionKind::Constant,
             },
@@ -17,8 +17,18 @@ use std::fmt::Write;
 use ecow::{eco_format, EcoString};
 
 use self::analyze::*;
+use crate::eval::{FuncInfo, Value};
 use crate::font::{FontInfo, FontStyle};
 
+/// Details about the function a value can be called as, if any.
+fn func_info(value: &Value) -> Option<&FuncInfo> {
+    match value {
+        Value::Func(func) => func.info(),
+        Value::Type(ty) => ty.constructor_info(),
+        _ => None,
+    }
+}
+
 /// Extract the first sentence of plain text of a piece of documentation.
 ///
 /// Removes Markdown formatting.
@@ -5,7 +5,7 @@ use ecow::{eco_format, EcoString};
 use if_chain::if_chain;
 
 use super::analyze::analyze_labels;
-use super::{analyze_expr, plain_docs_sentence, summarize_font_family};
+use super::{analyze_expr, func_info, plain_docs_sentence, summarize_font_family};
 use crate::doc::Frame;
 use crate::eval::{CastInfo, Tracer, Value};
 use crate::geom::{round_2, Length, Numeric};
@@ -154,8 +154,7 @@ fn named_param_tooltip(
         };
 
         // Find metadata about the function.
-        if let Some(Value::Func(func)) = world.library().global.scope().get(&callee);
-        if let Some(info) = func.info();
+        if let Some(info) = world.library().global.scope().get(&callee).and_then(func_info);
         then { (info, named) }
         else { return None; }
     };
@@ -16,6 +16,7 @@ use image::{ImageDecoder, ImageResult};
 use usvg::{TreeParsing, TreeTextToPath};
 
 use crate::diag::{format_xml_like_error, StrResult};
+use crate::eval::{cast, Cast, IntoValue};
 use crate::font::Font;
 use crate::geom::Axes;
 use crate::util::Bytes;
@@ -156,8 +157,36 @@ pub enum ImageFormat {
     Vector(VectorFormat),
 }
 
+impl ImageFormat {
+    /// Try to detect the format of an image from its data.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if let Some(format) = RasterFormat::detect(data) {
+            return Some(Self::Raster(format));
+        }
+
+        // SVGs are text, so look for the root element near the start.
+        let head = &data[..data.len().min(1024)];
+        let head = String::from_utf8_lossy(head);
+        if head.contains("<svg") {
+            return Some(Self::Vector(VectorFormat::Svg));
+        }
+
+        None
+    }
+}
+
+cast! {
+    ImageFormat,
+    self => match self {
+        Self::Raster(v) => v.into_value(),
+        Self::Vector(v) => v.into_value(),
+    },
+    v: RasterFormat => Self::Raster(v),
+    v: VectorFormat => Self::Vector(v),
+}
+
 /// A raster graphics format.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum RasterFormat {
     /// Raster format for illustrations and transparent graphics.
     Png,
@@ -167,8 +196,20 @@ pub enum RasterFormat {
     Gif,
 }
 
+impl RasterFormat {
+    /// Try to detect the format of a raster image from its data.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match image::guess_format(data).ok()? {
+            image::ImageFormat::Png => Some(Self::Png),
+            image::ImageFormat::Jpeg => Some(Self::Jpg),
+            image::ImageFormat::Gif => Some(Self::Gif),
+            _ => None,
+        }
+    }
+}
+
 /// A vector graphics format.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
 pub enum VectorFormat {
     /// The vector graphics format of the web.
     Svg,
@@ -67,8 +67,20 @@ use crate::util::Bytes;
 /// Compile a source file into a fully layouted document.
 #[tracing::instrument(skip(world))]
 pub fn compile(world: &dyn World) -> SourceResult<Document> {
+    compile_with_tracer(world, &mut Tracer::default())
+}
+
+/// Compile a source file into a fully layouted document with the given tracer.
+///
+/// The tracer collects the warnings emitted during compilation and accounts for
+/// the resources it uses. It can be created with a
+/// [deadline](Tracer::with_deadline).
+#[tracing::instrument(skip(world, tracer))]
+pub fn compile_with_tracer(
+    world: &dyn World,
+    tracer: &mut Tracer,
+) -> SourceResult<Document> {
     let route = Route::default();
-    let mut tracer = Tracer::default();
 
     // Call `track` just once to keep comemo's ID stable.
     let world = world.track();
@@ -144,4 +156,56 @@ pub trait World {
     fn packages(&self) -> &[(PackageSpec, Option<EcoString>)] {
         &[]
     }
+
+    /// Limits on the resources a compilation may use.
+    ///
+    /// This function is optional to implement. Embedders that compile
+    /// untrusted documents can use it to restrict evaluation and layout.
+    fn limits(&self) -> Limits {
+        Limits::default()
+    }
+}
+
+/// Limits on the resources a single compilation may use.
+///
+/// Exceeding any of the limits aborts compilation with an error that cannot
+/// be caught from within the document. A wall-clock deadline can be configured
+/// on the [`Tracer`] passed to [`compile`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Limits {
+    /// The maximum number of expressions to evaluate in total, including
+    /// imported modules, show rules and all layout passes.
+    pub steps: usize,
+    /// The maximum depth of nested function calls.
+    pub call_depth: usize,
+    /// The maximum number of iterations of a single `while` loop.
+    pub loop_iterations: usize,
+    /// The maximum number of pages in the document.
+    pub pages: usize,
+    /// The maximum number of layout passes until all introspections should
+    /// have stabilized.
+    ///
+    /// If they haven't, the document of the last pass is returned and a
+    /// warning is recorded in the [`Tracer`], unless convergence is required.
+    pub layout_passes: usize,
+    /// Whether compilation fails if the layout does not converge within the
+    /// maximum number of layout passes.
+    pub require_convergence: bool,
+    /// The amount of fuel a single plugin call may consume. Every executed
+    /// WebAssembly instruction consumes roughly one unit of fuel.
+    pub plugin_fuel: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            steps: usize::MAX,
+            call_depth: 64,
+            loop_iterations: 10_000,
+            pages: usize::MAX,
+            layout_passes: 5,
+            require_convergence: false,
+            plugin_fuel: 1_000_000_000,
+        }
+    }
 }
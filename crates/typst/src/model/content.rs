@@ -64,7 +64,7 @@ impl Content {
 
     /// The element function of the contained content.
     pub fn func(&self) -> ElemFunc {
-        self.func
+        self.func.clone()
     }
 
     /// Whether the content is an empty sequence.
@@ -105,13 +105,13 @@ impl Content {
     where
         C: ?Sized + 'static,
     {
-        (self.func.0.vtable)(TypeId::of::<C>()).is_some()
+        self.func.vtable(TypeId::of::<C>()).is_some()
     }
 
     /// Whether the contained element has the given capability.
     /// Where the capability is given by a `TypeId`.
     pub fn can_type_id(&self, type_id: TypeId) -> bool {
-        (self.func.0.vtable)(type_id).is_some()
+        self.func.vtable(type_id).is_some()
     }
 
     /// Cast to a trait object if the contained element has the given
@@ -120,7 +120,7 @@ impl Content {
     where
         C: ?Sized + 'static,
     {
-        let vtable = (self.func.0.vtable)(TypeId::of::<C>())?;
+        let vtable = self.func.vtable(TypeId::of::<C>())?;
         let data = self as *const Self as *const ();
         Some(unsafe { &*crate::util::fat::from_raw_parts(data, vtable) })
     }
@@ -131,7 +131,7 @@ impl Content {
     where
         C: ?Sized + 'static,
     {
-        let vtable = (self.func.0.vtable)(TypeId::of::<C>())?;
+        let vtable = self.func.vtable(TypeId::of::<C>())?;
         let data = self as *mut Self as *mut ();
         Some(unsafe { &mut *crate::util::fat::from_raw_parts_mut(data, vtable) })
     }
@@ -1,10 +1,11 @@
 use std::any::TypeId;
 use std::fmt::{self, Debug, Formatter};
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use once_cell::sync::Lazy;
 
-use super::{Content, Selector, Styles};
+use super::{Content, Selector, Styles, UserElemFunc};
 use crate::diag::SourceResult;
 use crate::eval::{cast, Args, Dict, Func, FuncInfo, Value, Vm};
 
@@ -36,28 +37,54 @@ pub trait Set {
 }
 
 /// An element's function.
-#[derive(Copy, Clone)]
-pub struct ElemFunc(pub(super) &'static NativeElemFunc);
+#[derive(Clone)]
+pub struct ElemFunc(Repr);
+
+/// The different kinds of element functions.
+#[derive(Clone)]
+enum Repr {
+    /// An element backed by a Rust type.
+    Native(&'static NativeElemFunc),
+    /// An element defined in Typst code.
+    User(Arc<UserElemFunc>),
+}
 
 impl ElemFunc {
     /// The function's name.
-    pub fn name(self) -> &'static str {
-        self.0.name
+    pub fn name(&self) -> &str {
+        match &self.0 {
+            Repr::Native(native) => native.name,
+            Repr::User(user) => user.name.as_str(),
+        }
     }
 
     /// Apply the given arguments to the function.
-    pub fn with(self, args: Args) -> Func {
-        Func::from(self).with(args)
+    pub fn with(&self, args: Args) -> Func {
+        Func::from(self.clone()).with(args)
+    }
+
+    /// Extract details about the function. User-defined elements have none.
+    pub fn info(&self) -> Option<&'static FuncInfo> {
+        match &self.0 {
+            Repr::Native(native) => Some(&native.info),
+            Repr::User(_) => None,
+        }
     }
 
-    /// Extract details about the function.
-    pub fn info(&self) -> &'static FuncInfo {
-        &self.0.info
+    /// Extract the definition of a user-defined element, if it is one.
+    pub fn user(&self) -> Option<&UserElemFunc> {
+        match &self.0 {
+            Repr::Native(_) => None,
+            Repr::User(user) => Some(&**user),
+        }
     }
 
     /// Construct an element.
-    pub fn construct(self, vm: &mut Vm, args: &mut Args) -> SourceResult<Content> {
-        (self.0.construct)(vm, args)
+    pub fn construct(&self, vm: &mut Vm, args: &mut Args) -> SourceResult<Content> {
+        match &self.0 {
+            Repr::Native(native) => (native.construct)(vm, args),
+            Repr::User(user) => user.construct(self, args),
+        }
     }
 
     /// Whether the contained element has the given capability.
@@ -65,23 +92,34 @@ impl ElemFunc {
     where
         C: ?Sized + 'static,
     {
-        (self.0.vtable)(TypeId::of::<C>()).is_some()
+        self.vtable(TypeId::of::<C>()).is_some()
+    }
+
+    /// Look up the vtable of the given capability.
+    pub(super) fn vtable(&self, of: TypeId) -> Option<*const ()> {
+        match &self.0 {
+            Repr::Native(native) => (native.vtable)(of),
+            Repr::User(_) => UserElemFunc::vtable(of),
+        }
     }
 
     /// Create a selector for elements of this function.
-    pub fn select(self) -> Selector {
-        Selector::Elem(self, None)
+    pub fn select(&self) -> Selector {
+        Selector::Elem(self.clone(), None)
     }
 
     /// Create a selector for elements of this function, filtering for those
     /// whose [fields](super::Content::field) match the given arguments.
-    pub fn where_(self, fields: Dict) -> Selector {
-        Selector::Elem(self, Some(fields))
+    pub fn where_(&self, fields: Dict) -> Selector {
+        Selector::Elem(self.clone(), Some(fields))
     }
 
     /// Execute the set rule for the element and return the resulting style map.
-    pub fn set(self, vm: &mut Vm, mut args: Args) -> SourceResult<Styles> {
-        let styles = (self.0.set)(vm, &mut args)?;
+    pub fn set(&self, vm: &mut Vm, mut args: Args) -> SourceResult<Styles> {
+        let styles = match &self.0 {
+            Repr::Native(native) => (native.set)(vm, &mut args)?,
+            Repr::User(user) => user.set(self, &mut args)?,
+        };
         args.finish()?;
         Ok(styles)
     }
@@ -97,13 +135,20 @@ impl Eq for ElemFunc {}
 
 impl PartialEq for ElemFunc {
     fn eq(&self, other: &Self) -> bool {
-        std::ptr::eq(self.0, other.0)
+        match (&self.0, &other.0) {
+            (Repr::Native(a), Repr::Native(b)) => std::ptr::eq(*a, *b),
+            (Repr::User(a), Repr::User(b)) => a.hash == b.hash,
+            _ => false,
+        }
     }
 }
 
 impl Hash for ElemFunc {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_usize(self.0 as *const _ as usize);
+        match &self.0 {
+            Repr::Native(native) => state.write_usize(*native as *const _ as usize),
+            Repr::User(user) => state.write_u128(user.hash),
+        }
     }
 }
 
@@ -115,7 +160,13 @@ cast! {
 
 impl From<&'static NativeElemFunc> for ElemFunc {
     fn from(native: &'static NativeElemFunc) -> Self {
-        Self(native)
+        Self(Repr::Native(native))
+    }
+}
+
+impl From<Arc<UserElemFunc>> for ElemFunc {
+    fn from(user: Arc<UserElemFunc>) -> Self {
+        Self(Repr::User(user))
     }
 }
 
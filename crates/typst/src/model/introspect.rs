@@ -294,6 +294,11 @@ impl Introspector {
                 }
                 list
             }
+            Selector::Page { selector, page } => self
+                .query(selector)
+                .into_iter()
+                .filter(|elem| self.page(elem.location().unwrap()) == *page)
+                .collect(),
         };
 
         self.queries.borrow_mut().insert(hash, output.clone());
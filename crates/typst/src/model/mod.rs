@@ -7,6 +7,7 @@ mod label;
 mod realize;
 mod selector;
 mod styles;
+mod user;
 
 #[doc(inline)]
 pub use typst_macros::element;
@@ -23,14 +24,17 @@ pub use self::styles::{
     Fold, Property, Recipe, Resolve, Style, StyleChain, StyleVec, StyleVecBuilder,
     Styles, Transform,
 };
+pub use self::user::UserElemFunc;
 
 use std::mem::ManuallyDrop;
 
 use comemo::{Track, Tracked, TrackedMut, Validate};
 
-use crate::diag::{SourceError, SourceResult};
+use crate::diag::{bail, error, SourceError, SourceResult};
 use crate::doc::Document;
 use crate::eval::Tracer;
+use crate::syntax::Span;
+use crate::util::hash128;
 use crate::World;
 
 /// Typeset content into a fully layouted document.
@@ -45,6 +49,8 @@ pub fn typeset(
 
     let library = world.library();
     let styles = StyleChain::new(&library.styles);
+    let limits = world.limits();
+    let span = world.main().root().span();
 
     let mut iter = 0;
     let mut document;
@@ -55,10 +61,15 @@ pub fn typeset(
     let mut introspector = ManuallyDrop::new(Introspector::new(&[]));
 
     // Relayout until all introspections stabilize.
-    // If that doesn't happen within five attempts, we give up.
+    // If that doesn't happen within the maximum number of passes, we give up.
     loop {
         tracing::info!("Layout iteration {iter}");
 
+        if tracer.past_deadline() {
+            ManuallyDrop::into_inner(introspector);
+            bail!(error!(span, "compilation deadline exceeded").into_fatal());
+        }
+
         delayed = DelayedErrors::default();
 
         let constraint = <Introspector as Validate>::Constraint::new();
@@ -74,17 +85,46 @@ pub fn typeset(
         // Layout!
         let result = (library.items.layout)(&mut vt, content, styles)?;
 
-        // Drop the old introspector.
-        ManuallyDrop::into_inner(introspector);
-
         // Only now assign the document and construct the new introspector.
         document = result;
-        introspector = ManuallyDrop::new(Introspector::new(&document.pages));
+        let next = Introspector::new(&document.pages);
         iter += 1;
 
-        if iter >= 5 || introspector.validate(&constraint) {
+        if next.validate(&constraint) {
+            ManuallyDrop::into_inner(introspector);
+            introspector = ManuallyDrop::new(next);
             break;
         }
+
+        // Report what kept changing. Unless convergence is required, we
+        // return the document with possibly stale introspection results.
+        if iter >= limits.layout_passes {
+            let diagnostics = convergence_diagnostics(
+                world,
+                TrackedMut::reborrow_mut(&mut tracer),
+                &introspector,
+                &next,
+                span,
+                iter,
+            );
+
+            ManuallyDrop::into_inner(introspector);
+            if limits.require_convergence {
+                let errors = diagnostics.into_iter().map(SourceError::into_fatal);
+                return Err(Box::new(errors.collect()));
+            }
+
+            for warning in diagnostics {
+                tracer.warn(warning);
+            }
+
+            introspector = ManuallyDrop::new(next);
+            break;
+        }
+
+        // Drop the old introspector.
+        ManuallyDrop::into_inner(introspector);
+        introspector = ManuallyDrop::new(next);
     }
 
     // Drop the introspector.
@@ -98,6 +138,61 @@ pub fn typeset(
     Ok(document)
 }
 
+/// The maximum number of elements to report when layout does not converge.
+const MAX_REPORTED_CHANGES: usize = 5;
+
+/// Produce the diagnostics for a layout that did not converge.
+///
+/// Points to the elements that introspect the document and whose result
+/// differs between the last two passes.
+fn convergence_diagnostics(
+    world: Tracked<dyn World + '_>,
+    mut tracer: TrackedMut<Tracer>,
+    prev: &Introspector,
+    next: &Introspector,
+    span: Span,
+    passes: usize,
+) -> Vec<SourceError> {
+    // Show an element with the introspection results of one of the passes.
+    let mut output = |introspector: &Introspector, elem: &Content| {
+        let show = elem.with::<dyn Show>()?;
+        let mut locator = Locator::new();
+        let mut delayed = DelayedErrors::default();
+        let shown = {
+            let mut vt = Vt {
+                world,
+                tracer: TrackedMut::reborrow_mut(&mut tracer),
+                locator: &mut locator,
+                introspector: introspector.track(),
+                delayed: delayed.track_mut(),
+            };
+            show.show(&mut vt, StyleChain::default())
+        };
+        Some(hash128(&(shown, delayed.0)))
+    };
+
+    let mut diagnostics =
+        vec![error!(span, "layout did not converge within {passes} passes").with_hints(
+            [
+                "the results of counters, states, or queries kept changing".into(),
+                "check whether an introspection affects its own result".into(),
+            ],
+        )];
+
+    for elem in next.all() {
+        if diagnostics.len() > MAX_REPORTED_CHANGES {
+            break;
+        }
+
+        if !elem.span().is_detached() && output(prev, elem) != output(next, elem) {
+            diagnostics
+                .push(error!(elem.span(), "result kept changing between layout passes"));
+        }
+    }
+
+    diagnostics
+}
+
 /// A virtual typesetter.
 ///
 /// Holds the state needed to [typeset] content.
@@ -66,7 +66,7 @@ pub fn realize(
     let mut realized = None;
     for recipe in styles.recipes() {
         let guard = Guard::Nth(n);
-        if recipe.applicable(target) && !target.is_guarded(guard) {
+        if recipe.applicable(target) && !target.is_guarded(guard.clone()) {
             if let Some(content) = try_apply(vt, target, recipe, guard)? {
                 realized = Some(content);
                 break;
@@ -134,7 +134,7 @@ fn try_apply(
                     result.push(make(&text[cursor..start]));
                 }
 
-                let piece = make(m.as_str()).guarded(guard);
+                let piece = make(m.as_str()).guarded(guard.clone());
                 let transformed = recipe.apply_vt(vt, piece)?;
                 result.push(transformed);
                 cursor = m.end();
@@ -158,7 +158,8 @@ fn try_apply(
             | Selector::Location(_)
             | Selector::Can(_)
             | Selector::Before { .. }
-            | Selector::After { .. },
+            | Selector::After { .. }
+            | Selector::Page { .. },
         ) => Ok(None),
 
         None => Ok(None),
@@ -219,7 +220,7 @@ pub enum Behaviour {
 }
 
 /// Guards content against being affected by the same show rule multiple times.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Guard {
     /// The nth recipe from the top of the chain.
     Nth(usize),
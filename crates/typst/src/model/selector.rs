@@ -1,5 +1,6 @@
 use std::any::{Any, TypeId};
 use std::fmt::{self, Debug, Formatter, Write};
+use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use ecow::{eco_format, EcoString, EcoVec};
@@ -36,6 +37,8 @@ pub enum Selector {
     Before { selector: Arc<Self>, end: Arc<Self>, inclusive: bool },
     /// Matches all matches of `selector` after `start`.
     After { selector: Arc<Self>, start: Arc<Self>, inclusive: bool },
+    /// Matches all matches of `selector` on the given page.
+    Page { selector: Arc<Self>, page: NonZeroUsize },
 }
 
 impl Selector {
@@ -79,6 +82,11 @@ impl Selector {
         }
     }
 
+    /// Transforms this selector into a [`Selector::Page`] selector.
+    pub fn on_page(self, page: NonZeroUsize) -> Self {
+        Self::Page { selector: Arc::new(self), page }
+    }
+
     /// Whether the selector matches for the target.
     pub fn matches(&self, target: &Content) -> bool {
         match self {
@@ -99,7 +107,7 @@ impl Selector {
             Self::And(selectors) => selectors.iter().all(move |sel| sel.matches(target)),
             Self::Location(location) => target.location() == Some(*location),
             // Not supported here.
-            Self::Before { .. } | Self::After { .. } => false,
+            Self::Before { .. } | Self::After { .. } | Self::Page { .. } => false,
         }
     }
 }
@@ -147,6 +155,10 @@ impl Debug for Selector {
                 }
                 f.write_char(')')
             }
+            Self::Page { selector, page } => {
+                selector.fmt(f)?;
+                write!(f, ".page({page})")
+            }
         }
     }
 }
@@ -214,6 +226,7 @@ impl FromValue for LocatableSelector {
                         validate(selector)?;
                     }
                 }
+                Selector::Page { selector, .. } => validate(selector)?,
             }
             Ok(())
         }
@@ -278,7 +291,8 @@ impl FromValue for ShowableSelector {
                 | Selector::Location(_)
                 | Selector::Can(_)
                 | Selector::Before { .. }
-                | Selector::After { .. } => {
+                | Selector::After { .. }
+                | Selector::Page { .. } => {
                     bail!("this selector cannot be used with show")
                 }
             }
@@ -0,0 +1,230 @@
+use std::any::TypeId;
+use std::sync::Arc;
+
+use ecow::{eco_format, EcoString};
+
+use super::{
+    Content, ElemFunc, Locatable, Property, Show, StyleChain, Styles, Synthesize, Vt,
+};
+use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::eval::{Args, Dict, FromValue, Func, Reflect, Value};
+use crate::syntax::{is_ident, Spanned};
+use crate::util::{fat, hash128, separated_list};
+
+/// An element function defined in Typst code.
+///
+/// User-defined elements behave like built-in ones: They produce real content
+/// with fields, can be configured with set rules, targeted by show rules and
+/// found through introspection. They are shown by calling their `display`
+/// function with the element, after unset fields were filled in from the
+/// active set rules or the fields' defaults.
+pub struct UserElemFunc {
+    /// The element's name.
+    pub(super) name: EcoString,
+    /// A hash of the definition. Identical definitions yield equal elements.
+    pub(super) hash: u128,
+    /// The element's fields.
+    fields: Vec<Field>,
+    /// Shows the element.
+    display: Func,
+}
+
+/// A field of a user-defined element.
+#[derive(Debug, Clone, Hash)]
+struct Field {
+    /// The field's name.
+    name: EcoString,
+    /// The names of the types the field accepts. `None` accepts any value.
+    types: Option<Vec<EcoString>>,
+    /// The value used if the field is neither given nor set.
+    default: Value,
+    /// Whether the field is given positionally instead of by name.
+    positional: bool,
+    /// Whether the field must be given when constructing the element. Required
+    /// fields cannot be configured with set rules.
+    required: bool,
+}
+
+impl UserElemFunc {
+    /// Define a new element.
+    ///
+    /// Each entry of `fields` maps a field name to a dictionary with the
+    /// optional keys `type`, `default`, `positional`, and `required`.
+    pub fn define(name: EcoString, display: Func, fields: Dict) -> StrResult<ElemFunc> {
+        if !is_ident(&name) {
+            bail!("element name must be a valid identifier");
+        }
+
+        let fields = fields
+            .into_iter()
+            .map(|(name, spec)| Field::new(name.into(), spec))
+            .collect::<StrResult<Vec<_>>>()?;
+
+        let hash = hash128(&(&name, &display, &fields));
+        Ok(Arc::new(Self { name, hash, fields, display }).into())
+    }
+
+    /// Construct an instance of the element.
+    pub(super) fn construct(
+        &self,
+        func: &ElemFunc,
+        args: &mut Args,
+    ) -> SourceResult<Content> {
+        let mut content = Content::new(func.clone());
+        for field in &self.fields {
+            let value = if field.positional && field.required {
+                Some(args.expect::<Spanned<Value>>(&field.name)?)
+            } else if field.positional {
+                args.eat::<Spanned<Value>>()?
+            } else {
+                args.named::<Spanned<Value>>(&field.name)?
+            };
+
+            match value {
+                Some(Spanned { v, span }) => {
+                    content.push_field(field.name.clone(), field.check(v).at(span)?);
+                }
+                None if field.required => {
+                    bail!(args.span, "missing argument: {}", field.name);
+                }
+                None => {}
+            }
+        }
+        Ok(content)
+    }
+
+    /// Parse the arguments of a set rule for the element.
+    pub(super) fn set(&self, func: &ElemFunc, args: &mut Args) -> SourceResult<Styles> {
+        let mut styles = Styles::new();
+        for field in self.fields.iter().filter(|field| !field.required) {
+            let value = if field.positional {
+                args.eat::<Spanned<Value>>()?
+            } else {
+                args.named::<Spanned<Value>>(&field.name)?
+            };
+
+            if let Some(Spanned { v, span }) = value {
+                let value = field.check(v).at(span)?;
+                styles.set(Property::new(func.clone(), field.name.clone(), value));
+            }
+        }
+        Ok(styles)
+    }
+
+    /// The capabilities shared by all user-defined elements.
+    pub(super) fn vtable(of: TypeId) -> Option<*const ()> {
+        let null = UserElem(Content::empty());
+        if of == TypeId::of::<dyn Locatable>() {
+            return Some(unsafe { fat::vtable(&null as &dyn Locatable) });
+        }
+        if of == TypeId::of::<dyn Synthesize>() {
+            return Some(unsafe { fat::vtable(&null as &dyn Synthesize) });
+        }
+        if of == TypeId::of::<dyn Show>() {
+            return Some(unsafe { fat::vtable(&null as &dyn Show) });
+        }
+        None
+    }
+}
+
+impl Field {
+    /// Parse a field from its specification.
+    fn new(name: EcoString, spec: Value) -> StrResult<Self> {
+        if !is_ident(&name) || name == "label" {
+            bail!("`{name}` is not a valid field name");
+        }
+
+        let mut spec = spec.cast::<Dict>()?;
+        let types = match spec.take("type").ok() {
+            None => None,
+            Some(Value::Array(array)) => {
+                Some(array.into_iter().map(type_name).collect::<StrResult<_>>()?)
+            }
+            Some(v) => Some(vec![type_name(v)?]),
+        };
+
+        let given = spec.take("default").ok();
+        let mut flag = |key| spec.take(key).ok().map(Value::cast).transpose();
+        let positional = flag("positional")?.unwrap_or(false);
+        let required = flag("required")?.unwrap_or(false);
+        spec.finish(&["type", "default", "positional", "required"])?;
+
+        if required && given.is_some() {
+            bail!("required field `{name}` cannot have a default");
+        }
+
+        let mut field = Self {
+            name,
+            types,
+            default: Value::None,
+            positional,
+            required,
+        };
+
+        if !required {
+            let default = given.unwrap_or(Value::None);
+            field.default = field.check(default).map_err(|err| {
+                eco_format!("invalid default for field `{}` ({err})", field.name)
+            })?;
+        }
+
+        Ok(field)
+    }
+
+    /// Check that the value is of one of the field's types. Strings and
+    /// symbols are converted if the field accepts content.
+    fn check(&self, value: Value) -> StrResult<Value> {
+        let Some(types) = &self.types else { return Ok(value) };
+        if types.iter().any(|ty| ty == value.type_name() || ty == "any") {
+            return Ok(value);
+        }
+
+        if types.iter().any(|ty| ty == "content") && Content::castable(&value) {
+            return Content::from_value(value).map(Value::Content);
+        }
+
+        Err(eco_format!(
+            "expected {}, found {}",
+            separated_list(types, "or"),
+            value.type_name()
+        ))
+    }
+}
+
+/// Extract the name of a type given as a type or its name.
+fn type_name(value: Value) -> StrResult<EcoString> {
+    match value {
+        Value::Type(ty) => Ok(ty.name().into()),
+        Value::Str(name) => Ok(name.into()),
+        v => bail!("expected type or string, found {}", v.type_name()),
+    }
+}
+
+/// A user-defined element.
+#[repr(transparent)]
+struct UserElem(Content);
+
+impl Locatable for UserElem {}
+
+impl Synthesize for UserElem {
+    fn synthesize(&mut self, _: &mut Vt, styles: StyleChain) -> SourceResult<()> {
+        let func = self.0.func();
+        let user = func.user().expect("element is user-defined");
+        for field in &user.fields {
+            if self.0.field_ref(&field.name).is_none() {
+                let value =
+                    styles.get(func.clone(), &field.name, None, || field.default.clone());
+                self.0.push_field(field.name.clone(), value);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Show for UserElem {
+    fn show(&self, vt: &mut Vt, _: StyleChain) -> SourceResult<Content> {
+        let func = self.0.func();
+        let display = &func.user().expect("element is user-defined").display;
+        Ok(display.call_vt(vt, [Value::Content(self.0.clone())])?.display())
+    }
+}
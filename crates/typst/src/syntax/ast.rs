@@ -1974,6 +1974,15 @@ impl ModuleImport {
             _ => Option::None,
         })
     }
+
+    /// The name the module or function is bound to with `as`:
+    /// `import "utils.typ" as tools`.
+    pub fn new_name(&self) -> Option<Ident> {
+        self.0
+            .children()
+            .skip_while(|node| node.kind() != SyntaxKind::As)
+            .find_map(SyntaxNode::cast)
+    }
 }
 
 /// The items that ought to be imported from a file.
@@ -1982,7 +1991,85 @@ pub enum Imports {
     /// All items in the scope of the file should be imported.
     Wildcard,
     /// The specified items from the file should be imported.
-    Items(Vec<Ident>),
+    Items(Vec<ImportItem>),
+}
+
+/// An item that ought to be imported from a file.
+#[derive(Debug, Clone, Hash)]
+pub enum ImportItem {
+    /// An item bound to its original name: `a` or `a.b`.
+    Simple(ImportItemPath),
+    /// An item bound to a different name: `a as c`.
+    Renamed(RenamedImportItem),
+}
+
+impl AstNode for ImportItem {
+    fn from_untyped(node: &SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::ImportItemPath => node.cast().map(Self::Simple),
+            SyntaxKind::RenamedImportItem => node.cast().map(Self::Renamed),
+            _ => Option::None,
+        }
+    }
+
+    fn as_untyped(&self) -> &SyntaxNode {
+        match self {
+            Self::Simple(v) => v.as_untyped(),
+            Self::Renamed(v) => v.as_untyped(),
+        }
+    }
+}
+
+impl ImportItem {
+    /// The path to the imported item.
+    pub fn path(&self) -> ImportItemPath {
+        match self {
+            Self::Simple(path) => path.clone(),
+            Self::Renamed(renamed) => renamed.path(),
+        }
+    }
+
+    /// The name the item is bound to.
+    pub fn bound_name(&self) -> Ident {
+        match self {
+            Self::Simple(path) => path.name(),
+            Self::Renamed(renamed) => renamed.new_name(),
+        }
+    }
+}
+
+node! {
+    /// A path to an imported item: `a.b.c`.
+    ImportItemPath
+}
+
+impl ImportItemPath {
+    /// The identifiers that make up the path.
+    pub fn segments(&self) -> impl DoubleEndedIterator<Item = Ident> + '_ {
+        self.0.children().filter_map(SyntaxNode::cast)
+    }
+
+    /// The name of the imported item, that is, the last segment of the path.
+    pub fn name(&self) -> Ident {
+        self.0.cast_last_match().unwrap_or_default()
+    }
+}
+
+node! {
+    /// A renamed import item: `a as d`.
+    RenamedImportItem
+}
+
+impl RenamedImportItem {
+    /// The path to the imported item.
+    pub fn path(&self) -> ImportItemPath {
+        self.0.cast_first_match().unwrap_or_default()
+    }
+
+    /// The name the item is bound to.
+    pub fn new_name(&self) -> Ident {
+        self.0.cast_last_match().unwrap_or_default()
+    }
 }
 
 node! {
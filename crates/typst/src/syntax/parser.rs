@@ -1130,6 +1130,9 @@ fn module_import(p: &mut Parser) {
     let m = p.marker();
     p.assert(SyntaxKind::Import);
     code_expr(p);
+    if p.eat_if(SyntaxKind::As) {
+        p.expect(SyntaxKind::Ident);
+    }
     if p.eat_if(SyntaxKind::Colon) && !p.eat_if(SyntaxKind::Star) {
         import_items(p);
     }
@@ -1139,7 +1142,17 @@ fn module_import(p: &mut Parser) {
 fn import_items(p: &mut Parser) {
     let m = p.marker();
     while !p.eof() && !p.at(SyntaxKind::Semicolon) {
-        if !p.eat_if(SyntaxKind::Ident) {
+        let item = p.marker();
+        if p.eat_if(SyntaxKind::Ident) {
+            while p.eat_if(SyntaxKind::Dot) {
+                p.expect(SyntaxKind::Ident);
+            }
+            p.wrap(item, SyntaxKind::ImportItemPath);
+            if p.eat_if(SyntaxKind::As) {
+                p.expect(SyntaxKind::Ident);
+                p.wrap(item, SyntaxKind::RenamedImportItem);
+            }
+        } else {
             p.unexpected();
         }
         if p.current().is_terminator() {
@@ -4,6 +4,10 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use comemo::Prehashed;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::StrResult;
+use crate::eval::{IntoValue, Value};
 
 /// A shared byte buffer that is cheap to clone and hash.
 #[derive(Clone, Hash, Eq, PartialEq)]
@@ -24,6 +28,44 @@ impl Bytes {
     pub fn to_vec(&self) -> Vec<u8> {
         self.0.to_vec()
     }
+
+    /// Return the byte at the specified index as an integer. Negative indices
+    /// count from the back.
+    pub fn at(&self, index: i64, default: Option<Value>) -> StrResult<Value> {
+        self.locate(index)
+            .and_then(|i| self.get(i))
+            .map(|&byte| byte.into_value())
+            .or(default)
+            .ok_or_else(|| out_of_bounds_no_default(index, self.len()))
+    }
+
+    /// Extract a contiguous subregion of the bytes.
+    pub fn slice(&self, start: i64, end: Option<i64>) -> StrResult<Self> {
+        let len = self.len();
+        let start = self
+            .locate(start)
+            .filter(|&start| start <= len)
+            .ok_or_else(|| out_of_bounds(start, len))?;
+
+        let end = end.unwrap_or(len as i64);
+        let end = self
+            .locate(end)
+            .filter(|&end| end <= len)
+            .ok_or_else(|| out_of_bounds(end, len))?
+            .max(start);
+
+        Ok(self[start..end].into())
+    }
+
+    /// Resolve an index, counting from the back if it is negative.
+    fn locate(&self, index: i64) -> Option<usize> {
+        usize::try_from(if index >= 0 {
+            index
+        } else {
+            (self.len() as i64).checked_add(index)?
+        })
+        .ok()
+    }
 }
 
 impl From<&[u8]> for Bytes {
@@ -57,3 +99,18 @@ impl Debug for Bytes {
         write!(f, "bytes({})", self.len())
     }
 }
+
+/// The out of bounds access error message.
+#[cold]
+fn out_of_bounds(index: i64, len: usize) -> EcoString {
+    eco_format!("byte index out of bounds (index: {index}, len: {len})")
+}
+
+/// The out of bounds access error message when no default value was given.
+#[cold]
+fn out_of_bounds_no_default(index: i64, len: usize) -> EcoString {
+    eco_format!(
+        "byte index out of bounds (index: {index}, len: {len}) \
+         and no default value was specified",
+    )
+}
@@ -10,6 +10,7 @@ use std::io::{self, Write};
 use std::iter;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use clap::Parser;
 use comemo::{Prehashed, Track};
@@ -23,12 +24,12 @@ use walkdir::WalkDir;
 
 use typst::diag::{bail, FileError, FileResult, StrResult};
 use typst::doc::{Document, Frame, FrameItem, Meta};
-use typst::eval::{eco_format, func, Datetime, Library, NoneValue, Value};
+use typst::eval::{eco_format, func, Datetime, Library, NoneValue, Tracer, Value};
 use typst::font::{Font, FontBook};
 use typst::geom::{Abs, Color, RgbaColor, Smart};
 use typst::syntax::{Source, Span, SyntaxNode};
 use typst::util::{Bytes, PathExt};
-use typst::World;
+use typst::{Limits, World};
 use typst_library::layout::{Margin, PageElem};
 use typst_library::text::{TextElem, TextSize};
 
@@ -205,6 +206,7 @@ struct TestWorld {
     book: Prehashed<FontBook>,
     fonts: Vec<Font>,
     paths: RefCell<HashMap<PathBuf, PathSlot>>,
+    limits: Limits,
 }
 
 #[derive(Clone)]
@@ -235,6 +237,7 @@ impl TestWorld {
             book: Prehashed::new(FontBook::from_fonts(&fonts)),
             fonts,
             paths: RefCell::default(),
+            limits: Limits::default(),
         }
     }
 }
@@ -277,6 +280,10 @@ impl World for TestWorld {
     fn today(&self, _: Option<i64>) -> Option<Datetime> {
         Some(Datetime::from_ymd(1970, 1, 1).unwrap())
     }
+
+    fn limits(&self) -> Limits {
+        self.limits
+    }
 }
 
 impl TestWorld {
@@ -520,7 +527,13 @@ fn test_part(
         writeln!(output, "Model:\n{:#?}\n", module.content()).unwrap();
     }
 
-    let (mut frames, errors) = match typst::compile(world) {
+    world.limits = metadata.limits;
+    let mut tracer = Tracer::default();
+    if metadata.deadline {
+        tracer = tracer.with_deadline(Instant::now());
+    }
+
+    let (mut frames, errors) = match typst::compile_with_tracer(world, &mut tracer) {
         Ok(document) => (document.pages, vec![]),
         Err(errors) => (vec![], *errors),
     };
@@ -538,12 +551,23 @@ fn test_part(
     // however, as the line of the hint is still verified.
     let actual_errors_and_hints: HashSet<UserOutput> = errors
         .into_iter()
-        .inspect(|error| assert!(!error.span.is_detached()))
-        .filter(|error| error.span.id() == source.id())
-        .flat_map(|error| {
-            let range = error.span.range(world);
-            let output_error =
-                UserOutput::Error(range.clone(), error.message.replace('\\', "/"));
+        .map(|error| (false, error))
+        .chain(tracer.warnings().iter().map(|warning| (true, warning.clone())))
+        .inspect(|(_, error)| assert!(!error.span.is_detached()))
+        .filter(|(_, error)| error.span.id() == source.id())
+        .flat_map(|(warning, error)| {
+            // Errors for the whole document are annotated as `0-0`.
+            let range = if error.span == source.root().span() {
+                0..0
+            } else {
+                error.span.range(world)
+            };
+            let message = error.message.replace('\\', "/");
+            let output_error = if warning {
+                UserOutput::Warning(range.clone(), message)
+            } else {
+                UserOutput::Error(range.clone(), message)
+            };
             let hints = error
                 .hints
                 .iter()
@@ -593,6 +617,7 @@ fn print_user_output(
 ) {
     let (range, message) = match &user_output {
         UserOutput::Error(r, m) => (r, m),
+        UserOutput::Warning(r, m) => (r, m),
         UserOutput::Hint(r, m) => (r, m),
     };
 
@@ -602,6 +627,7 @@ fn print_user_output(
     let end_col = 1 + source.byte_to_column(range.end).unwrap();
     let kind = match user_output {
         UserOutput::Error(_, _) => "Error",
+        UserOutput::Warning(_, _) => "Warning",
         UserOutput::Hint(_, _) => "Hint",
     };
     writeln!(output, "{kind}: {start_line}:{start_col}-{end_line}:{end_col}: {message}")
@@ -616,11 +642,14 @@ struct TestConfiguration {
 struct TestPartMetadata {
     part_configuration: TestConfiguration,
     invariants: HashSet<UserOutput>,
+    limits: Limits,
+    deadline: bool,
 }
 
 #[derive(PartialEq, Eq, Debug, Hash)]
 enum UserOutput {
     Error(Range<usize>, String),
+    Warning(Range<usize>, String),
     Hint(Range<usize>, String),
 }
 
@@ -628,6 +657,7 @@ impl UserOutput {
     fn start(&self) -> usize {
         match self {
             UserOutput::Error(r, _) => r.start,
+            UserOutput::Warning(r, _) => r.start,
             UserOutput::Hint(r, _) => r.start,
         }
     }
@@ -636,6 +666,10 @@ impl UserOutput {
         UserOutput::Error(range, message)
     }
 
+    fn warning(range: Range<usize>, message: String) -> UserOutput {
+        UserOutput::Warning(range, message)
+    }
+
     fn hint(range: Range<usize>, message: String) -> UserOutput {
         UserOutput::Hint(range, message)
     }
@@ -645,12 +679,32 @@ fn parse_part_metadata(source: &Source) -> TestPartMetadata {
     let mut compare_ref = None;
     let mut validate_hints = None;
     let mut expectations = HashSet::default();
+    let mut limits = Limits::default();
+    let mut deadline = false;
 
     let lines: Vec<_> = source.text().lines().map(str::trim).collect();
     for (i, line) in lines.iter().enumerate() {
         compare_ref = get_flag_metadata(line, "Ref").or(compare_ref);
         validate_hints = get_flag_metadata(line, "Hints").or(validate_hints);
 
+        if let Some(spec) = get_metadata(line, "Limits") {
+            for entry in spec.split(',').map(str::trim) {
+                let (key, value) = entry.split_once('=').unwrap_or((entry, ""));
+                let value = || value.parse().unwrap();
+                match key {
+                    "steps" => limits.steps = value(),
+                    "call-depth" => limits.call_depth = value(),
+                    "loop-iterations" => limits.loop_iterations = value(),
+                    "pages" => limits.pages = value(),
+                    "layout-passes" => limits.layout_passes = value(),
+                    "require-convergence" => limits.require_convergence = true,
+                    "plugin-fuel" => limits.plugin_fuel = value(),
+                    "deadline" => deadline = true,
+                    _ => panic!("unknown limit: {key}"),
+                }
+            }
+        }
+
         fn num(s: &mut Scanner) -> usize {
             s.eat_while(char::is_numeric).parse().unwrap()
         }
@@ -667,16 +721,26 @@ fn parse_part_metadata(source: &Source) -> TestPartMetadata {
         };
 
         let error_factory: fn(Range<usize>, String) -> UserOutput = UserOutput::error;
+        let warning_factory: fn(Range<usize>, String) -> UserOutput = UserOutput::warning;
         let hint_factory: fn(Range<usize>, String) -> UserOutput = UserOutput::hint;
 
         let error_metadata = get_metadata(line, "Error").map(|s| (s, error_factory));
+        let get_warning_metadata =
+            || get_metadata(line, "Warning").map(|s| (s, warning_factory));
         let get_hint_metadata = || get_metadata(line, "Hint").map(|s| (s, hint_factory));
 
-        if let Some((expectation, factory)) = error_metadata.or_else(get_hint_metadata) {
+        if let Some((expectation, factory)) = error_metadata
+            .or_else(get_warning_metadata)
+            .or_else(get_hint_metadata)
+        {
             let mut s = Scanner::new(expectation);
-            let start = pos(&mut s);
-            let end = if s.eat_if('-') { pos(&mut s) } else { start };
-            let range = start..end;
+            let range = if s.eat_if("0-0") {
+                0..0
+            } else {
+                let start = pos(&mut s);
+                let end = if s.eat_if('-') { pos(&mut s) } else { start };
+                start..end
+            };
 
             expectations.insert(factory(range, s.after().trim().to_string()));
         };
@@ -685,6 +749,8 @@ fn parse_part_metadata(source: &Source) -> TestPartMetadata {
     TestPartMetadata {
         part_configuration: TestConfiguration { compare_ref, validate_hints },
         invariants: expectations,
+        limits,
+        deadline,
     }
 }
 
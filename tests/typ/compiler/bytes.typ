@@ -0,0 +1,47 @@
+// Test the bytes type and its methods.
+// Ref: false
+
+---
+// Test the constructor.
+#test(bytes("Hi").len(), 2)
+#test(bytes("😃").len(), 4)
+#test(bytes((72, 105)), bytes("Hi"))
+#test(bytes(bytes("Hi")), bytes("Hi"))
+#test(str(bytes((72, 105))), "Hi")
+#test(repr(bytes("abc")), "bytes(3)")
+#test(type(bytes(())), "bytes")
+
+---
+// Test the `at` method.
+#let data = bytes((1, 2, 255))
+#test(data.at(0), 1)
+#test(data.at(-1), 255)
+#test(data.at(3, default: none), none)
+
+---
+// Test the `slice` method.
+#let data = bytes("Hello")
+#test(data.slice(1, 3), bytes("el"))
+#test(data.slice(1, count: 3), bytes("ell"))
+#test(data.slice(-2), bytes("lo"))
+#test(data.slice(5), bytes(()))
+
+---
+// Error: 2-21 byte index out of bounds (index: 5, len: 2) and no default value was specified
+#bytes((1, 2)).at(5)
+
+---
+// Error: 2-24 byte index out of bounds (index: 3, len: 2)
+#bytes((1, 2)).slice(3)
+
+---
+// Error: 8-16 number must be between 0 and 255
+#bytes((1, 256))
+
+---
+// Error: 8-16 expected integer, found string
+#bytes((1, "a"))
+
+---
+// Error: 8-9 expected string, array, or bytes, found integer
+#bytes(1)
@@ -115,6 +115,10 @@
 ---
 // Too few arguments.
 #{
+// Warning: 21-34 using a type as a string is deprecated
+// Hint: 21-34 compare with the type itself or convert it with `str`
+// Warning: 21-51 using a type as a string is deprecated
+// Hint: 21-51 compare with the type itself or convert it with `str`
   let types(x, y) = "[" + type(x) + ", " + type(y) + "]"
   test(types(14%, 12pt), "[ratio, length]")
 
@@ -23,7 +23,7 @@
 #test(item(1, 2), 3)
 
 // Code mode
-{
+#{
   import "module.typ": b
   test(b, 1)
 }
@@ -82,6 +82,41 @@
 
 #item(2)[a]
 
+---
+// Renaming a module import.
+#import "module.typ" as other
+#test(other.b, 1)
+#test(other.item(1, 2), 3)
+
+// Built-in modules can be renamed, too.
+#import calc as math
+#test(math.abs(-2), 2)
+
+---
+// Renaming item imports.
+#import "module.typ": b as x, item as add
+#test(x, 1)
+#test(add(1, 2), 3)
+
+---
+// Renaming a module and importing items from it.
+#import "module.typ" as other: b
+#test(other.c, 2)
+#test(b, 1)
+
+---
+// Importing items from nested modules.
+#import "module.typ": chap1.name, chap1.name as hero
+#test(name, "Klaus")
+#test(hero, "Klaus")
+
+// Code mode
+#{
+  import "module.typ" as m: c as three
+  test(m.d, 3)
+  test(three, 2)
+}
+
 ---
 // Can't import from closures.
 #let f(x) = x
@@ -120,6 +155,14 @@
 // Error: 23-35 unresolved import
 #import "module.typ": non_existing
 
+---
+// Error: 29-33 unresolved import
+#import "module.typ": chap1.nope
+
+---
+// Error: 23-24 expected module, found integer
+#import "module.typ": b.c
+
 ---
 // Cyclic import of this very file.
 // Error: 9-23 cyclic import
@@ -171,3 +214,11 @@ This is never reached.
 ---
 // Error: 14 expected comma
 #import "": a b
+
+---
+// Error: 27 expected identifier
+#import "module.typ": a as
+
+---
+// Error: 24 expected identifier
+#import "module.typ" as: a
@@ -0,0 +1,36 @@
+// Test the resource limits configured by the embedder.
+// Ref: false
+
+---
+// Limits: steps=100
+// Error: 23-25 maximum number of evaluation steps exceeded
+#for i in range(1000) {}
+
+---
+// The step budget is shared with the closures a module calls.
+// Limits: steps=100
+// Error: 12-14 maximum number of evaluation steps exceeded
+#let f() = {}
+#for i in range(1000) { f() }
+
+---
+// Limits: call-depth=5
+// Error: 24-32 maximum function call depth exceeded
+#let f(n) = if n > 0 { f(n - 1) }
+#f(10)
+
+---
+// Limits: loop-iterations=10
+// Error: 2:2-2:25 loop seems to be infinite
+#let i = 0
+#while i < 20 { i += 1 }
+
+---
+// Limits: pages=2
+// Error: 0-0 maximum number of pages exceeded
+A #pagebreak() B #pagebreak() C
+
+---
+// Limits: deadline
+// Error: 0-0 compilation deadline exceeded
+Hello
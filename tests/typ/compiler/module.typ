@@ -9,5 +9,6 @@
 #let item(a, b) = a + b
 #let push(a) = a + 1
 #let fn = rect.with(fill: conifer, inset: 5pt)
+#import "modules/chap1.typ"
 
 Some _includable_ text.
@@ -79,10 +79,14 @@
   test(v + v, 2 * v)
 
   // Integer addition does not give a float.
+// Warning: 6-26 using a type as a string is deprecated
+// Hint: 6-26 compare with the type itself or convert it with `str`
   if type(v) != "integer" {
     test(v + v, 2.0 * v)
   }
 
+// Warning: 6-31 using a type as a string is deprecated
+// Hint: 6-31 compare with the type itself or convert it with `str`
   if "relative" not in type(v) and ("pt" not in repr(v) or "em" not in repr(v)) {
     test(v / v, 1.0)
   }
@@ -0,0 +1,85 @@
+// Test types as first-class values.
+// Ref: false
+
+---
+#test(type(1), int)
+#test(type(1.5), float)
+#test(type("hi"), str)
+#test(type(true), bool)
+#test(type(12pt), length)
+#test(type(50% + 1pt), relative)
+#test(type((1, 2)), array)
+#test(type((a: 1)), dictionary)
+#test(type([a]), content)
+#test(type(x => x), function)
+#test(type(int), type)
+#test(type(type), type)
+#test(type(1) != float, true)
+
+---
+// Types compare equal to their names, but that is deprecated.
+// Warning: 7-27 using a type as a string is deprecated
+// Hint: 7-27 compare with the type itself or convert it with `str`
+#test(type(1) == "integer", true)
+// Warning: 7-29 using a type as a string is deprecated
+// Hint: 7-29 compare with the type itself or convert it with `str`
+#test("string" == type("hi"), true)
+#test(type(none), "none")
+#test(repr(int), "integer")
+#test(str(type(12pt)), "length")
+
+---
+// Calling types.
+#test(int("3"), 3)
+#test(float(2), 2.0)
+#test(str(10), "10")
+#test(type(1)("12"), 12)
+#test((1, 2).map(str), ("1", "2"))
+#test(str.to-unicode("a"), 97)
+#test(datetime(year: 2023, month: 1, day: 1).year(), 2023)
+
+---
+// Methods are available in the scope of their type.
+#test(str.len("hello"), 5)
+#test(str.slice("abc", 1), "bc")
+#test(array.len((1, 2, 3)), 3)
+#test(array.map((1, 2), x => x * 2), (2, 4))
+#test(type(str.len), function)
+#let len = dictionary.len
+#test(len((a: 1, b: 2)), 2)
+#test((1, 2, 3).sum(), 6)
+#test(datetime.year(datetime(year: 2023, month: 1, day: 1)), 2023)
+
+---
+// User-defined elements accept types for their fields.
+#let note = element("note", it => it.body, fields: (
+  body: (type: content, positional: true, required: true),
+  size: (type: (length, ratio), default: 1pt),
+))
+#test(note(size: 50%)[Hi].size, 50%)
+
+---
+#let note = element("note", it => none, fields: (size: (type: (length, ratio)),))
+// Error: 13-18 expected length or ratio, found string
+#note(size: "big")
+
+---
+// Error: 2-7 type array does not have a constructor
+#array(1)
+
+---
+// Error: 10-11 expected string, found integer
+#str.len(1)
+
+---
+// Mutating methods can't be called through the type.
+// Error: 2-19 type dictionary does not contain field `insert`
+#dictionary.insert
+
+---
+// Error: 6-10 type string does not contain field `nope`
+#str.nope
+
+---
+// Error: 2-19 type string has no method `to-unicode`
+#"hi".to-unicode()
@@ -103,7 +103,7 @@
 #test(str(10 / 3).len() > 10, true)
 
 ---
-// Error: 6-8 expected integer, float, label, or string, found content
+// Error: 6-8 expected integer, float, decimal, label, type, bytes, or string, found content
 #str([])
 
 ---
@@ -223,3 +223,72 @@
 ---
 // Error: 26-36 failed to format datetime in the requested format
 #datetime.today().display("[hour]")
+
+---
+// Test durations.
+#let d = duration(weeks: 1, days: 2, hours: 3, minutes: 4, seconds: 5)
+#test(repr(d), "duration(weeks: 1, days: 2, hours: 3, minutes: 4, seconds: 5)")
+#test(repr(duration()), "duration(seconds: 0)")
+#test(type(d), duration)
+#test(duration(hours: 36).days(), 1.5)
+#test(duration(days: 14).weeks(), 2.0)
+#test(duration(minutes: 90).hours(), 1.5)
+#test(duration(hours: 1).minutes(), 60.0)
+#test(duration(hours: 1).seconds(), 3600.0)
+#test(duration(days: 1) + duration(hours: 12), duration(hours: 36))
+#test(duration(days: 1) - duration(hours: 36), duration(hours: -12))
+#test(-duration(days: 1), duration(days: -1))
+#test(3 * duration(days: 1), duration(days: 3))
+#test(duration(days: 1) * 1.5, duration(hours: 36))
+#test(duration(days: 3) / 2, duration(hours: 36))
+#test(duration(weeks: 1) / duration(days: 1), 7.0)
+#test(duration(hours: 1) < duration(minutes: 61), true)
+
+---
+// Test datetime arithmetic and accessors.
+#let start = datetime(year: 2023, month: 12, day: 28)
+#test((start + duration(days: 7)).display(), "2024-01-04")
+#test((duration(days: 7) + start).display(), "2024-01-04")
+#test((start - duration(weeks: 1)).display(), "2023-12-21")
+#test((start + duration(hours: 36)).display(), "2023-12-29")
+#test(datetime(year: 2024, month: 3, day: 1) - datetime(year: 2024, month: 2, day: 1), duration(days: 29))
+#test(start < datetime(year: 2024, month: 1, day: 1), true)
+#let t = datetime(hour: 23, minute: 30, second: 0)
+#test((t + duration(hours: 1)).display(), "00:30:00")
+#let dt = datetime(year: 2023, month: 1, day: 1, hour: 12, minute: 0, second: 0)
+#test((dt - duration(minutes: 30)).display(), "2023-01-01 11:30:00")
+#test(start.ordinal(), 362)
+#test(start.iso-week(), 52)
+#test(datetime(year: 2021, month: 1, day: 1).iso-week(), 53)
+#test(t.ordinal(), none)
+
+---
+// Test parsing datetimes.
+#test(datetime.from-iso("2023-05-01"), datetime(year: 2023, month: 5, day: 1))
+#test(datetime.from-iso("10:30:15"), datetime(hour: 10, minute: 30, second: 15))
+#test(datetime.from-iso("2023-05-01T10:30:15").display(), "2023-05-01 10:30:15")
+#test(datetime.from-iso("2023-05-01T10:30:00+02:00").display(), "2023-05-01 10:30:00")
+#test(datetime.from-iso("2023-05-01T10:30:00+02:00", offset: 0).display(), "2023-05-01 08:30:00")
+#test(datetime.from-iso("2023-05-01T23:30:00Z", offset: 2).display(), "2023-05-02 01:30:00")
+#test(datetime.parse("03.08.2012", "[day].[month].[year]"), datetime(year: 2012, month: 8, day: 3))
+#test(datetime.parse("19h05m00s", "[hour]h[minute]m[second]s").display(), "19:05:00")
+
+---
+// Error: 19-33 invalid ISO 8601 datetime: 2023-13-01
+#datetime.from-iso("2023-13-01")
+
+---
+// Error: 16-34 format does not describe a date or time
+#datetime.parse("2023", "[year]")
+
+---
+// Error: 3-83 cannot subtract time from date
+#(datetime(year: 2023, month: 1, day: 1) - datetime(hour: 1, minute: 0, second: 0))
+
+---
+// Error: 10-38 duration is too large
+#duration(weeks: 9223372036854775807)
+
+---
+// Error: 3-24 cannot add duration and integer
+#(duration(days: 1) + 1)
@@ -14,6 +14,18 @@
 // Error: 18-34 file is not valid utf-8
 #let data = read("/files/bad.txt")
 
+---
+// Test reading files as bytes.
+#let data = read("/files/hello.txt", encoding: none)
+#test(type(data), "bytes")
+#test(data.len(), 13)
+#test(str(data), "Hello, world!")
+#test(read("/files/bad.txt", encoding: none).len() > 0, true)
+
+---
+// Error: 48-56 expected "utf8" or none
+#let data = read("/files/hello.txt", encoding: "latin1")
+
 ---
 // Test reading CSV data.
 // Ref: true
@@ -111,3 +123,68 @@
 ---
 // Error: 6-22 failed to parse xml file: found closing tag 'data' instead of 'hello' in line 3
 #xml("/files/bad.xml")
+
+---
+// Test decoding and encoding JSON.
+#test(json.decode("{\"a\": [1, 2.5, null, true]}"), (a: (1, 2.5, none, true)))
+#test(json.encode((a: (1, 2.5, none, true)), pretty: false), "{\"a\":[1,2.5,null,true]}")
+#test(json.encode((1, "hi")), "[\n  1,\n  \"hi\"\n]")
+#test(json.encode(datetime(year: 2023, month: 2, day: 1)), "\"2023-02-01\"")
+#let data = json("/files/zoo.json")
+#test(json.decode(json.encode(data)), data)
+
+---
+// Error: 14-20 cannot encode content as json
+#json.encode([*hi*])
+
+---
+// Error: 14-18 failed to parse json file: syntax error in line 1
+#json.decode("{1")
+
+---
+// Test decoding and encoding TOML.
+#test(toml.decode("x = 1\n[t]\ny = \"s\""), (x: 1, t: (y: "s")))
+#test(toml.encode((list: (1, 2)), pretty: false), "list = [1, 2]\n")
+#test(toml.encode((list: (1, 2))), "list = [\n    1,\n    2,\n]\n")
+#test(toml.encode((d: datetime(year: 2023, month: 1, day: 2))), "d = 2023-01-02\n")
+#let data = toml("/files/toml-types.toml")
+#test(toml.decode(toml.encode(data)), data)
+
+---
+// Error: 14-20 expected dictionary, found array
+#toml.encode((1, 2))
+
+---
+// Error: 14-23 cannot encode none as toml
+#toml.encode((a: none))
+
+---
+// Test decoding and encoding YAML.
+#test(yaml.decode("a: [1, ~]"), (a: (1, none)))
+#test(yaml.encode((a: (1, none))), "---\na:\n  - 1\n  - ~\n")
+#let data = yaml("/files/yaml-types.yaml")
+#test(yaml.decode(yaml.encode(data)), data)
+
+---
+// Error: 14-20 cannot encode function as yaml
+#yaml.encode(x => x)
+
+---
+// Test reading, decoding and encoding CBOR data.
+#let data = cbor("/files/zoo.cbor")
+#test(data, (name: "Debby", weight: 150))
+#test(cbor.decode(cbor.encode(data)), data)
+#test(cbor.decode(cbor.encode((1, 2.5, "a", none, (b: true)))), (1, 2.5, "a", none, (b: true)))
+#test(type(cbor.encode(none)), "bytes")
+
+---
+// Error: 14-18 expected bytes, found string
+#cbor.decode("hi")
+
+---
+// Test decoding CSV and XML from strings and bytes.
+#test(csv.decode("a;b\n1;2", delimiter: ";"), (("a", "b"), ("1", "2")))
+#test(csv.decode(bytes("a,b")), (("a", "b"),))
+#test(xml.decode("<a x=\"1\">b</a>"), ((tag: "a", attrs: (x: "1"), children: ("b",)),))
+#test(json.decode(bytes("[1]")), (1,))
+#test(json.decode(read("/files/zoo.json", encoding: none)), json("/files/zoo.json"))
@@ -0,0 +1,98 @@
+// Test decimal numbers.
+// Ref: false
+
+---
+// Test construction and display.
+#test(type(decimal("1.5")), decimal)
+#test(decimal("1.5"), decimal("1.50"))
+#test(decimal(3), decimal("3"))
+#test(decimal(0.5), decimal("0.5"))
+#test(decimal(decimal("2.25")), decimal("2.25"))
+#test(repr(decimal("12.50")), "decimal(\"12.50\")")
+#test(str(decimal("-0.001")), "-0.001")
+#test(str(decimal("100000000000000000000")), "100000000000000000000")
+#test(str(decimal("0.0000000000000000000001")), "0.0000000000000000000001")
+#test(int(decimal("2.9")), 2)
+#test(int(decimal("-2.9")), -2)
+#test(float(decimal("2.25")), 2.25)
+
+---
+// Test arithmetic.
+#test(decimal("0.1") + decimal("0.2"), decimal("0.3"))
+#test(decimal("0.3") - decimal("0.1"), decimal("0.2"))
+#test(decimal("19.99") * 3, decimal("59.97"))
+#test(2 * decimal("1.25"), decimal("2.5"))
+#test(decimal("10") / 4, decimal("2.5"))
+#test(1 - decimal("0.01"), decimal("0.99"))
+#test(-decimal("1.5"), decimal("-1.5"))
+#test(+decimal("1.5"), decimal("1.5"))
+#test(str(decimal("1.10") + decimal("2.20")), "3.30")
+
+---
+// Test comparison.
+#test(decimal("1.0") == 1, true)
+#test(2 == decimal("2.00"), true)
+#test(decimal("0.1") < decimal("0.2"), true)
+#test(decimal("1.5") > 1, true)
+#test(3 <= decimal("2.99"), false)
+#test(calc.max(decimal("1.5"), decimal("2.5")), decimal("2.5"))
+
+---
+// Test rounding.
+#test(calc.round(decimal("2.345"), digits: 2), decimal("2.35"))
+#test(calc.round(decimal("-2.345"), digits: 2), decimal("-2.35"))
+#test(calc.round(decimal("2.345"), digits: 2, mode: "half-down"), decimal("2.34"))
+#test(calc.round(decimal("2.345"), digits: 2, mode: "half-even"), decimal("2.34"))
+#test(calc.round(decimal("2.355"), digits: 2, mode: "half-even"), decimal("2.36"))
+#test(calc.round(decimal("2.341"), digits: 2, mode: "up"), decimal("2.35"))
+#test(calc.round(decimal("2.349"), digits: 2, mode: "down"), decimal("2.34"))
+#test(calc.round(decimal("-2.341"), digits: 2, mode: "ceiling"), decimal("-2.34"))
+#test(calc.round(decimal("-2.341"), digits: 2, mode: "floor"), decimal("-2.35"))
+#test(calc.round(decimal("1250"), digits: -2, mode: "half-even"), decimal("1200"))
+#test(calc.round(decimal("5"), digits: -29), decimal("0"))
+#test(calc.round(decimal("-5"), digits: -100, mode: "ceiling"), decimal("0"))
+#test(str(calc.round(decimal("2.5"), digits: 2)), "2.50")
+#test(str(calc.round(decimal("2.5"))), "3")
+
+---
+// Error: 11-50 value is too large
+#calc.round(decimal("5"), digits: -29, mode: "up")
+
+---
+// Test rounding modes for floats.
+#test(calc.round(2.5), 3.0)
+#test(calc.round(2.5, mode: "half-down"), 2.0)
+#test(calc.round(2.5, mode: "half-even"), 2.0)
+#test(calc.round(3.5, mode: "half-even"), 4.0)
+#test(calc.round(-2.1, mode: "up"), -3.0)
+#test(calc.round(-2.9, mode: "down"), -2.0)
+#test(calc.round(2.1, mode: "ceiling"), 3.0)
+#test(calc.round(2.9, mode: "floor"), 2.0)
+
+---
+// Error: 10-15 invalid decimal: 1e3
+#decimal("1e3")
+
+---
+// Error: 10-20 invalid decimal: 12.5 EUR
+#decimal("12.5 EUR")
+
+---
+// Error: 10-46 decimal has too many digits: 0.12345678901234567890123456789012
+#decimal("0.12345678901234567890123456789012")
+
+---
+// Error: 10-22 number cannot be represented as a decimal
+#decimal(float("nan"))
+
+---
+// Error: 3-23 cannot add decimal and float
+#(decimal("1.5") + 0.5)
+
+---
+// Error: 3-21 cannot divide by zero
+#(decimal("1.5") / 0)
+
+---
+// Error: 35-40 expected "half-up", "half-down", "half-even", "up", "down", "ceiling", or "floor"
+#calc.round(decimal("1.5"), mode: "bad")
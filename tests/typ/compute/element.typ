@@ -0,0 +1,93 @@
+// Test user-defined elements.
+// Ref: false
+
+---
+#let callout = element(
+  "callout",
+  it => [(#it.kind: #it.body)],
+  fields: (
+    body: (type: "content", positional: true, required: true),
+    kind: (type: "string", default: "info"),
+  ),
+)
+
+#let c = callout[Hello]
+#test(c.func(), callout)
+#test(c.body, [Hello])
+#test(c.has("kind"), false)
+#test(callout("Text").body, [Text])
+#test(repr(callout), "callout")
+
+// Set rules fill in the fields before show rules run.
+#set callout(kind: "warn")
+#show callout: it => {
+  test(it.kind, "warn")
+  it
+}
+#callout[Careful]
+
+---
+// Show rules and selectors with fields.
+#let note = element("note", it => it.body, fields: (
+  body: (positional: true, required: true),
+  level: (type: "integer", default: 1),
+))
+
+#show note.where(level: 2): it => {
+  test(it.body, [Second])
+  [Replaced]
+}
+#note[First]
+#note(level: 2)[Second]
+
+---
+// Introspection with query and counters.
+#let task = element("task", it => it.title, fields: (
+  title: (type: "string", positional: true, required: true),
+))
+
+#task("A")
+#task("B")
+#locate(loc => {
+  let tasks = query(task, loc)
+  test(tasks.len(), 2)
+  test(tasks.map(t => t.title), ("A", "B"))
+  test(counter(task).final(loc), (2,))
+})
+
+---
+#let box-elem = element("boxed", it => it.body, fields: (
+  body: (type: "content", positional: true, required: true),
+  width: (type: ("length", "auto"), default: auto),
+))
+
+// Error: 18-24 expected length or auto, found string
+#box-elem(width: "wide")[Hi]
+
+---
+#let box-elem = element("boxed", it => none, fields: (
+  width: (type: "length", default: 1pt),
+))
+
+// Error: 22-26 expected length, found boolean
+#set box-elem(width: true)
+
+---
+#let box-elem = element("boxed", it => none, fields: (
+  body: (required: true),
+))
+
+// Error: 10-12 missing argument: body
+#box-elem()
+
+---
+// Error: 54-79 invalid default for field `width` (expected length, found none)
+#let box-elem = element("boxed", it => none, fields: (width: (type: "length")))
+
+---
+// Error: 54-72 unexpected key "kind", valid keys are "type", "default", "positional", and "required"
+#let box-elem = element("boxed", it => none, fields: (width: (kind: 1)))
+
+---
+// Error: 18-25 element name must be a valid identifier
+#let e = element("a b c", it => none)
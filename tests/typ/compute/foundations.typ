@@ -25,6 +25,42 @@
 // Error: 7-24 panicked with: "this is wrong"
 #panic("this is wrong")
 
+---
+// Test catching errors.
+#test(catch(() => 1 + 1), 2)
+#test(catch(() => int("twelve")), none)
+#test(catch(() => int("twelve"), err => err.message), "invalid integer: twelve")
+#test(catch(() => 1 + "a", err => err.message), "cannot add integer and string")
+#test(catch(() => json("missing.json"), err => "fallback"), "fallback")
+#let err = catch(() => {
+  let x = 1
+  assert(x > 1)
+}, err => err)
+#test(err.message, "assertion failed")
+#test(err.hints, ())
+#test(type(err.line), int)
+#test(err.column, 9)
+
+---
+// Errors in the handler are not caught.
+// Error: 37-44 assertion failed
+#catch(() => int("x"), err => assert(false))
+
+---
+// Panics cannot be caught.
+// Error: 19-27 panicked with: "boom"
+#catch(() => panic("boom"), err => none)
+
+---
+// Resource limits cannot be caught.
+// Error: 15-21 maximum function call depth exceeded
+#let rec(n) = rec(n) + 1
+#catch(() => rec(1), err => none)
+
+---
+// Error: 27-49 loop seems to be infinite
+#catch(() => { let j = 1; while j > 0 { j += 1 } })
+
 ---
 // Test failing assertions.
 // Error: 8-16 assertion failed
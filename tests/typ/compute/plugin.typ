@@ -0,0 +1,49 @@
+// Test WebAssembly plugins.
+// Ref: false
+
+---
+#let p = plugin("/files/hello.wasm")
+#test(type(p.hello()), "bytes")
+#test(str(p.hello()), "Hello from wasm!!!")
+#test(str(p.double_it(p.hello())), "Hello from wasm!!!Hello from wasm!!!")
+
+---
+#let p = plugin("/files/hello.wasm")
+
+// Error: 15-17 plugin function `returns_err` errored with: This is an error
+#p.returns_err()
+
+---
+#let p = plugin("/files/hello.wasm")
+
+// Error: 14-16 plugin function `will_panic` panicked: wasm `unreachable` instruction executed
+#p.will_panic()
+
+---
+// Limits: plugin-fuel=100000
+#let p = plugin("/files/hello.wasm")
+
+// Error: 8-10 plugin function `spin` ran out of fuel
+#p.spin()
+
+---
+// Limits: plugin-fuel=100000
+#let p = plugin("/files/hello.wasm")
+
+// Running out of fuel can't be caught.
+// Error: 20-22 plugin function `spin` ran out of fuel
+#catch(() => p.spin(), err => none)
+
+---
+#let p = plugin("/files/hello.wasm")
+
+// Error: 13-15 plugin function `double_it` takes 1 argument, but 0 were given
+#p.double_it()
+
+---
+// Error: 30-34 module `hello` does not contain `nope`
+#plugin("/files/hello.wasm").nope()
+
+---
+// Error: 9-27 failed to load WebAssembly module (magic header not detected: bad magic number (at offset 0x0))
+#plugin("/files/hello.txt")
@@ -0,0 +1,58 @@
+// Test that sticky blocks are kept together with what follows them.
+
+---
+// Ref: false
+// A heading moves along with a box that doesn't fit anymore.
+#set page(height: 100pt)
+#v(30pt)
+= Heading <heading>
+#rect(height: 40pt)
+#locate(loc => test(query(<heading>, loc).first().location().page(), 2))
+
+---
+// Ref: false
+// A custom sticky block moves along with a table that breaks.
+#set page(height: 100pt)
+#v(40pt)
+#block(sticky: true)[Caption #locate(loc => test(loc.page(), 2))]
+#table(columns: 1, rows: 20pt, ..range(4).map(str))
+
+---
+// Ref: false
+// Without stickiness, the block stays on the first page.
+#set page(height: 100pt)
+#v(30pt)
+#block[Caption #locate(loc => test(loc.page(), 1))]
+#rect(height: 40pt)
+
+---
+// Ref: false
+// Configurable widow and orphan prevention. A single line fits on the first
+// page and the last page gets at least three lines.
+#set page(height: 80pt)
+#set par(orphans: 1, widows: 3)
+#show regex("[a-zA-Z]+"): it => [#counter("word").update(0) <word>#it]
+#v(40pt)
+#locate(loc => {
+  let words = query(<word>, loc).map(it => it.location().position())
+  let lines(page) = {
+    let ys = words.filter(p => p.page == page).map(p => p.y)
+    ys.zip(ys.slice(1)).filter(pair => pair.at(0) != pair.at(1)).len() + 1
+  }
+  test(lines(1), 1)
+  test(lines(words.last().page) >= 3, true)
+})
+#lorem(40)
+
+---
+// Ref: false
+// With orphan prevention, the single line moves to the next page.
+#set page(height: 80pt)
+#set par(orphans: 2)
+#show regex("[a-zA-Z]+"): it => [#counter("word").update(0) <word>#it]
+#v(40pt)
+#locate(loc => {
+  let words = query(<word>, loc).map(it => it.location().position())
+  test(words.first().page, 2)
+})
+#lorem(40)
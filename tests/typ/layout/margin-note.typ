@@ -0,0 +1,78 @@
+// Test margin notes.
+// Ref: false
+
+---
+#set page(width: 200pt, height: 200pt, margin: (x: 60pt))
+A#margin-note[#metadata(none) <first>First]
+B#margin-note[#metadata(none) <second>Second, which collides with the first]
+#margin-note(side: left)[#metadata(none) <left>Left]
+#margin-note(side: "inside")[#metadata(none) <inside>Inside]
+
+#locate(loc => {
+  let notes = query(margin-note, loc)
+  test(notes.len(), 4)
+  test(notes.map(it => it.side), ("outside", "outside", left, "inside"))
+  test(notes.map(it => it.numbering), (none,) * 4)
+
+  // Single-sided pages are bound on the left, so the outside is on the right.
+  let pos(label) = query(label, loc).first().location().position()
+  test((pos(<first>).x, pos(<second>).x), (151pt, 151pt))
+  test((pos(<left>).x, pos(<inside>).x), (11pt, 11pt))
+
+  // A note starts at its anchor's baseline, colliding ones are stacked below.
+  test(pos(<first>).y, notes.first().location().position().y)
+  test(pos(<second>).y > pos(<first>).y, true)
+  test(pos(<inside>).y > pos(<left>).y, true)
+})
+
+---
+// Two-sided layout with right binding.
+#set page(width: 200pt, height: 100pt, margin: (inside: 20pt, outside: 70pt), binding: right)
+One#margin-note[#metadata(none) <one>Outer]
+#pagebreak()
+Two#margin-note[#metadata(none) <two>Outer again]
+
+#locate(loc => {
+  // The outside alternates, starting on the left of the first page.
+  let pos(label) = query(label, loc).first().location().position()
+  test((pos(<one>).page, pos(<one>).x), (1, 11pt))
+  test((pos(<two>).page, pos(<two>).x), (2, 141pt))
+})
+
+---
+// Numbered notes only step the counter when they are numbered.
+#set page(width: 200pt, height: 200pt, margin: (right: 70pt))
+#set margin-note(numbering: "a")
+Ravens#margin-note[_Corvus corax_]
+and crows#margin-note[_Corvus corone_]
+and owls#margin-note(numbering: none)[Not numbered.]
+
+#locate(loc => {
+  test(counter(margin-note).at(loc), (2,))
+  test(query(margin-note, loc).first().numbering, "a")
+})
+
+---
+// Many notes near the bottom are shifted upwards.
+#set page(width: 200pt, height: 120pt, margin: (right: 70pt))
+#locate(loc => {
+  let anchor = query(margin-note, loc).last().location().position().y
+  let notes = query(<note>, loc).map(it => it.location().position().y)
+  test(range(3).all(i => notes.at(i) < notes.at(i + 1)), true)
+  test(notes.last() < anchor, true)
+})
+
+#v(1fr)
+#for i in range(4) [Note#margin-note[#metadata(i) <note>Number #i]]
+
+---
+// Error: 20-21 expected "inside", "outside", or alignment, found integer
+#margin-note(side: 1)[A]
+
+---
+// Error: 20-26 must be `left` or `right`
+#margin-note(side: center)[A]
+
+---
+// Error: 2-15 missing argument: body
+#margin-note()
@@ -0,0 +1,110 @@
+// Test configurable line breaking costs and justification limits.
+
+---
+// Ref: false
+#set page(width: 180pt)
+#set par(justify: true)
+#set text(hyphenate: true)
+#let story = [
+  In olden times when wishing still helped one, there lived a king whose
+  daughters were all beautiful.
+]
+
+#par(costs: (hyphenation: 0%), story)
+#par(costs: (hyphenation: 400%, consecutive-hyphenation: 0%), story)
+#par(costs: (runt: 200%), story)
+#par(justification-limits: (spacing: (min: 90%, max: 110%)), story)
+#par(justification-limits: (tracking: (min: -0.01em, max: 0.02em)), story)
+
+---
+// Ref: false
+// The first four words fit exactly, so the last one ends up alone.
+#set par(justify: true)
+#set text(hyphenate: true)
+#let mark = counter("mark").update(0)
+#let words = [wonderful wonderful wonderful wonderful]
+#style(styles => block(width: measure(words, styles).width)[
+  wonderful wonderful wonderful
+  #mark <c>wonderful#mark <d>
+  #mark <e>wonderful
+])
+
+#locate(loc => {
+  let y(label) = query(label, loc).first().location().position().y
+  test((y(<c>) == y(<d>), y(<d>) == y(<e>)), (true, false))
+})
+
+---
+// Ref: false
+// Expensive runts are avoided by hyphenating the fourth word, even though the
+// last word has hyphenation opportunities.
+#set text(hyphenate: true)
+#set par(justify: true, costs: (runt: 10000%))
+#let mark = counter("mark").update(0)
+#let words = [wonderful wonderful wonderful wonderful]
+#style(styles => block(width: measure(words, styles).width)[
+  wonderful wonderful wonderful
+  #mark <c>wonderful#mark <d>
+  #mark <e>wonderful
+])
+
+#locate(loc => {
+  let y(label) = query(label, loc).first().location().position().y
+  test((y(<c>) == y(<d>), y(<d>) == y(<e>)), (false, true))
+})
+
+---
+// Ref: false
+// Expensive hyphenation moves the fourth word to the next line instead.
+#set text(hyphenate: true)
+#set par(justify: true, costs: (runt: 10000%, hyphenation: 1000000%))
+#let mark = counter("mark").update(0)
+#let words = [wonderful wonderful wonderful wonderful]
+#style(styles => block(width: measure(words, styles).width)[
+  wonderful wonderful wonderful
+  #mark <c>wonderful#mark <d>
+  #mark <e>wonderful
+])
+
+#locate(loc => {
+  let y(label) = query(label, loc).first().location().position().y
+  test((y(<c>) == y(<d>), y(<d>) == y(<e>)), (true, true))
+})
+
+---
+// Ref: false
+// Spaces that may not shrink can't fit the fourth word anymore, so it is
+// hyphenated.
+#set text(hyphenate: true)
+#set par(
+  justify: true,
+  justification-limits: (spacing: (min: 100%, max: 150%)),
+)
+#let mark = counter("mark").update(0)
+#let words = [wonderful wonderful wonderful wonderful]
+#style(styles => block(width: measure(words, styles).width - 1pt)[
+  wonderful wonderful wonderful
+  #mark <c>wonderful#mark <d>
+  #mark <e>wonderful
+])
+
+#locate(loc => {
+  let y(label) = query(label, loc).first().location().position().y
+  test((y(<c>) == y(<d>), y(<d>) == y(<e>)), (false, true))
+})
+
+---
+// Error: 17-30 unexpected key "hyphen", valid keys are "hyphenation", "consecutive-hyphenation", and "runt"
+#set par(costs: (hyphen: 50%))
+
+---
+// Error: 17-28 expected ratio, found length
+#set par(costs: (runt: 1pt))
+
+---
+// Error: 32-64 unexpected key "mid", valid keys are "min" and "max"
+#set par(justification-limits: (spacing: (min: 50%, mid: 100%)))
+
+---
+// Error: 32-47 expected dictionary, found length
+#set par(justification-limits: (tracking: 1pt))
@@ -0,0 +1,80 @@
+// Test line numbering.
+// Ref: false
+
+---
+#set page(width: 120pt, height: auto, margin: (left: 30pt))
+#set par.line(numbering: "1")
+#show ref.where(form: "line"): it => {
+  show regex("^[0-9]+$"): n => [#metadata(n.text) <ref>#n]
+  it
+}
+
+First line \
+Second line #metadata(none) <second> \
+#metadata(none) <third> Third line #locate(loc => {
+  let lines = query(par.line, loc)
+  test(lines.len(), 4)
+  test(lines.first().numbering, "1")
+  test(lines.first().reset, "document")
+  test(lines.first().side, left)
+
+  // Elements at the end and the start of a line find that line.
+  test(query(<ref>, loc).map(it => it.value), ("2", "3"))
+})
+
+See #ref(<second>, form: "line") and #ref(<third>, form: "line").
+
+---
+// Every other number, numbers on the right and per-section reset. The lines of
+// headings are numbered, too.
+#set page(width: 120pt, height: auto, margin: (x: 30pt))
+#set par.line(
+  numbering: n => [#metadata(n) <number>(#n)],
+  every: 2,
+  side: right,
+  reset: "section",
+)
+
+= Intro
+A \ B \ C
+= Outro
+D \ E \ F \ G #locate(loc => {
+  let numbers = query(<number>, loc)
+  test(numbers.map(it => it.value), (2, 4, 2, 4))
+  test(numbers.map(it => it.location().position().x), (101pt,) * 4)
+})
+
+---
+// Excluding lines of figures and tables.
+#set page(width: 120pt, height: auto, margin: (left: 30pt))
+#set par.line(numbering: "1")
+#show figure: set par.line(numbering: none)
+#show table: set par.line(numbering: none)
+
+Numbered #locate(loc => test(query(par.line, loc).len(), 1))
+#figure(table[Cell], caption: [Caption])
+
+---
+// Restarting on each page.
+#set page(width: 120pt, height: 60pt, margin: (left: 30pt, rest: 10pt))
+#set par.line(numbering: n => [#metadata(n) <number>#n], reset: "page")
+#lorem(40) #locate(loc => {
+  let numbers = query(<number>, loc)
+  let pages = numbers.map(it => it.location().page())
+  test(pages.last() > 1, true)
+  for (i, number) in numbers.enumerate() {
+    let restart = i == 0 or pages.at(i) != pages.at(i - 1)
+    test(number.value, if restart { 1 } else { numbers.at(i - 1).value + 1 })
+    test(number.location().position().x < 19pt, true)
+  }
+})
+
+---
+// Error: 2-27 referenced element is not in a numbered line
+// Hint: 2-27 you can enable line numbering with `#set par.line(numbering: "1")`
+#ref(<here>, form: "line")
+#metadata(none) <here>
+
+---
+// Error: 38-44 expected "document", "page", or "section"
+#set par.line(numbering: "1", reset: "part")
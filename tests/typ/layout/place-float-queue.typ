@@ -0,0 +1,100 @@
+// Test float placement preferences and deferral.
+// Ref: false
+
+---
+// Floats that don't fit are deferred and stay in order.
+#set page(height: 140pt, margin: 10pt)
+#set place(clearance: 5pt)
+Intro
+#figure(placement: bottom, rect(height: 110pt))
+#figure(placement: top, rect(height: 20pt))
+Outro #metadata(none) <outro>
+
+#locate(loc => {
+  let pages = query(figure, loc).map(it => it.location().page())
+  test(pages, (2, 3))
+  test(query(<outro>, loc).first().location().page(), 1)
+})
+
+---
+// Test the float limit.
+#set page(height: 200pt, margin: 10pt)
+#set place(limit: 1)
+#figure(placement: top, rect[A])
+#figure(placement: top, rect[B])
+#figure(placement: bottom, rect[C])
+Text
+
+#locate(loc => {
+  let pages = query(figure, loc).map(it => it.location().page())
+  test(pages, (1, 2, 3))
+})
+
+---
+// A float placed "here" stays in the flow if it fits and moves to the top of
+// the next page otherwise.
+#set page(height: 150pt, margin: 10pt)
+#set place(clearance: 6pt)
+Before #metadata(none) <a>
+#figure(placement: "here", rect(height: 20pt)) <b>
+Middle #metadata(none) <c>
+#figure(placement: "here", rect(height: 100pt)) <d>
+After #metadata(none) <e>
+
+#locate(loc => {
+  let pos(label) = query(label, loc).first().location().position()
+  test(pos(<a>).page, 1)
+  test(pos(<b>).page, 1)
+  test(pos(<c>).page, 1)
+  test(pos(<e>).page, 1)
+  test(pos(<a>).y < pos(<b>).y, true)
+  test(pos(<b>).y < pos(<c>).y, true)
+  test(pos(<d>).page, 2)
+  test(pos(<d>).y, 10pt)
+})
+
+---
+// Test float pages.
+#set page(height: 150pt, margin: 10pt)
+Before
+#figure(placement: "page", rect(height: 40pt)[A]) <f1>
+#figure(placement: "page", rect(height: 40pt)[B]) <f2>
+After
+#figure(placement: (top, "page"), rect(height: 100pt)[C]) <f3>
+
+#locate(loc => {
+  let pos(label) = query(label, loc).first().location().position()
+  test((pos(<f1>).page, pos(<f2>).page, pos(<f3>).page), (2, 2, 3))
+  test(pos(<f1>).y > 20pt, true)
+  test(pos(<f1>).y < pos(<f2>).y, true)
+})
+
+---
+// Float pages fall back to the top for pages of automatic height.
+#set page(height: auto, margin: 10pt)
+#lorem(5)
+#figure(placement: "page", rect[A]) <f>
+
+#locate(loc => {
+  let pos = query(<f>, loc).first().location().position()
+  test(pos.page, 1)
+  test(pos.y, 10pt)
+})
+
+---
+// A placement makes a purely horizontal alignment valid.
+#set page(height: 100pt)
+#place(right, float: true, placement: bottom, rect[Right])
+#place(float: true, placement: ("here", top), rect[Start])
+
+---
+// Error: 32-38 must be `top` or `bottom`
+#place(float: true, placement: center)[Hello]
+
+---
+// Error: 32-34 placement must contain at least one position
+#place(float: true, placement: ())[Hello]
+
+---
+// Error: 20-27 expected "here", "page", alignment, array, auto, or none
+#figure(placement: "there", rect[A])
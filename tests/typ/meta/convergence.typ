@@ -0,0 +1,42 @@
+// Test introspections that never converge.
+// Ref: false
+
+---
+// A query that affects itself produces a document and a warning.
+// Warning: 0-0 layout did not converge within 5 passes
+// Hint: 0-0 the results of counters, states, or queries kept changing
+// Hint: 0-0 check whether an introspection affects its own result
+// Warning: 2-53 result kept changing between layout passes
+#locate(loc => query(heading, loc).len() * [= Fake])
+= Real
+
+---
+// A state that affects itself.
+// Warning: 0-0 layout did not converge within 5 passes
+// Hint: 0-0 the results of counters, states, or queries kept changing
+// Hint: 0-0 check whether an introspection affects its own result
+// Warning: 2:2-2:43 result kept changing between layout passes
+// Warning: 3:2-3:13 result kept changing between layout passes
+#let s = state("x", 1)
+#locate(loc => s.update(s.final(loc) + 1))
+#s.display()
+
+---
+// Limits: require-convergence
+// Error: 0-0 layout did not converge within 5 passes
+// Hint: 0-0 the results of counters, states, or queries kept changing
+// Hint: 0-0 check whether an introspection affects its own result
+// Error: 2-53 result kept changing between layout passes
+#locate(loc => query(heading, loc).len() * [= Fake])
+= Real
+
+---
+// Limits: layout-passes=2, require-convergence
+// Error: 0-0 layout did not converge within 2 passes
+// Hint: 0-0 the results of counters, states, or queries kept changing
+// Hint: 0-0 check whether an introspection affects its own result
+// Error: 2:2-2:43 result kept changing between layout passes
+// Error: 3:2-3:13 result kept changing between layout passes
+#let s = state("x", 1)
+#locate(loc => s.update(s.final(loc) + 1))
+#s.display()
@@ -0,0 +1,87 @@
+// Test endnotes and footnote numbering resets.
+// Ref: false
+
+---
+// Endnotes are collected per chapter.
+#set footnote(placement: "endnote", reset: 1)
+= Cats
+Cats purr.#footnote[Mostly.] <purr>
+And sleep.#footnote[A lot.]
+#endnotes()
+
+= Dogs
+Dogs bark.#footnote[Loudly.] Again.#footnote(<purr>)
+#endnotes(title: none)
+
+#locate(loc => {
+  let notes = query(footnote, loc)
+  test(notes.map(it => it.placement), ("endnote",) * 4)
+  test(notes.first().reset, 1)
+  test(counter(footnote).at(notes.at(1).location()), (2,))
+  test(counter(footnote).at(notes.at(2).location()), (1,))
+  test(query(endnotes, loc).len(), 2)
+})
+
+---
+// Mixing page footnotes and endnotes.
+Page#footnote[At the bottom.]
+and end#footnote(placement: "endnote")[At the end.]
+#endnotes(title: [Remarks], level: 1)
+
+---
+// Restart on each page.
+#set page(height: 100pt)
+#set footnote(reset: "page")
+A#footnote[One] <a>
+B#footnote[Two] <b>
+#pagebreak()
+C#footnote[One again] <c>
+
+#locate(loc => {
+  let at(label) = counter(footnote).at(query(label, loc).first().location())
+  test((at(<a>), at(<b>), at(<c>)), ((1,), (2,), (1,)))
+  test(query(<c>, loc).first().location().page(), 2)
+})
+
+---
+// Only headings of the given level or higher restart the numbering. The
+// title of a list of endnotes doesn't.
+#set footnote(reset: 1)
+= Chapter
+A#footnote[One] <a>
+== Section
+B#footnote[Two] <b>
+#endnotes(level: 1)
+C#footnote[Three] <c>
+= Next
+D#footnote[One again] <d>
+
+#locate(loc => {
+  let at(label) = counter(footnote).at(query(label, loc).first().location())
+  test((at(<a>), at(<b>), at(<c>), at(<d>)), ((1,), (2,), (3,), (1,)))
+})
+
+---
+// Restarts combine with manual counter updates.
+#set footnote(reset: 1)
+= One
+A#footnote[A] <a>
+#counter(footnote).update(5)
+B#footnote[B] <b>
+#counter(footnote).update(0)
+C#footnote[C] <c>
+= Two
+D#footnote[D] <d>
+
+#locate(loc => {
+  let at(label) = counter(footnote).at(query(label, loc).first().location())
+  test((at(<a>), at(<b>), at(<c>), at(<d>)), ((1,), (6,), (1,), (1,)))
+})
+
+---
+// Error: 26-33 expected "page" or "endnote"
+#set footnote(placement: "float")
+
+---
+// Error: 22-28 expected "page", integer, or none
+#set footnote(reset: "part")
@@ -0,0 +1,62 @@
+// Test the glossary.
+// Ref: false
+
+---
+// The first use shows the long form, later ones the short form. Only used terms
+// are listed.
+#show regex("Transport Layer Security|TLS|HTTP|OK|UNUSED"): it => {
+  [#metadata(it.text) <form>#it]
+}
+
+#glossary.entry(
+  "tls",
+  short: "TLS",
+  long: [Transport Layer Security],
+  description: [A protocol for secure connections.],
+)
+#glossary.entry("http", short: "HTTP", long: [Hypertext Transfer Protocol])
+#glossary.entry("unused", short: "UNUSED")
+#glossary.entry("ok", short: [OK])
+
+Servers speak @tls over @http.
+#pagebreak()
+Every #glossary.term("tls") connection starts with a handshake. @ok then.
+
+#locate(loc => {
+  test(counter(glossary.term.where(key: "tls")).at(loc), (2,))
+  test(counter(glossary.term.where(key: "http")).at(loc), (1,))
+  test(query(glossary.term, loc).map(it => it.key), ("tls", "http", "tls", "ok"))
+  test(query(<form>, loc).map(it => it.value), (
+    // The uses in the text.
+    "Transport Layer Security", "TLS", "HTTP", "TLS", "OK",
+    // The glossary, sorted by short form.
+    "HTTP", "OK", "TLS", "Transport Layer Security",
+  ))
+})
+
+#glossary()
+
+---
+// Restarting first uses.
+#show regex("Transport Layer Security|TLS"): it => [#metadata(it.text) <form>#it]
+#glossary.entry("tls", short: "TLS", long: [Transport Layer Security])
+@tls, @tls
+#counter(glossary.term.where(key: "tls")).update(0)
+@tls
+#glossary(title: none)
+
+#locate(loc => test(query(<form>, loc).map(it => it.value), (
+  "Transport Layer Security", "TLS", "TLS",
+  "Transport Layer Security", "TLS",
+  "TLS", "Transport Layer Security",
+)))
+
+---
+// Error: 2-23 glossary entry `nope` does not exist
+#glossary.term("nope")
+
+---
+#glossary.entry("a", short: "A")
+#glossary.entry("a", short: "B")
+// Error: 2-20 glossary entry `a` is declared multiple times
+#glossary.term("a")
@@ -0,0 +1,43 @@
+// Test the index.
+// Ref: false
+
+---
+#set page(numbering: "1")
+Cats#index("cat") purr and dogs#index("dog") bark.
+#pagebreak()
+Kittens#index("cat", sub: "kitten") are small cats.#index("cat")
+#pagebreak()
+Ähren#index("Ähre") and apples#index("apple").
+#index("pet", see-also: ("dog", "cat"))
+
+#locate(loc => {
+  let markers = query(index, loc)
+  test(markers.map(it => it.term), ("cat", "dog", "cat", "cat", "Ähre", "apple", "pet"))
+  test(markers.at(2).sub, "kitten")
+  test(markers.last().see-also, ("dog", "cat"))
+})
+
+#make-index()
+
+---
+// Titles and sorting in other languages. Pages are merged into ranges and link
+// to the markers.
+#set page(numbering: "1")
+#set text(lang: "sv")
+#show regex("zebra|ål|älg|ödla"): it => [#metadata(it.text) <term>#it]
+#show link: it => [#metadata(it.dest.page()) <dest>#it]
+Animals#index("ödla")#index("zebra")
+#pagebreak()
+Animals#index("ödla")#index("ål")
+#pagebreak()
+Animals#index("ödla")#index("älg")
+#make-index(title: [Sakregister], indent: 2em)
+
+#locate(loc => {
+  test(query(<term>, loc).map(it => it.value), ("zebra", "ål", "älg", "ödla"))
+  test(query(<dest>, loc).map(it => it.value), (1, 2, 3, 1, 3))
+})
+
+---
+// Error: 2-9 missing argument: term
+#index()
@@ -0,0 +1,49 @@
+// Test page marks and page selectors.
+// Ref: false
+
+---
+#set page(height: 100pt, header: locate(loc => {
+  let marks = marks(heading, loc)
+  if marks.first != none [#marks.first.body -- #marks.last.body]
+}))
+
+#locate(loc => {
+  let marks = marks(heading, loc)
+  test(marks.first.body, [Aardvark])
+  test(marks.last.body, [Abacus])
+  test(marks.previous, none)
+})
+
+= Aardvark
+= Abacus
+#pagebreak()
+#lorem(10)
+#locate(loc => {
+  // No heading starts on this page.
+  let marks = marks(heading, loc)
+  test(marks.first.body, [Abacus])
+  test(marks.last.body, [Abacus])
+  test(marks.previous.body, [Abacus])
+})
+#pagebreak()
+= Abbey
+
+#locate(loc => {
+  test(query(selector(heading).page(1), loc).map(it => it.body), ([Aardvark], [Abacus]))
+  test(query(selector(heading).page(2), loc), ())
+  test(query(selector(heading).page(3), loc).len(), 1)
+
+  let marks = marks(heading.where(level: 1), loc)
+  test(marks.first.body, [Abbey])
+  test(marks.previous.body, [Abacus])
+})
+
+---
+#locate(loc => test(marks(heading, loc), (first: none, last: none, previous: none)))
+
+---
+#test(repr(selector(heading).page(2)), "heading.page(2)")
+
+---
+// Error: 25-26 number must be positive
+#selector(heading).page(0)
@@ -0,0 +1,23 @@
+// Test the metadata element.
+// Ref: false
+
+---
+#metadata("hello") <note>
+#metadata((version: 2, authors: ("A", "B")))
+
+#locate(loc => {
+  test(query(<note>, loc).first().value, "hello")
+  let all = query(metadata, loc)
+  test(all.len(), 2)
+  test(all.last().value.version, 2)
+  test(all.last().value.authors, ("A", "B"))
+})
+
+---
+// Metadata is invisible and doesn't interrupt text.
+A#metadata(1)B
+#test(metadata(none).value, none)
+
+---
+// Error: 2-12 missing argument: value
+#metadata()
@@ -0,0 +1,51 @@
+// Test page references.
+// Ref: false
+
+---
+// The page number follows the numbering of the referenced page.
+#show ref.where(form: "page"): it => {
+  show regex("^[0-9ivx]+$"): n => [#metadata(n.text) <number>#n]
+  it
+}
+
+#set page(numbering: "i")
+#set heading(numbering: "1.")
+
+= Preface <preface>
+#pagebreak()
+
+#set page(numbering: "1")
+#counter(page).update(1)
+= Intro <intro>
+#figure(rect[Robot], caption: [A robot]) <robot>
+
+See @robot on #ref(<robot>, form: "page").
+The preface starts on #ref(<preface>, form: "page").
+
+#locate(loc => test(query(<number>, loc).map(it => it.value), ("1", "i")))
+
+---
+// Page references to unnumbered elements.
+#show ref: it => {
+  test(it.form, "page")
+  it
+}
+
+#figure(rect[Robot], numbering: none, caption: [A robot]) <robot>
+#ref(<robot>, form: "page")
+
+---
+// Per-form supplements.
+#show ref.where(form: "page"): set ref(supplement: [p.])
+#set heading(numbering: "1.")
+= Intro <intro>
+@intro is on #ref(<intro>, form: "page") and
+#ref(<intro>, form: "page", supplement: none).
+
+---
+// Error: 2-26 label does not exist in the document
+#ref(<foo>, form: "page")
+
+---
+// Error: 17-23 expected "normal" or "page"
+#ref(<a>, form: "none")
@@ -0,0 +1,44 @@
+// Test CJK-Latin spacing.
+
+---
+// Ref: false
+#set page(width: 50pt + 10pt, margin: (x: 5pt))
+#set text(lang: "zh", font: "Noto Serif CJK SC", cjk-latin-spacing: auto)
+#set par(justify: true)
+
+中文，中12文1中，文12中文
+
+中文，中ab文a中，文ab中文
+
+#set text(cjk-latin-spacing: none)
+中文，中12文1中，文12中文
+
+中文，中ab文a中，文ab中文
+
+---
+// Ref: false
+// Spacing across text items and at line edges.
+#set page(width: 60pt)
+#set text(font: ("Linux Libertine", "Noto Serif CJK SC"), cjk-latin-spacing: auto)
+中文#text(fill: red)[abc]中文【括号】〔龟甲〕
+
+---
+// Ref: false
+// A quarter em is added between CJK and Latin text, also across text items.
+// Consecutive marks are compressed in the GB and Japanese styles, but keep
+// their full width in the Taiwan style. Autospacing is off by default.
+#set text(lang: "zh", font: ("Linux Libertine", "Noto Serif CJK SC"))
+#style(styles => {
+  let width(body) = measure(body, styles).width
+  let extra(body) = calc.round((width(body) - width[中] - width[a]) / 1pt, digits: 2)
+  test(extra[中a], 0.0)
+  test(extra(text(cjk-latin-spacing: auto)[中a]), 2.75)
+  test(extra(text(cjk-latin-spacing: auto)[中#text(fill: red)[a]]), 2.75)
+  test(width[中，「中], 38.5pt)
+  test(width(text(lang: "ja")[中，「中]), 38.5pt)
+  test(width(text(region: "TW")[中，「中]), 44pt)
+})
+
+---
+// Error: 30-33 expected none or auto, found length
+#set text(cjk-latin-spacing: 1pt)
@@ -77,3 +77,13 @@ The keyword ```rust let```.
 // Unterminated.
 // Error: 1-2:1 unclosed raw text
 `endless
+
+---
+// Test raw text from bytes.
+// Ref: false
+#let it = raw(bytes("fn main() {}"), lang: "rust")
+#test(it.text, "fn main() {}")
+
+---
+// Error: 6-19 bytes are not valid utf-8
+#raw(bytes((255,)))
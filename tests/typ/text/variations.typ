@@ -0,0 +1,30 @@
+// Test variable font axis values.
+
+---
+// Ref: false
+// Fonts without variation axes ignore them.
+#set text(variations: ("wght": 600, "GRAD": 100))
+#text(weight: 450)[Medium] \
+#text(variations: (opsz: 12))[Optical size]
+
+---
+// Ref: false
+// The weight and explicit variations pick an instance of a variable font.
+#set text(font: "Variable Test", size: 100pt)
+#style(styles => {
+  let width(body) = calc.round(measure(body, styles).width / 1pt, digits: 1)
+  test(width[I], 30.0)
+  test(width(text(weight: 450)[I]), 32.0)
+  test(width(text(weight: "black")[I]), 50.0)
+  test(width(text(weight: "thin")[I]), 22.5)
+  test(width(text(variations: (wght: 650))[I]), 40.0)
+  test(width(text(weight: 900, variations: (wght: 400))[I]), 30.0)
+})
+
+---
+// Error: 23-37 expected float, found string
+#set text(variations: (wght: "bold"))
+
+---
+// Error: 23-28 expected dictionary, found boolean
+#set text(variations: false)
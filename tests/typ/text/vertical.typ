@@ -0,0 +1,70 @@
+// Test vertical writing.
+
+---
+// Ref: false
+#set page(width: 120pt, height: 100pt)
+#set text(dir: ttb, lang: "ja", font: "Noto Serif CJK JP")
+吾輩は猫である。名前はまだ無い。「Typst」で縦書き、令和5年12月31日。
+#text(orientation: "upright")[ABC]
+#text(orientation: "sideways")[日本]
+#text(combine-upright: none)[12]
+
+---
+// Ref: false
+// Lines run from top to bottom and progress from right to left.
+#set page(width: 100pt, height: 80pt, margin: 10pt)
+#set text(dir: ttb)
+#locate(loc => test(loc.position().x > 50pt, true))
+
+---
+// Ref: false
+// Characters within a line follow each other downwards and the next line
+// starts to the left of the previous one.
+#set page(width: 120pt, height: 100pt, margin: 10pt)
+#set text(dir: ttb, lang: "ja", font: "Noto Serif CJK JP", size: 10pt)
+#let mark = counter("mark").update(0)
+#mark <a>吾輩#mark <b>は猫 \
+#mark <c>名前
+
+#locate(loc => {
+  let pos(label) = query(label, loc).first().location().position()
+  let close(a, b) = calc.abs((a - b).pt()) < 0.01
+  test(close(pos(<b>).x, pos(<a>).x), true)
+  test(close(pos(<b>).y - pos(<a>).y, 20pt), true)
+  test(close(pos(<c>).y, pos(<a>).y), true)
+  test(pos(<c>).x < pos(<a>).x - 5pt, true)
+})
+
+---
+// Ref: false
+// Upright characters advance by a full cell, rotated ones by their width and
+// combined digits share a single cell (tate-chu-yoko).
+#set page(width: 120pt, height: 100pt, margin: 10pt)
+#set text(dir: ttb, lang: "ja", font: "Noto Serif CJK JP", size: 10pt)
+#let mark = counter("mark").update(0)
+#text(orientation: "upright")[#mark <a>ABC#mark <b>] \
+#text(orientation: "sideways")[#mark <c>ABC#mark <d>] \
+#text(combine-upright: 3)[#mark <e>123#mark <f>] \
+#text(combine-upright: none)[#mark <g>123#mark <h>]
+
+#locate(loc => {
+  let y(label) = query(label, loc).first().location().position().y
+  let close(a, b) = calc.abs((a - b).pt()) < 0.01
+  test(close(y(<b>) - y(<a>), 30pt), true)
+  test(y(<d>) - y(<c>) > 15pt and y(<d>) - y(<c>) < 25pt, true)
+  test(close(y(<f>) - y(<e>), 10pt), true)
+  test(close(y(<h>) - y(<g>), 16.17pt), true)
+})
+
+---
+// Error: 17-19 vertical text must be set up for the whole page
+#text(dir: ttb)[Hi]
+
+---
+// A direction change in the middle of a page can't make it vertical.
+// Error: 24-25 vertical text must be set up for the whole page
+A #set text(dir: ttb); B
+
+---
+// Error: 24-28 expected "mixed", "upright", or "sideways"
+#set text(orientation: "up")
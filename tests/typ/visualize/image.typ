@@ -60,3 +60,17 @@ A #box(image("/files/tiger.jpg", height: 1cm, width: 80%)) B
 ---
 // Error: 2-25 failed to parse svg: found closing tag 'g' instead of 'style' in line 4
 #image("/files/bad.svg")
+
+
+---
+// Test decoding images from strings and bytes.
+// Ref: false
+#set page(height: 60pt)
+#let svg = read("/files/monkey.svg")
+#box(image.decode(svg, height: 30pt))
+#box(image.decode(bytes(svg), format: "svg", height: 30pt))
+#box(image.decode(read("/files/rhino.png", encoding: none), height: 30pt))
+
+---
+// Error: 2-32 unknown image format
+#image.decode(bytes((1, 2, 3)))